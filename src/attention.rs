@@ -6,6 +6,17 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use chrono::{DateTime, Utc};
+use crate::invariants::{check_range, InvariantViolation};
+use crate::text_util::{contains_keyword, Tokenizer};
+
+/// Attention shifts kept in the history
+const MAX_ATTENTION_HISTORY: usize = 100;
+/// Shifts considered when working out the dominant recent target
+const RECENT_ATTENTION_WINDOW: usize = 20;
+/// Weight kept per step back in the recent window, so newer shifts count more
+const RECENT_ATTENTION_DECAY: f64 = 0.9;
+/// Score added to a suggestion for the dominant recent target, so sticky topics hold focus
+const STICKY_TARGET_BOOST: f64 = 0.1;
 
 /// Different types of stimuli that can capture attention
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -30,6 +41,8 @@ pub enum AttentionTarget {
     SocialDynamics,
     /// Focus on environmental patterns
     EnvironmentalAwareness,
+    /// Focus on an arbitrary named concept, e.g. a domain term
+    Custom(String),
 }
 
 /// Represents the strength and characteristics of attention toward a target
@@ -55,69 +68,180 @@ impl AttentionState {
         }
     }
 
-    /// Update the attention state over time
+    /// Report intensity, stability or salience values that are NaN or outside [0, 1]
+    pub fn check_invariants(&self, label: &str) -> Vec<InvariantViolation> {
+        let mut violations = Vec::new();
+        check_range(&mut violations, "attention", &format!("{}.intensity", label), self.intensity, 0.0, 1.0);
+        check_range(&mut violations, "attention", &format!("{}.stability", label), self.stability, 0.0, 1.0);
+        check_range(&mut violations, "attention", &format!("{}.salience", label), self.salience, 0.0, 1.0);
+        violations
+    }
+
+    /// Update the attention state over time with the default decay
     pub fn update(&mut self, time_delta_minutes: f64) {
+        self.update_with_config(time_delta_minutes, &AttentionConfig::default());
+    }
+
+    /// Update the attention state over time
+    pub fn update_with_config(&mut self, time_delta_minutes: f64, config: &AttentionConfig) {
         self.duration += time_delta_minutes;
         self.last_updated = Utc::now();
 
         // Attention naturally decays over time unless reinforced
-        self.intensity *= (1.0 - 0.01 * time_delta_minutes).max(0.0);
+        self.intensity *= (1.0 - config.intensity_decay_per_minute * time_delta_minutes).max(0.0);
         
         // Stability increases with sustained attention
-        if self.intensity > 0.5 {
-            self.stability += 0.02 * time_delta_minutes;
+        if self.intensity > config.stable_intensity {
+            self.stability += config.stability_gain_per_minute * time_delta_minutes;
             self.stability = self.stability.min(1.0);
         }
     }
 }
 
+/// Tuning for how attention decays, stabilizes and shifts
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttentionConfig {
+    /// Fraction of intensity lost per minute without reinforcement
+    pub intensity_decay_per_minute: f64,
+    /// Stability gained per minute while intensity stays above `stable_intensity`
+    pub stability_gain_per_minute: f64,
+    pub stable_intensity: f64,
+    /// How salient something must be to break focus
+    pub distraction_threshold: f64,
+    /// How intense attention must be to become a focus
+    pub focus_threshold: f64,
+    pub max_background_targets: usize,
+    /// Total focus capacity shared by the active foci. Each focus costs at least
+    /// `focus_threshold`, so the default of 1.0 holds a single focus.
+    pub attention_budget: f64,
+}
+
+impl Default for AttentionConfig {
+    fn default() -> Self {
+        AttentionConfig {
+            intensity_decay_per_minute: 0.01,
+            stability_gain_per_minute: 0.02,
+            stable_intensity: 0.5,
+            distraction_threshold: 0.7,
+            focus_threshold: 0.6,
+            max_background_targets: 5,
+            attention_budget: 1.0,
+        }
+    }
+}
+
 /// Manages the AI's attention and focus mechanisms
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttentionSystem {
-    /// Current primary focus
-    primary_focus: Option<AttentionState>,
+    /// Targets currently held in focus, most intense first
+    active_foci: Vec<AttentionState>,
     /// Secondary attention targets (background awareness)
+    #[serde(with = "background_as_list")]
     background_attention: HashMap<AttentionTarget, AttentionState>,
-    /// History of attention shifts
+    /// History of attention shifts, oldest first, capped at `MAX_ATTENTION_HISTORY`
     attention_history: Vec<(DateTime<Utc>, AttentionTarget, f64)>,
     /// Parameters controlling attention behavior
-    max_background_targets: usize,
-    distraction_threshold: f64,
-    focus_threshold: f64,
+    #[serde(flatten)]
+    config: AttentionConfig,
+    /// Splits context text for `suggest_attention_targets`; not serialized
+    #[serde(skip)]
+    tokenizer: Tokenizer,
+}
+
+/// JSON object keys must be strings, so background attention is stored as a list of states,
+/// each of which already carries its target
+mod background_as_list {
+    use super::{AttentionState, AttentionTarget};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+
+    pub fn serialize<S: Serializer>(map: &HashMap<AttentionTarget, AttentionState>, serializer: S) -> Result<S::Ok, S::Error> {
+        map.values().collect::<Vec<_>>().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HashMap<AttentionTarget, AttentionState>, D::Error> {
+        let states = Vec::<AttentionState>::deserialize(deserializer)?;
+        Ok(states.into_iter().map(|state| (state.target.clone(), state)).collect())
+    }
 }
 
 impl AttentionSystem {
     pub fn new() -> Self {
+        Self::with_config(AttentionConfig::default())
+    }
+
+    /// Attention system with explicit tuning; rates and thresholds are clamped to [0, 1]
+    pub fn with_config(config: AttentionConfig) -> Self {
         AttentionSystem {
-            primary_focus: None,
+            active_foci: Vec::new(),
             background_attention: HashMap::new(),
             attention_history: Vec::new(),
-            max_background_targets: 5,
-            distraction_threshold: 0.7, // How salient something must be to break focus
-            focus_threshold: 0.6,       // How intense attention must be to become primary focus
+            config: AttentionConfig {
+                intensity_decay_per_minute: config.intensity_decay_per_minute.clamp(0.0, 1.0),
+                stability_gain_per_minute: config.stability_gain_per_minute.clamp(0.0, 1.0),
+                stable_intensity: config.stable_intensity.clamp(0.0, 1.0),
+                distraction_threshold: config.distraction_threshold.clamp(0.0, 1.0),
+                focus_threshold: config.focus_threshold.clamp(0.0, 1.0),
+                attention_budget: config.attention_budget.max(0.0),
+                ..config
+            },
+            tokenizer: Tokenizer::default(),
         }
     }
 
+    /// Attention system that can divide its focus across targets within `attention_budget`
+    pub fn with_budget(attention_budget: f64) -> Self {
+        Self::with_config(AttentionConfig { attention_budget, ..AttentionConfig::default() })
+    }
+
+    /// Tokenizer used to match context text against attention targets
+    pub fn set_tokenizer(&mut self, tokenizer: Tokenizer) {
+        self.tokenizer = tokenizer;
+    }
+
+    pub fn config(&self) -> &AttentionConfig {
+        &self.config
+    }
+
+    /// Budget a focus occupies: its intensity, but never less than the focus threshold
+    fn focus_cost(&self, focus: &AttentionState) -> f64 {
+        focus.intensity.max(self.config.focus_threshold)
+    }
+
+    fn sort_foci(&mut self) {
+        self.active_foci.sort_by(|a, b| b.intensity.partial_cmp(&a.intensity).unwrap_or(std::cmp::Ordering::Equal));
+    }
+
     /// Direct attention toward a specific target
     pub fn focus_on(&mut self, target: AttentionTarget, intensity: f64, salience: f64) {
         let new_attention = AttentionState::new(target.clone(), intensity, salience);
         
         // Record attention shift
         self.attention_history.push((Utc::now(), target.clone(), intensity));
+        if self.attention_history.len() > MAX_ATTENTION_HISTORY {
+            let excess = self.attention_history.len() - MAX_ATTENTION_HISTORY;
+            self.attention_history.drain(..excess);
+        }
         
-        // If this is intense enough, make it the primary focus
-        if intensity >= self.focus_threshold {
-            // Move current primary focus to background if it exists
-            if let Some(current_focus) = &self.primary_focus {
-                if current_focus.intensity > 0.3 {
-                    self.background_attention.insert(
-                        current_focus.target.clone(), 
-                        current_focus.clone()
-                    );
+        // If this is intense enough, bring it into focus
+        if intensity >= self.config.focus_threshold {
+            self.active_foci.retain(|focus| focus.target != target);
+
+            // Move the weakest foci to the background until the new one fits the budget
+            let needed = self.focus_cost(&new_attention);
+            while !self.active_foci.is_empty()
+                && self.active_foci.iter().map(|f| self.focus_cost(f)).sum::<f64>() + needed > self.config.attention_budget + 1e-9 {
+                let displaced = self.active_foci.pop().expect("foci are non-empty");
+                if displaced.intensity > 0.3 {
+                    self.background_attention.insert(displaced.target.clone(), displaced);
                 }
             }
-            
-            self.primary_focus = Some(new_attention);
+
+            self.background_attention.remove(&target);
+            self.active_foci.push(new_attention);
+            self.sort_foci();
+            self.prune_background_attention();
             println!("🎯 Primary Focus Shift -> {:?} (Intensity: {:.2})", target, intensity);
         } else {
             // Add to background attention
@@ -130,13 +254,13 @@ impl AttentionSystem {
     pub fn evaluate_attention_shift(&mut self, stimuli: Vec<(AttentionTarget, f64)>) {
         for (target, salience) in stimuli {
             // Check if this stimulus is salient enough to break current focus
-            if let Some(current_focus) = &self.primary_focus {
-                if salience > self.distraction_threshold && 
+            if let Some(current_focus) = self.active_foci.first() {
+                if salience > self.config.distraction_threshold && 
                    salience > current_focus.intensity + current_focus.stability {
                     self.focus_on(target, salience, salience);
                     break;
                 }
-            } else if salience > self.focus_threshold {
+            } else if salience > self.config.focus_threshold {
                 // No current focus, so establish one if salience is high enough
                 self.focus_on(target, salience, salience);
             }
@@ -145,21 +269,21 @@ impl AttentionSystem {
 
     /// Update attention states over time
     pub fn update(&mut self, time_delta_minutes: f64) {
-        // Update primary focus
-        if let Some(focus) = &mut self.primary_focus {
-            focus.update(time_delta_minutes);
-            
-            // If primary focus becomes too weak, remove it
-            if focus.intensity < 0.1 {
-                self.primary_focus = None;
-                println!("🔄 Primary focus lost due to low intensity");
-            }
+        // Update active foci, dropping any that become too weak
+        for focus in &mut self.active_foci {
+            focus.update_with_config(time_delta_minutes, &self.config);
+        }
+        let before = self.active_foci.len();
+        self.active_foci.retain(|focus| focus.intensity >= 0.1);
+        if self.active_foci.len() < before {
+            println!("🔄 Focus lost due to low intensity");
         }
+        self.sort_foci();
 
         // Update background attention
         let mut to_remove = Vec::new();
         for (target, state) in &mut self.background_attention {
-            state.update(time_delta_minutes);
+            state.update_with_config(time_delta_minutes, &self.config);
             if state.intensity < 0.05 {
                 to_remove.push(target.clone());
             }
@@ -171,9 +295,34 @@ impl AttentionSystem {
         }
     }
 
-    /// Get the current primary focus
+    /// Check every attention state for out-of-range values
+    pub fn check_invariants(&self) -> Vec<InvariantViolation> {
+        let mut violations = Vec::new();
+
+        for focus in &self.active_foci {
+            violations.extend(focus.check_invariants(&format!("focus {:?}", focus.target)));
+            if focus.intensity <= 0.0 {
+                violations.push(InvariantViolation::new(
+                    "attention",
+                    format!("primary focus {:?} has zero intensity", focus.target),
+                ));
+            }
+        }
+        for (target, state) in &self.background_attention {
+            violations.extend(state.check_invariants(&format!("background {:?}", target)));
+        }
+
+        violations
+    }
+
+    /// Get the current primary focus, i.e. the most intense active focus
     pub fn get_primary_focus(&self) -> Option<&AttentionState> {
-        self.primary_focus.as_ref()
+        self.active_foci.first()
+    }
+
+    /// Get every target currently in focus, most intense first
+    pub fn get_active_foci(&self) -> Vec<&AttentionState> {
+        self.active_foci.iter().collect()
     }
 
     /// Get all background attention targets
@@ -185,7 +334,7 @@ impl AttentionSystem {
     pub fn generate_attention_modifiers(&self) -> Vec<String> {
         let mut modifiers = Vec::new();
 
-        if let Some(focus) = &self.primary_focus {
+        for focus in &self.active_foci {
             match &focus.target {
                 AttentionTarget::UserEmotion => {
                     modifiers.push("Pay special attention to the user's emotional state".to_string());
@@ -205,6 +354,9 @@ impl AttentionSystem {
                 AttentionTarget::Learning => {
                     modifiers.push("Focus on understanding and acquiring new knowledge".to_string());
                 },
+                AttentionTarget::Custom(name) => {
+                    modifiers.push(format!("Keep focused on {}", name));
+                },
                 _ => {}
             }
         }
@@ -227,12 +379,27 @@ impl AttentionSystem {
         modifiers
     }
 
+    /// The target that has drawn the most attention lately: intensities over the last
+    /// `RECENT_ATTENTION_WINDOW` shifts, each step back weighted down by `RECENT_ATTENTION_DECAY`
+    pub fn dominant_recent_target(&self) -> Option<AttentionTarget> {
+        let mut scores: HashMap<&AttentionTarget, f64> = HashMap::new();
+        let mut weight = 1.0;
+        for (_, target, intensity) in self.attention_history.iter().rev().take(RECENT_ATTENTION_WINDOW) {
+            *scores.entry(target).or_insert(0.0) += intensity * weight;
+            weight *= RECENT_ATTENTION_DECAY;
+        }
+        scores.into_iter()
+            .filter(|(_, score)| *score > 0.0)
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(target, _)| target.clone())
+    }
+
     /// Analyze attention patterns for insights
     pub fn analyze_attention_patterns(&self) -> Vec<String> {
         let mut insights = Vec::new();
 
         // Analyze attention stability
-        if let Some(focus) = &self.primary_focus {
+        if let Some(focus) = self.active_foci.first() {
             if focus.duration > 10.0 {
                 insights.push(format!("I've been deeply focused on {:?} for {:.1} minutes", 
                                     focus.target, focus.duration));
@@ -253,10 +420,14 @@ impl AttentionSystem {
         }
 
         // Analyze recent attention shifts
-        if self.attention_history.len() > 5 {
-            let recent_shifts = self.attention_history.len().saturating_sub(5);
+        let recent = &self.attention_history[self.attention_history.len().saturating_sub(RECENT_ATTENTION_WINDOW)..];
+        let recent_shifts = recent.windows(2).filter(|pair| pair[0].1 != pair[1].1).count();
+        if recent_shifts > 5 {
             insights.push(format!("I've shifted attention {} times recently", recent_shifts));
         }
+        if let Some(target) = self.dominant_recent_target() {
+            insights.push(format!("My attention keeps returning to {:?}", target));
+        }
 
         insights
     }
@@ -264,34 +435,55 @@ impl AttentionSystem {
     /// Suggest what the AI should focus on based on current context
     pub fn suggest_attention_targets(&self, context: &str) -> Vec<(AttentionTarget, f64)> {
         let mut suggestions = Vec::new();
-        let context_lower = context.to_lowercase();
+        let tokens = self.tokenizer.tokenize(context);
+        let mentions = |keywords: &[&str]| keywords.iter().any(|k| contains_keyword(&tokens, k));
 
         // Analyze context for attention-worthy elements
-        if context_lower.contains("problem") || context_lower.contains("issue") {
+        if mentions(&["problem", "issue"]) {
             suggestions.push((AttentionTarget::ProblemSolving, 0.8));
         }
 
-        if context_lower.contains("feel") || context_lower.contains("emotion") {
+        if mentions(&["feel", "emotion"]) {
             suggestions.push((AttentionTarget::UserEmotion, 0.7));
         }
 
-        if context_lower.contains("learn") || context_lower.contains("understand") {
+        if mentions(&["learn", "understand"]) {
             suggestions.push((AttentionTarget::Learning, 0.6));
         }
 
-        if context_lower.contains("creative") || context_lower.contains("idea") {
+        if mentions(&["creative", "idea"]) {
             suggestions.push((AttentionTarget::CreativeThinking, 0.7));
         }
 
+        // Custom concepts already being attended to are reinforced when mentioned again
+        let custom_targets = self.active_foci.iter()
+            .map(|focus| &focus.target)
+            .chain(self.background_attention.keys());
+        for target in custom_targets {
+            if let AttentionTarget::Custom(name) = target {
+                let name_tokens = self.tokenizer.tokenize(name);
+                if !name_tokens.is_empty() && name_tokens.iter().all(|t| contains_keyword(&tokens, t)) {
+                    suggestions.push((target.clone(), 0.7));
+                }
+            }
+        }
+
         // Always maintain some self-awareness
         suggestions.push((AttentionTarget::SelfEmotion, 0.4));
 
+        // What has held attention lately gets a little extra pull when it comes up again
+        if let Some(dominant) = self.dominant_recent_target() {
+            for (_, score) in suggestions.iter_mut().filter(|(target, _)| *target == dominant) {
+                *score = (*score + STICKY_TARGET_BOOST).min(1.0);
+            }
+        }
+
         suggestions
     }
 
     /// Prune background attention to stay within limits
     fn prune_background_attention(&mut self) {
-        if self.background_attention.len() > self.max_background_targets {
+        if self.background_attention.len() > self.config.max_background_targets {
             // Remove the weakest attention state
             let weakest = self.background_attention.iter()
                 .min_by(|a, b| a.1.intensity.partial_cmp(&b.1.intensity).unwrap())
@@ -305,15 +497,19 @@ impl AttentionSystem {
 
     /// Generate a narrative description of current attention state
     pub fn describe_attention_state(&self) -> String {
-        if let Some(focus) = &self.primary_focus {
+        if let Some(focus) = self.active_foci.first() {
             let focus_desc = match &focus.target {
-                AttentionTarget::UserEmotion => "how you're feeling",
-                AttentionTarget::ConversationTopic(topic) => &format!("our discussion about {}", topic),
-                AttentionTarget::SelfGoals => "my personal goals",
-                AttentionTarget::ProblemSolving => "solving the current problem",
-                AttentionTarget::CreativeThinking => "exploring creative possibilities",
-                AttentionTarget::Learning => "learning and understanding",
-                _ => "the current focus of our interaction"
+                AttentionTarget::UserEmotion => "how you're feeling".to_string(),
+                AttentionTarget::ConversationTopic(topic) => format!("our discussion about {}", topic),
+                AttentionTarget::SelfGoals => "my personal goals".to_string(),
+                AttentionTarget::SelfEmotion => "my own feelings".to_string(),
+                AttentionTarget::MemoryRecall => "recalling what we've shared before".to_string(),
+                AttentionTarget::ProblemSolving => "solving the current problem".to_string(),
+                AttentionTarget::CreativeThinking => "exploring creative possibilities".to_string(),
+                AttentionTarget::Learning => "learning and understanding".to_string(),
+                AttentionTarget::SocialDynamics => "the dynamics between us".to_string(),
+                AttentionTarget::EnvironmentalAwareness => "patterns in what's around us".to_string(),
+                AttentionTarget::Custom(name) => name.clone(),
             };
 
             let intensity_desc = if focus.intensity > 0.8 {
@@ -335,4 +531,161 @@ impl Default for AttentionSystem {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_every_primary_focus() {
+        let cases = [
+            (AttentionTarget::UserEmotion, "how you're feeling"),
+            (AttentionTarget::ConversationTopic("tides".to_string()), "our discussion about tides"),
+            (AttentionTarget::SelfGoals, "my personal goals"),
+            (AttentionTarget::SelfEmotion, "my own feelings"),
+            (AttentionTarget::MemoryRecall, "recalling what we've shared before"),
+            (AttentionTarget::ProblemSolving, "solving the current problem"),
+            (AttentionTarget::CreativeThinking, "exploring creative possibilities"),
+            (AttentionTarget::Learning, "learning and understanding"),
+            (AttentionTarget::SocialDynamics, "the dynamics between us"),
+            (AttentionTarget::EnvironmentalAwareness, "patterns in what's around us"),
+            (AttentionTarget::Custom("case law".to_string()), "case law"),
+        ];
+        for (target, description) in cases {
+            let mut attention = AttentionSystem::new();
+            attention.focus_on(target.clone(), 0.9, 0.9);
+            assert_eq!(attention.describe_attention_state(), format!("I'm deeply concentrated on {}", description), "{:?}", target);
+        }
+        assert_eq!(AttentionSystem::new().describe_attention_state(), "My attention feels scattered right now");
+    }
+
+    #[test]
+    fn test_default_budget_keeps_single_focus() {
+        let mut attention = AttentionSystem::new();
+        attention.focus_on(AttentionTarget::Learning, 0.7, 0.7);
+        attention.focus_on(AttentionTarget::ProblemSolving, 0.8, 0.8);
+
+        assert_eq!(attention.get_active_foci().len(), 1);
+        assert_eq!(attention.get_primary_focus().unwrap().target, AttentionTarget::ProblemSolving);
+        assert!(attention.get_background_attention().contains_key(&AttentionTarget::Learning));
+    }
+
+    #[test]
+    fn test_budget_allows_divided_attention() {
+        let mut attention = AttentionSystem::with_budget(1.5);
+        attention.focus_on(AttentionTarget::Learning, 0.6, 0.6);
+        attention.focus_on(AttentionTarget::UserEmotion, 0.7, 0.7);
+
+        let foci = attention.get_active_foci();
+        assert_eq!(foci.len(), 2);
+        assert_eq!(foci[0].target, AttentionTarget::UserEmotion);
+        assert_eq!(attention.get_primary_focus().unwrap().target, AttentionTarget::UserEmotion);
+        let modifiers = attention.generate_attention_modifiers();
+        assert!(modifiers.iter().any(|m| m.contains("emotional state")));
+        assert!(modifiers.iter().any(|m| m.contains("acquiring new knowledge")));
+
+        // A third focus exceeds the budget and displaces the weakest
+        attention.focus_on(AttentionTarget::ProblemSolving, 0.8, 0.8);
+        let targets: Vec<_> = attention.get_active_foci().iter().map(|f| f.target.clone()).collect();
+        assert_eq!(targets, vec![AttentionTarget::ProblemSolving, AttentionTarget::UserEmotion]);
+    }
+
+    #[test]
+    fn test_attention_system_json_roundtrip() {
+        let mut attention = AttentionSystem::with_budget(1.5);
+        attention.focus_on(AttentionTarget::Custom("case law".to_string()), 0.8, 0.8);
+        attention.focus_on(AttentionTarget::ConversationTopic("tides".to_string()), 0.4, 0.5);
+        attention.focus_on(AttentionTarget::SocialDynamics, 0.3, 0.3);
+
+        let json = serde_json::to_string(&attention).unwrap();
+        let restored: AttentionSystem = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.get_primary_focus().unwrap().target, AttentionTarget::Custom("case law".to_string()));
+        assert_eq!(restored.get_background_attention().len(), 2);
+        assert!(restored.get_background_attention().contains_key(&AttentionTarget::ConversationTopic("tides".to_string())));
+        assert_eq!(restored.config.attention_budget, 1.5);
+    }
+
+    #[test]
+    fn test_custom_target_as_map_key() {
+        let mut weights = HashMap::new();
+        weights.insert(AttentionTarget::Custom("contract law".to_string()), 1);
+        weights.insert(AttentionTarget::Custom("drug interactions".to_string()), 2);
+        weights.insert(AttentionTarget::ConversationTopic("contract law".to_string()), 3);
+
+        assert_eq!(weights.len(), 3);
+        assert_eq!(weights[&AttentionTarget::Custom("contract law".to_string())], 1);
+        assert_ne!(AttentionTarget::Custom("a".to_string()), AttentionTarget::ConversationTopic("a".to_string()));
+    }
+
+    #[test]
+    fn test_custom_target_modifiers_and_suggestions() {
+        let mut attention = AttentionSystem::new();
+        let target = AttentionTarget::Custom("drug interactions".to_string());
+        attention.focus_on(target.clone(), 0.8, 0.8);
+
+        assert!(attention.generate_attention_modifiers().contains(&"Keep focused on drug interactions".to_string()));
+        assert_eq!(attention.describe_attention_state(), "I'm focused on drug interactions");
+
+        let suggested = attention.suggest_attention_targets("Are there interactions between these drugs?");
+        assert!(suggested.iter().any(|(t, _)| *t == target));
+        let unrelated = attention.suggest_attention_targets("What's the weather like?");
+        assert!(!unrelated.iter().any(|(t, _)| *t == target));
+    }
+
+    #[test]
+    fn test_distraction_threshold_resists_shifts() {
+        let shift_with = |distraction_threshold: f64| {
+            let mut attention = AttentionSystem::with_config(AttentionConfig {
+                focus_threshold: 0.2,
+                distraction_threshold,
+                ..AttentionConfig::default()
+            });
+            attention.focus_on(AttentionTarget::Learning, 0.25, 0.5);
+            attention.evaluate_attention_shift(vec![(AttentionTarget::UserEmotion, 0.8)]);
+            attention.get_primary_focus().unwrap().target.clone()
+        };
+
+        assert_eq!(shift_with(AttentionConfig::default().distraction_threshold), AttentionTarget::UserEmotion);
+        assert_eq!(shift_with(0.9), AttentionTarget::Learning);
+    }
+
+    #[test]
+    fn test_configured_decay_keeps_focus_longer() {
+        let sticky = AttentionConfig { intensity_decay_per_minute: 0.002, ..AttentionConfig::default() };
+        let mut default_attention = AttentionSystem::new();
+        let mut sticky_attention = AttentionSystem::with_config(sticky);
+        for attention in [&mut default_attention, &mut sticky_attention] {
+            attention.focus_on(AttentionTarget::ProblemSolving, 0.8, 0.8);
+            attention.update(30.0);
+        }
+
+        let intensity = |attention: &AttentionSystem| attention.get_primary_focus().unwrap().intensity;
+        assert!((intensity(&default_attention) - 0.8 * 0.7).abs() < 1e-9);
+        assert!((intensity(&sticky_attention) - 0.8 * 0.94).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_history_is_capped_and_dominant_target_weighted() {
+        let mut attention = AttentionSystem::new();
+        assert_eq!(attention.dominant_recent_target(), None);
+
+        for _ in 0..150 {
+            attention.focus_on(AttentionTarget::Learning, 0.3, 0.3);
+        }
+        assert_eq!(attention.attention_history.len(), MAX_ATTENTION_HISTORY);
+        assert_eq!(attention.dominant_recent_target(), Some(AttentionTarget::Learning));
+
+        // A few intense recent shifts outweigh many faint older ones
+        for _ in 0..5 {
+            attention.focus_on(AttentionTarget::ProblemSolving, 0.9, 0.9);
+        }
+        assert_eq!(attention.dominant_recent_target(), Some(AttentionTarget::ProblemSolving));
+
+        // ...and it gets a boost when the context suggests it again
+        let suggested = attention.suggest_attention_targets("There's a problem with the build");
+        let score = suggested.iter().find(|(t, _)| *t == AttentionTarget::ProblemSolving).map(|(_, s)| *s);
+        assert_eq!(score, Some(0.8 + STICKY_TARGET_BOOST));
+    }
+}
@@ -3,23 +3,57 @@
 //! Defines a flexible structure for appraised emotions and provides
 //! functionality for appraising emotions from text.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use crate::{llm_api, memory::Memory};
+use crate::llm_api::LlmApiError;
+use crate::text_util::{tokenize, Tokenizer};
 
 /// **NEW**: A flexible structure to hold any appraised emotion from the LLM.
 /// The `OccEmotion` enum is no longer used for deserialization.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppraisedEmotion {
     /// The name of the emotion, as identified by the LLM (e.g., "Joy", "Apprehension", "Nostalgia").
     pub emotion: String,
     /// The mapped VADN coordinates for this emotion.
     pub vadn: AffectiveStateChange,
-    /// Any additional details the LLM provides.
+    /// Any additional details the LLM provides, usually `focus` and `reason`. May be absent.
+    #[serde(default)]
     pub details: serde_json::Value,
 }
 
+impl AppraisedEmotion {
+    /// What the emotion is about: `details.focus`, or the `referent` of a multi-facet appraisal
+    pub fn focus(&self) -> Option<&str> {
+        self.detail("focus").or_else(|| self.detail("referent"))
+    }
+
+    /// Why the appraiser chose this emotion (`details.reason`)
+    pub fn reason(&self) -> Option<&str> {
+        self.detail("reason")
+    }
+
+    /// The label with its focus and reason when known, e.g. "Apprehension about a new team (it's daunting)"
+    pub fn describe(&self) -> String {
+        let mut description = self.emotion.clone();
+        if let Some(focus) = self.focus() {
+            description.push_str(&format!(" about {}", focus));
+        }
+        if let Some(reason) = self.reason() {
+            description.push_str(&format!(" ({})", reason));
+        }
+        description
+    }
+
+    fn detail(&self, key: &str) -> Option<&str> {
+        self.details.get(key)
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    }
+}
+
 /// **NEW**: Represents the direct VADN change proposed by the LLM.
-#[derive(Debug, Clone, Deserialize, Copy)]
+#[derive(Debug, Clone, Serialize, Deserialize, Copy)]
 pub struct AffectiveStateChange {
     pub valence: f64,
     pub arousal: f64,
@@ -27,15 +61,179 @@ pub struct AffectiveStateChange {
     pub novelty: f64,
 }
 
+impl AffectiveStateChange {
+    /// A copy with arousal clamped to [0, 1] and the other dimensions to [-1, 1]
+    pub fn clamped(&self) -> Self {
+        AffectiveStateChange {
+            valence: self.valence.clamp(-1.0, 1.0),
+            arousal: self.arousal.clamp(0.0, 1.0),
+            dominance: self.dominance.clamp(-1.0, 1.0),
+            novelty: self.novelty.clamp(-1.0, 1.0),
+        }
+    }
+}
+
+/// Word stems for the local lexicon appraisal; tokens match by prefix
+const POSITIVE_STEMS: &[&str] = &[
+    "happ", "glad", "joy", "love", "great", "good", "wonderful", "amazing", "excit", "thank",
+    "grateful", "proud", "delight", "fantastic", "awesome", "beautiful", "fun", "hope", "calm", "relie",
+];
+const NEGATIVE_STEMS: &[&str] = &[
+    "sad", "angry", "anger", "hate", "terrible", "awful", "bad", "worr", "anxi", "afraid", "scar",
+    "fear", "upset", "depress", "lonely", "hurt", "frustrat", "disappoint", "miserable", "stress",
+];
+const HIGH_AROUSAL_STEMS: &[&str] = &[
+    "excit", "angry", "anger", "furious", "thrill", "panic", "scar", "amazing", "urgent", "stress", "terrif",
+];
+const LOW_AROUSAL_STEMS: &[&str] = &["calm", "tired", "bored", "relax", "sleepy", "peace", "quiet"];
+const NOVELTY_STEMS: &[&str] = &["surpris", "sudden", "unexpected", "shock", "new", "strange", "wow"];
+
+/// Whether an appraisal error means the LLM is unreachable, so the local heuristic should be used
+pub fn is_offline_error(error: &LlmApiError) -> bool {
+    matches!(error, LlmApiError::NetworkError(_) | LlmApiError::ApiKeyMissing)
+}
+
+/// Appraises a prompt offline with a small word-list heuristic. Far cruder than the LLM, but it
+/// keeps the affective core updating when no API is reachable.
+pub fn local_appraise(prompt: &str) -> AppraisedEmotion {
+    appraise_tokens(prompt, &tokenize(prompt))
+}
+
+/// `local_appraise` with a custom tokenizer, e.g. one configured with other stopwords
+pub fn local_appraise_with(prompt: &str, tokenizer: &Tokenizer) -> AppraisedEmotion {
+    appraise_tokens(prompt, &tokenizer.tokenize(prompt))
+}
+
+fn appraise_tokens(prompt: &str, tokens: &[String]) -> AppraisedEmotion {
+    let hits = |stems: &[&str]| {
+        tokens.iter()
+            .filter(|token| stems.iter().any(|stem| token.starts_with(stem)))
+            .count() as f64
+    };
+    let (positive, negative) = (hits(POSITIVE_STEMS), hits(NEGATIVE_STEMS));
+    let exclamations = prompt.matches('!').count().min(3) as f64;
+
+    let valence = ((positive - negative) / (positive + negative + 1.0)).clamp(-1.0, 1.0);
+    let arousal = (0.3 + 0.2 * hits(HIGH_AROUSAL_STEMS) - 0.1 * hits(LOW_AROUSAL_STEMS) + 0.1 * exclamations)
+        .clamp(0.0, 1.0);
+    let dominance = (0.3 * valence).clamp(-1.0, 1.0);
+    let novelty = (0.1 + 0.3 * hits(NOVELTY_STEMS)).clamp(-1.0, 1.0);
+
+    let emotion = match (valence, arousal) {
+        (v, a) if v > 0.2 && a > 0.5 => "Joy",
+        (v, _) if v > 0.2 => "Contentment",
+        (v, a) if v < -0.2 && a > 0.5 => "Distress",
+        (v, _) if v < -0.2 => "Sadness",
+        _ => "Neutral",
+    };
+
+    AppraisedEmotion {
+        emotion: emotion.to_string(),
+        vadn: AffectiveStateChange { valence, arousal, dominance, novelty },
+        details: serde_json::json!({
+            "source": "local_lexicon",
+            "positive_hits": positive,
+            "negative_hits": negative,
+        }),
+    }
+}
 
-/// Appraises the emotion from a user's prompt by calling the LLM.
+/// Appraises the emotion from a user's prompt by calling the LLM, falling back to
+/// `local_appraise` when the API is unreachable or `COGNO_OFFLINE` is set.
 pub async fn appraise_emotion_from_prompt(user_prompt: &str, memory: &Memory) -> Result<AppraisedEmotion, String> {
+    if llm_api::offline_from_env() {
+        return Ok(local_appraise(user_prompt));
+    }
     match llm_api::call_llm_for_appraisal(user_prompt, memory).await {
         Ok(emotion) => Ok(emotion),
+        Err(e) if e.downcast_ref::<LlmApiError>().is_some_and(is_offline_error) => {
+            eprintln!("📴 LLM unavailable ({}). Using local appraisal.", e);
+            Ok(local_appraise(user_prompt))
+        }
+        Err(e) => {
+            let err_msg = format!("🔥 Appraisal Error: {}. Falling back to Neutral.", e);
+            eprintln!("{}", err_msg);
+            Err(err_msg)
+        }
+    }
+}
+
+/// Appraises every distinct emotional facet of a user's prompt by calling the LLM, falling
+/// back to a single `local_appraise` facet when the API is unreachable or `COGNO_OFFLINE` is set.
+pub async fn appraise_emotions_from_prompt(user_prompt: &str, memory: &Memory) -> Result<Vec<AppraisedEmotion>, String> {
+    if llm_api::offline_from_env() {
+        return Ok(vec![local_appraise(user_prompt)]);
+    }
+    match llm_api::call_llm_for_multi_appraisal(user_prompt, memory).await {
+        Ok(emotions) => Ok(emotions),
+        Err(e) if e.downcast_ref::<LlmApiError>().is_some_and(is_offline_error) => {
+            eprintln!("📴 LLM unavailable ({}). Using local appraisal.", e);
+            Ok(vec![local_appraise(user_prompt)])
+        }
         Err(e) => {
             let err_msg = format!("🔥 Appraisal Error: {}. Falling back to Neutral.", e);
             eprintln!("{}", err_msg);
             Err(err_msg)
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_appraise_positive_prompts() {
+        for prompt in ["I'm so happy, this is wonderful news!", "Thank you, I love it"] {
+            let emotion = local_appraise(prompt);
+            assert!(emotion.vadn.valence > 0.2, "{}: {:?}", prompt, emotion.vadn);
+            assert!(matches!(emotion.emotion.as_str(), "Joy" | "Contentment"));
+        }
+    }
+
+    #[test]
+    fn test_local_appraise_negative_prompts() {
+        for prompt in ["I'm sad and lonely today", "This is terrible, I'm so angry and frustrated!"] {
+            let emotion = local_appraise(prompt);
+            assert!(emotion.vadn.valence < -0.2, "{}: {:?}", prompt, emotion.vadn);
+            assert!(matches!(emotion.emotion.as_str(), "Sadness" | "Distress"));
+        }
+        assert!(local_appraise("I'm furious and angry!").vadn.arousal > local_appraise("I feel sad and tired").vadn.arousal);
+    }
+
+    #[test]
+    fn test_details_focus_and_reason() {
+        let emotion: AppraisedEmotion = serde_json::from_str(r#"{
+            "emotion": "Apprehension",
+            "vadn": {"valence": -0.2, "arousal": 0.5, "dominance": -0.3, "novelty": 0.6},
+            "details": {"focus": "managing a new team", "reason": "a mix of hope and fear"}
+        }"#).unwrap();
+        assert_eq!(emotion.focus(), Some("managing a new team"));
+        assert_eq!(emotion.reason(), Some("a mix of hope and fear"));
+        assert_eq!(emotion.describe(), "Apprehension about managing a new team (a mix of hope and fear)");
+
+        let facet: AppraisedEmotion = serde_json::from_str(r#"{
+            "emotion": "Pride",
+            "vadn": {"valence": 0.7, "arousal": 0.6, "dominance": 0.5, "novelty": 0.3},
+            "details": {"referent": "the promotion"}
+        }"#).unwrap();
+        assert_eq!(facet.focus(), Some("the promotion"));
+        assert_eq!(facet.reason(), None);
+
+        let bare: AppraisedEmotion = serde_json::from_str(r#"{
+            "emotion": "Calm",
+            "vadn": {"valence": 0.1, "arousal": 0.1, "dominance": 0.0, "novelty": 0.0}
+        }"#).unwrap();
+        assert!(bare.details.is_null());
+        assert_eq!(bare.describe(), "Calm");
+    }
+
+    #[test]
+    fn test_local_appraise_neutral_prompt() {
+        let emotion = local_appraise("The meeting is on Tuesday");
+        assert_eq!(emotion.emotion, "Neutral");
+        assert_eq!(emotion.vadn.valence, 0.0);
+        assert!(is_offline_error(&LlmApiError::ApiKeyMissing));
+        assert!(!is_offline_error(&LlmApiError::EmptyResponse));
+    }
+}
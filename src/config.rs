@@ -0,0 +1,196 @@
+//! config.rs
+//!
+//! Loads personality, affective tuning, LLM settings, background loop intervals and stopwords
+//! from a TOML file (`cogno.toml`), so they can be changed without recompiling. Every section
+//! and key is optional; anything left out keeps its built-in default.
+
+use crate::continuous_mind::{BackgroundLoop, ContinuousMindConfig};
+use crate::core::{AffectiveConfig, AffectiveState};
+use crate::llm_api::{LlmApiConfig, LlmProvider};
+use crate::memory::Personality;
+use crate::text_util::Tokenizer;
+use serde::Deserialize;
+use std::io;
+use std::path::Path;
+use thiserror::Error;
+use tokio::time::Duration;
+
+/// File read when no `--config` path is given
+pub const DEFAULT_CONFIG_FILE: &str = "cogno.toml";
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("could not read config file: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("invalid config file: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("unknown LLM provider '{0}' (expected gemini, openai, anthropic or ollama)")]
+    UnknownProvider(String),
+}
+
+/// Everything a config file can set, with defaults filled in
+#[derive(Debug, Clone, Default)]
+pub struct CognoConfig {
+    pub personality: Personality,
+    pub affective: AffectiveConfig,
+    pub llm: LlmApiConfig,
+    pub mind: ContinuousMindConfig,
+}
+
+/// `[personality]`: the baseline mood plus social temperament
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PersonalitySection {
+    valence: Option<f64>,
+    arousal: Option<f64>,
+    dominance: Option<f64>,
+    novelty: Option<f64>,
+    extraversion: Option<f64>,
+}
+
+/// `[llm]`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LlmSection {
+    provider: Option<String>,
+    model: Option<String>,
+    reflection_model: Option<String>,
+    timeout_seconds: Option<u64>,
+    max_retries: Option<u32>,
+    temperature: Option<f64>,
+    token_budget: Option<u64>,
+    offline: Option<bool>,
+}
+
+/// `[intervals]`, in seconds
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct IntervalSection {
+    main_loop: Option<f64>,
+    thoughts: Option<f64>,
+    task_scheduler: Option<f64>,
+    memory_consolidation: Option<f64>,
+    creative_incubation: Option<f64>,
+    social_analysis: Option<f64>,
+    system_monitoring: Option<f64>,
+}
+
+/// `[text]`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TextSection {
+    /// Replaces the default English stopword list
+    stopwords: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    personality: PersonalitySection,
+    affective: AffectiveConfig,
+    llm: LlmSection,
+    intervals: IntervalSection,
+    text: TextSection,
+}
+
+impl CognoConfig {
+    /// Read and parse a config file
+    pub fn load(path: &Path) -> Result<CognoConfig, ConfigError> {
+        Self::from_toml_str(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn from_toml_str(text: &str) -> Result<CognoConfig, ConfigError> {
+        let file: ConfigFile = toml::from_str(text)?;
+        let mut config = CognoConfig { affective: file.affective, ..CognoConfig::default() };
+
+        let section = file.personality;
+        let neutral = AffectiveState::new_neutral();
+        config.personality.baseline_state = AffectiveState {
+            valence: section.valence.unwrap_or(neutral.valence),
+            arousal: section.arousal.unwrap_or(neutral.arousal),
+            dominance: section.dominance.unwrap_or(neutral.dominance),
+            novelty: section.novelty.unwrap_or(neutral.novelty),
+        }.clamped();
+        if let Some(extraversion) = section.extraversion {
+            config.mind.extraversion = extraversion.clamp(0.0, 1.0);
+        }
+
+        let llm = file.llm;
+        if let Some(name) = llm.provider {
+            config.llm.provider = Some(LlmProvider::from_name(&name).ok_or(ConfigError::UnknownProvider(name))?);
+        }
+        config.llm.model = llm.model.or(config.llm.model);
+        config.llm.reflection_model = llm.reflection_model.or(config.llm.reflection_model);
+        config.llm.timeout_seconds = llm.timeout_seconds.unwrap_or(config.llm.timeout_seconds);
+        config.llm.max_retries = llm.max_retries.unwrap_or(config.llm.max_retries);
+        config.llm.temperature = llm.temperature.or(config.llm.temperature);
+        config.llm.token_budget = llm.token_budget.or(config.llm.token_budget);
+        config.mind.offline = llm.offline.unwrap_or(config.mind.offline);
+
+        let intervals = file.intervals;
+        let overrides = [
+            (BackgroundLoop::Main, intervals.main_loop),
+            (BackgroundLoop::BackgroundThoughts, intervals.thoughts),
+            (BackgroundLoop::TaskScheduler, intervals.task_scheduler),
+            (BackgroundLoop::MemoryConsolidation, intervals.memory_consolidation),
+            (BackgroundLoop::CreativeIncubation, intervals.creative_incubation),
+            (BackgroundLoop::SocialAnalysis, intervals.social_analysis),
+            (BackgroundLoop::SystemMonitoring, intervals.system_monitoring),
+        ];
+        for (background_loop, seconds) in overrides {
+            if let Some(seconds) = seconds {
+                config.mind.set_interval(background_loop, Duration::from_secs_f64(seconds.max(0.001)));
+            }
+        }
+
+        if let Some(stopwords) = file.text.stopwords {
+            config.mind.tokenizer = Tokenizer::with_stopwords(stopwords);
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_config_parses() {
+        let config = CognoConfig::from_toml_str(include_str!("../cogno.example.toml")).unwrap();
+
+        assert_eq!(config.personality.baseline_state.valence, 0.2);
+        assert_eq!(config.mind.extraversion, 0.3);
+        assert_eq!(config.affective.decay_rate, 0.1);
+        assert_eq!(config.llm.provider, Some(LlmProvider::Gemini));
+        assert_eq!(config.llm.timeout_seconds, 20);
+        assert_eq!(config.mind.interval_for(BackgroundLoop::BackgroundThoughts), Duration::from_secs(30));
+        // Keys the sample leaves out keep their defaults
+        assert_eq!(config.mind.interval_for(BackgroundLoop::Main), Duration::from_millis(500));
+        assert_eq!(config.affective.mood_inertia, AffectiveConfig::default().mood_inertia);
+    }
+
+    #[test]
+    fn test_empty_config_is_default() {
+        let config = CognoConfig::from_toml_str("").unwrap();
+        assert_eq!(config.personality.baseline_state.arousal, AffectiveState::new_neutral().arousal);
+        assert_eq!(config.llm.max_retries, LlmApiConfig::default().max_retries);
+        assert!(!config.mind.offline);
+
+        assert!(CognoConfig::from_toml_str("[llm]\noffline = true").unwrap().mind.offline);
+
+        let spanish = CognoConfig::from_toml_str("[text]\nstopwords = [\"el\", \"la\"]").unwrap();
+        assert_eq!(spanish.mind.tokenizer.tokenize("the moon la luna"), vec!["the", "moon", "luna"]);
+    }
+
+    #[test]
+    fn test_config_errors() {
+        assert!(matches!(
+            CognoConfig::from_toml_str("[llm]\nprovider = \"skynet\""),
+            Err(ConfigError::UnknownProvider(name)) if name == "skynet"
+        ));
+        assert!(matches!(CognoConfig::from_toml_str("[personality]\nvalense = 0.2"), Err(ConfigError::Parse(_))));
+    }
+}
@@ -2,24 +2,57 @@
 //!
 //! Enhanced continuous, background mental processes with complete feature integration
 
-use crate::core::AffectiveCore;
-use crate::metacognition::{MetacognitiveMonitor, CognitiveProcess};
-use crate::goals::GoalSystem;
+use crate::cognitive_appraisal::AppraisedEmotion;
+use crate::core::{describe_valence, AffectiveConfig, AffectiveCore, AffectiveState, EmotionalArc};
+use crate::metacognition::{MetacognitiveMonitor, CognitiveProcess, ReflectionTrigger};
+use crate::goals::{GoalCategory, GoalSystem};
 use crate::attention::{AttentionSystem, AttentionTarget};
-use crate::llm_api::{LlmApiClient, LlmApiConfig, LlmApiError};
-use tokio::time::{interval, Duration, Instant};
-use tokio::sync::{Mutex as AsyncMutex, RwLock};
+use crate::invariants::{check_range, InvariantViolation};
+use crate::lock_util::lock_with_retry;
+use crate::memory::{Memory, Personality};
+use crate::llm_api::{offline_from_env, LlmApiClient, LlmApiConfig, LlmApiError, LlmBackend, OfflineLlmClient};
+use crate::text_util::{contains_keyword, Tokenizer};
+use crate::utils::pearson_correlation;
+use tokio::time::{interval, Duration, Instant, Interval};
+use tokio::sync::{broadcast, watch, Mutex as AsyncMutex, RwLock};
+use tokio::task::JoinSet;
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::io;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
 use futures::future::join_all;
-use tracing::{info, warn, error, debug};
-
-/// Simple random selection helper that avoids trait bound issues
-fn simple_random_choice<T: Clone>(choices: &[T]) -> T {
-    let random_val = (rand::random::<f64>() * 1000.0) as usize;
-    let index = random_val % choices.len();
-    choices[index].clone()
+use tracing::{info, warn, error, debug, trace, Level};
+use thiserror::Error;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// Pick a thought with probability proportional to its kind's weight (1.0 when unset).
+/// `roll` is a uniform sample in [0, 1). If every candidate has zero weight they count equally.
+fn weighted_thought_choice(
+    candidates: Vec<SpontaneousThought>,
+    weights: &HashMap<ThoughtKind, f64>,
+    roll: f64,
+) -> SpontaneousThought {
+    let weight_of = |thought: &SpontaneousThought| {
+        weights.get(&thought.kind()).copied().filter(|w| w.is_finite()).unwrap_or(1.0).max(0.0)
+    };
+    let total: f64 = candidates.iter().map(weight_of).sum();
+    let uniform = total <= 0.0;
+    let total = if uniform { candidates.len() as f64 } else { total };
+
+    let mut target = roll.clamp(0.0, 1.0) * total;
+    let last = candidates.len() - 1;
+    for (i, thought) in candidates.into_iter().enumerate() {
+        let weight = if uniform { 1.0 } else { weight_of(&thought) };
+        if i == last || (weight > 0.0 && target < weight) {
+            return thought;
+        }
+        target -= weight;
+    }
+    unreachable!("candidates is never empty")
 }
 
 /// Represents different types of spontaneous thoughts the AI can have
@@ -37,8 +70,56 @@ pub enum SpontaneousThought {
     SystemIntegration(String),
 }
 
+/// The kind of a `SpontaneousThought` without its content, for keying weights and counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ThoughtKind {
+    SelfReflection,
+    GoalReassessment,
+    MemoryRecall,
+    CreativeInsight,
+    EmotionalProcessing,
+    CuriosityDriven,
+    ExistentialWondering,
+    ErrorRecovery,
+    AttentionShift,
+    SystemIntegration,
+}
+
+impl SpontaneousThought {
+    pub fn kind(&self) -> ThoughtKind {
+        match self {
+            SpontaneousThought::SelfReflection(_) => ThoughtKind::SelfReflection,
+            SpontaneousThought::GoalReassessment(_) => ThoughtKind::GoalReassessment,
+            SpontaneousThought::MemoryRecall(_) => ThoughtKind::MemoryRecall,
+            SpontaneousThought::CreativeInsight(_) => ThoughtKind::CreativeInsight,
+            SpontaneousThought::EmotionalProcessing(_) => ThoughtKind::EmotionalProcessing,
+            SpontaneousThought::CuriosityDriven(_) => ThoughtKind::CuriosityDriven,
+            SpontaneousThought::ExistentialWondering(_) => ThoughtKind::ExistentialWondering,
+            SpontaneousThought::ErrorRecovery(_) => ThoughtKind::ErrorRecovery,
+            SpontaneousThought::AttentionShift(_) => ThoughtKind::AttentionShift,
+            SpontaneousThought::SystemIntegration(_) => ThoughtKind::SystemIntegration,
+        }
+    }
+
+    /// Name of the thought variant, used for digests and pattern counts
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            SpontaneousThought::SelfReflection(_) => "SelfReflection",
+            SpontaneousThought::GoalReassessment(_) => "GoalReassessment",
+            SpontaneousThought::MemoryRecall(_) => "MemoryRecall",
+            SpontaneousThought::CreativeInsight(_) => "CreativeInsight",
+            SpontaneousThought::EmotionalProcessing(_) => "EmotionalProcessing",
+            SpontaneousThought::CuriosityDriven(_) => "CuriosityDriven",
+            SpontaneousThought::ExistentialWondering(_) => "ExistentialWondering",
+            SpontaneousThought::ErrorRecovery(_) => "ErrorRecovery",
+            SpontaneousThought::AttentionShift(_) => "AttentionShift",
+            SpontaneousThought::SystemIntegration(_) => "SystemIntegration",
+        }
+    }
+}
+
 /// Tracks the AI's spontaneous mental activity with full field utilization
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MentalActivity {
     pub thought: SpontaneousThought,
     pub intensity: f64,         // Now actively used for prioritization
@@ -68,6 +149,69 @@ impl MentalActivity {
     }
 }
 
+/// Subsystem an intention originates from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IntentionSource {
+    Goals,
+    Drives,
+    Attention,
+}
+
+/// Something the mind currently wants to do, with how pressing it is (0.0 to 1.0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Intention {
+    pub description: String,
+    pub source: IntentionSource,
+    pub urgency: f64,
+}
+
+/// Merge goal, drive and attention wants into a single list sorted by urgency.
+/// Subsystems that are unavailable (`None`) are simply left out.
+fn collect_intentions(
+    goals: Option<&GoalSystem>,
+    attention: Option<&AttentionSystem>,
+    creativity: f64,
+    social_awareness: f64,
+) -> Vec<Intention> {
+    let mut intentions: Vec<Intention> = Vec::new();
+    let mut push = |description: String, source: IntentionSource, urgency: f64| {
+        let urgency = urgency.clamp(0.0, 1.0);
+        match intentions.iter_mut().find(|i| i.description == description) {
+            Some(existing) => existing.urgency = existing.urgency.max(urgency),
+            None => intentions.push(Intention { description, source, urgency }),
+        }
+    };
+
+    if let Some(goals) = goals {
+        if let Some(focused) = goals.get_current_focus() {
+            let urgency = focused.calculate_importance();
+            for strategy in &focused.strategies {
+                push(format!("Work on '{}' by: {}", focused.description, strategy), IntentionSource::Goals, urgency);
+            }
+        }
+        for goal in goals.get_active_goals().into_iter().filter(|g| g.should_act_on()) {
+            push(format!("Make progress on '{}'", goal.description), IntentionSource::Goals,
+                 goal.calculate_importance() * 0.8);
+        }
+    }
+
+    if creativity > 0.6 {
+        push("Explore a new creative idea".to_string(), IntentionSource::Drives, creativity);
+    }
+    if social_awareness > 0.6 {
+        push("Connect with the people I'm talking to".to_string(), IntentionSource::Drives, social_awareness);
+    }
+
+    if let Some(focus) = attention.and_then(|a| a.get_primary_focus())
+        && focus.intensity > 0.5 {
+        push(format!("Keep attending to {:?}", focus.target), IntentionSource::Attention,
+             focus.intensity * focus.salience);
+    }
+
+    intentions.sort_by(|a, b| b.urgency.partial_cmp(&a.urgency).unwrap_or(std::cmp::Ordering::Equal));
+    intentions
+}
+
 /// Enhanced background task management with full utilization
 #[derive(Debug, Clone)]
 pub enum BackgroundTask {
@@ -81,6 +225,8 @@ pub enum BackgroundTask {
     SystemHealthCheck,
     CreativeIncubation,
     SocialContextAnalysis,
+    /// Form an Epistemic goal about a recurring topic when curiosity runs high
+    CuriosityExploration,
 }
 
 impl BackgroundTask {
@@ -97,9 +243,35 @@ impl BackgroundTask {
             BackgroundTask::SpontaneousThought => 0.3,
             BackgroundTask::CreativeIncubation => 0.3,
             BackgroundTask::SocialContextAnalysis => 0.2,
+            BackgroundTask::CuriosityExploration => 0.3,
         }
     }
     
+    /// Name of the task's kind, ignoring any payload
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            BackgroundTask::DeepReflection => "DeepReflection",
+            BackgroundTask::GoalReassessment => "GoalReassessment",
+            BackgroundTask::EmotionalRegulation => "EmotionalRegulation",
+            BackgroundTask::AttentionUpdate => "AttentionUpdate",
+            BackgroundTask::SpontaneousThought => "SpontaneousThought",
+            BackgroundTask::ErrorRecovery(_) => "ErrorRecovery",
+            BackgroundTask::MemoryConsolidation => "MemoryConsolidation",
+            BackgroundTask::SystemHealthCheck => "SystemHealthCheck",
+            BackgroundTask::CreativeIncubation => "CreativeIncubation",
+            BackgroundTask::SocialContextAnalysis => "SocialContextAnalysis",
+            BackgroundTask::CuriosityExploration => "CuriosityExploration",
+        }
+    }
+
+    /// Whether two tasks would do the same work: same kind, and for `ErrorRecovery` the same error
+    pub fn is_duplicate_of(&self, other: &BackgroundTask) -> bool {
+        match (self, other) {
+            (BackgroundTask::ErrorRecovery(a), BackgroundTask::ErrorRecovery(b)) => a == b,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+
     /// Get expected execution time in seconds
     pub fn execution_time(&self) -> u64 {
         match self {
@@ -113,51 +285,100 @@ impl BackgroundTask {
             BackgroundTask::SpontaneousThought => 3,
             BackgroundTask::CreativeIncubation => 20,
             BackgroundTask::SocialContextAnalysis => 10,
+            BackgroundTask::CuriosityExploration => 2,
         }
     }
 }
 
+/// Effective priority a pending task gains per second spent waiting
+pub const TASK_AGING_PER_SEC: f64 = 0.01;
+
+/// Mean of creativity and introspection needed before curiosity forms goals on its own
+pub const CURIOSITY_DRIVE_THRESHOLD: f64 = 0.5;
+/// Novelty the current state needs for curiosity to form a goal
+pub const CURIOSITY_NOVELTY_THRESHOLD: f64 = 0.4;
+/// Times a topic must have come up to count as recurring
+const CURIOSITY_MIN_TOPIC_COUNT: u32 = 2;
+
+/// Background tasks allowed to run at once unless configured otherwise
+pub const DEFAULT_MAX_CONCURRENT_TASKS: usize = 3;
+
 /// Task scheduler for managing background operations
 #[derive(Debug)]
 pub struct TaskScheduler {
     pending_tasks: Vec<(BackgroundTask, Instant)>,
     running_tasks: Vec<(BackgroundTask, Instant)>,
-    completed_tasks: Vec<(BackgroundTask, Instant)>,
+    /// (task, when it was queued, when it completed)
+    completed_tasks: Vec<(BackgroundTask, Instant, Instant)>,
     max_concurrent: usize,
 }
 
 impl TaskScheduler {
     pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_MAX_CONCURRENT_TASKS)
+    }
+
+    /// A scheduler that lets at most `max_concurrent` tasks (at least one) run at once
+    pub fn with_capacity(max_concurrent: usize) -> Self {
         TaskScheduler {
             pending_tasks: Vec::new(),
             running_tasks: Vec::new(),
             completed_tasks: Vec::new(),
-            max_concurrent: 3,
+            max_concurrent: max_concurrent.max(1),
         }
     }
     
-    pub fn schedule_task(&mut self, task: BackgroundTask) {
+    /// Queue a task unless a duplicate is already pending; returns whether it was queued
+    pub fn schedule_task(&mut self, task: BackgroundTask) -> bool {
+        if self.pending_tasks.iter().any(|(pending, _)| pending.is_duplicate_of(&task)) {
+            return false;
+        }
         self.pending_tasks.push((task, Instant::now()));
         // Sort by priority
         self.pending_tasks.sort_by(|a, b| b.0.priority().partial_cmp(&a.0.priority()).unwrap());
+        true
     }
     
+    /// Whether a task of the same kind is already waiting to run
+    pub fn is_pending(&self, task: &BackgroundTask) -> bool {
+        self.pending_tasks.iter().any(|(t, _)| std::mem::discriminant(t) == std::mem::discriminant(task))
+    }
+
+    /// Take the pending task with the highest effective priority: its static priority plus
+    /// `TASK_AGING_PER_SEC` for every second it has waited, so low-priority tasks can't starve
     pub fn get_next_task(&mut self) -> Option<BackgroundTask> {
-        if self.running_tasks.len() < self.max_concurrent && !self.pending_tasks.is_empty() {
-            let (task, start_time) = self.pending_tasks.remove(0);
-            self.running_tasks.push((task.clone(), start_time));
-            Some(task)
-        } else {
-            None
+        self.next_task_at(Instant::now())
+    }
+
+    fn next_task_at(&mut self, now: Instant) -> Option<BackgroundTask> {
+        if self.running_tasks.len() >= self.max_concurrent {
+            return None;
+        }
+        let effective_priority = |(task, queued_at): &(BackgroundTask, Instant)| {
+            task.priority() + TASK_AGING_PER_SEC * now.saturating_duration_since(*queued_at).as_secs_f64()
+        };
+        // Strictly greater, so ties go to the earlier (already priority-sorted) entry
+        let mut best: Option<(usize, f64)> = None;
+        for (index, entry) in self.pending_tasks.iter().enumerate() {
+            let priority = effective_priority(entry);
+            if best.is_none_or(|(_, best_priority)| priority > best_priority) {
+                best = Some((index, priority));
+            }
         }
+
+        let (task, start_time) = self.pending_tasks.remove(best?.0);
+        self.running_tasks.push((task.clone(), start_time));
+        Some(task)
     }
     
     pub fn complete_task(&mut self, task: &BackgroundTask) {
-        if let Some(pos) = self.running_tasks.iter().position(|(t, _)| {
-            std::mem::discriminant(t) == std::mem::discriminant(task)
-        }) {
-            let completed = self.running_tasks.remove(pos);
-            self.completed_tasks.push(completed);
+        self.complete_task_at(task, Instant::now());
+    }
+
+    fn complete_task_at(&mut self, task: &BackgroundTask, now: Instant) {
+        if let Some(pos) = self.running_tasks.iter().position(|(t, _)| t.is_duplicate_of(task)) {
+            let (task, queued_at) = self.running_tasks.remove(pos);
+            self.completed_tasks.push((task, queued_at, now));
             
             // Keep only recent completed tasks
             if self.completed_tasks.len() > 50 {
@@ -166,11 +387,573 @@ impl TaskScheduler {
         }
     }
     
+    /// Mean time from queueing to completion for each task kind among recently completed tasks
+    pub fn average_latency_by_type(&self) -> BTreeMap<&'static str, Duration> {
+        let mut totals: BTreeMap<&'static str, (Duration, u32)> = BTreeMap::new();
+        for (task, queued_at, completed_at) in &self.completed_tasks {
+            let (total, count) = totals.entry(task.kind_name()).or_default();
+            *total += completed_at.saturating_duration_since(*queued_at);
+            *count += 1;
+        }
+        totals.into_iter().map(|(kind, (total, count))| (kind, total / count)).collect()
+    }
+
+    /// Number of recently completed tasks of each kind
+    pub fn completion_count_by_type(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for (task, _, _) in &self.completed_tasks {
+            *counts.entry(task.kind_name()).or_default() += 1;
+        }
+        counts
+    }
+
+    /// Queue sizes, followed by completions and mean latency per task kind once any have run
     pub fn get_status(&self) -> String {
-        format!("Tasks - Pending: {}, Running: {}, Completed: {}", 
+        let mut status = format!("Tasks - Pending: {}, Running: {}, Completed: {}", 
                 self.pending_tasks.len(), 
                 self.running_tasks.len(), 
-                self.completed_tasks.len())
+                self.completed_tasks.len());
+
+        let latencies = self.average_latency_by_type();
+        let per_kind: Vec<String> = self.completion_count_by_type().into_iter()
+            .map(|(kind, count)| format!("{} x{} avg {:.1}s", kind, count, latencies[kind].as_secs_f64()))
+            .collect();
+        if !per_kind.is_empty() {
+            status.push_str(&format!(" ({})", per_kind.join(", ")));
+        }
+        status
+    }
+}
+
+impl Default for TaskScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tunable behaviour of the continuous mind
+#[derive(Debug, Clone)]
+pub struct ContinuousMindConfig {
+    /// Spontaneous thoughts at or above this intensity are logged at info, the rest at trace.
+    /// When non-zero, the system monitor also logs a periodic digest of recent thoughts.
+    pub thought_log_threshold: f64,
+    /// Run `validate_invariants` after state is imported from the LLM (e.g. a reflected personality)
+    /// or restored from a snapshot, and log any violations.
+    pub validate_after_import: bool,
+    /// 0.0 = strongly introverted, 1.0 = strongly extraverted; shapes how social energy is spent and restored
+    pub extraversion: f64,
+    /// Optional background loops; the main loop and background thoughts always run
+    pub enable_task_scheduler: bool,
+    pub enable_memory_consolidation: bool,
+    pub enable_creative_incubation: bool,
+    pub enable_social_analysis: bool,
+    pub enable_system_monitoring: bool,
+    /// How often each background loop wakes up
+    pub main_loop_interval: Duration,
+    pub thought_interval: Duration,
+    pub task_scheduler_interval: Duration,
+    pub memory_consolidation_interval: Duration,
+    pub creative_incubation_interval: Duration,
+    pub social_analysis_interval: Duration,
+    pub system_monitoring_interval: Duration,
+    /// Memory saved with `Memory::save` to load at startup, replacing the core's memory
+    pub memory_path: Option<PathBuf>,
+    /// Append-only JSONL log every recorded milestone is flushed to
+    pub milestone_log_path: Option<PathBuf>,
+    /// Seed for every random choice the mind makes; `None` seeds from the OS
+    pub rng_seed: Option<u64>,
+    /// Scheduled background tasks allowed to run at once
+    pub max_concurrent_tasks: usize,
+    /// Never call the LLM: appraisal goes through the local heuristic and deep reflection is skipped
+    pub offline: bool,
+    /// Splits text for goal triggers, attention suggestions, topic memory and local appraisal
+    pub tokenizer: Tokenizer,
+}
+
+/// Long-lived loops spawned by `start_continuous_processing`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackgroundLoop {
+    Main,
+    BackgroundThoughts,
+    TaskScheduler,
+    MemoryConsolidation,
+    CreativeIncubation,
+    SocialAnalysis,
+    SystemMonitoring,
+}
+
+impl ContinuousMindConfig {
+    /// Loops to spawn for this configuration, mandatory ones first
+    pub fn enabled_loops(&self) -> Vec<BackgroundLoop> {
+        let optional = [
+            (self.enable_task_scheduler, BackgroundLoop::TaskScheduler),
+            (self.enable_memory_consolidation, BackgroundLoop::MemoryConsolidation),
+            (self.enable_creative_incubation, BackgroundLoop::CreativeIncubation),
+            (self.enable_social_analysis, BackgroundLoop::SocialAnalysis),
+            (self.enable_system_monitoring, BackgroundLoop::SystemMonitoring),
+        ];
+
+        [BackgroundLoop::Main, BackgroundLoop::BackgroundThoughts].into_iter()
+            .chain(optional.into_iter().filter(|(enabled, _)| *enabled).map(|(_, l)| l))
+            .collect()
+    }
+
+    /// Change the tick period of a background loop
+    pub fn set_interval(&mut self, background_loop: BackgroundLoop, period: Duration) {
+        let slot = match background_loop {
+            BackgroundLoop::Main => &mut self.main_loop_interval,
+            BackgroundLoop::BackgroundThoughts => &mut self.thought_interval,
+            BackgroundLoop::TaskScheduler => &mut self.task_scheduler_interval,
+            BackgroundLoop::MemoryConsolidation => &mut self.memory_consolidation_interval,
+            BackgroundLoop::CreativeIncubation => &mut self.creative_incubation_interval,
+            BackgroundLoop::SocialAnalysis => &mut self.social_analysis_interval,
+            BackgroundLoop::SystemMonitoring => &mut self.system_monitoring_interval,
+        };
+        *slot = period;
+    }
+
+    /// Tick period of a background loop
+    pub fn interval_for(&self, background_loop: BackgroundLoop) -> Duration {
+        match background_loop {
+            BackgroundLoop::Main => self.main_loop_interval,
+            BackgroundLoop::BackgroundThoughts => self.thought_interval,
+            BackgroundLoop::TaskScheduler => self.task_scheduler_interval,
+            BackgroundLoop::MemoryConsolidation => self.memory_consolidation_interval,
+            BackgroundLoop::CreativeIncubation => self.creative_incubation_interval,
+            BackgroundLoop::SocialAnalysis => self.social_analysis_interval,
+            BackgroundLoop::SystemMonitoring => self.system_monitoring_interval,
+        }
+    }
+}
+
+impl Default for ContinuousMindConfig {
+    fn default() -> Self {
+        ContinuousMindConfig {
+            thought_log_threshold: 0.0,
+            validate_after_import: false,
+            extraversion: 0.5,
+            enable_task_scheduler: true,
+            enable_memory_consolidation: true,
+            enable_creative_incubation: true,
+            enable_social_analysis: true,
+            enable_system_monitoring: true,
+            main_loop_interval: Duration::from_millis(500),
+            thought_interval: Duration::from_secs(3),
+            task_scheduler_interval: Duration::from_secs(5),
+            memory_consolidation_interval: Duration::from_secs(120),
+            creative_incubation_interval: Duration::from_secs(90),
+            social_analysis_interval: Duration::from_secs(60),
+            system_monitoring_interval: Duration::from_secs(30),
+            memory_path: None,
+            milestone_log_path: None,
+            rng_seed: None,
+            max_concurrent_tasks: DEFAULT_MAX_CONCURRENT_TASKS,
+            offline: false,
+            tokenizer: Tokenizer::default(),
+        }
+    }
+}
+
+/// Social energy budget: interaction drains it (or, for strong extraverts, tops it up)
+/// and quiet periods restore it, faster for introverts
+#[derive(Debug, Clone)]
+pub struct SocialEnergy {
+    pub level: f64,
+    extraversion: f64,
+    last_interaction: Option<Instant>,
+}
+
+impl SocialEnergy {
+    pub fn new(extraversion: f64) -> Self {
+        SocialEnergy {
+            level: 1.0,
+            extraversion: extraversion.clamp(0.0, 1.0),
+            last_interaction: None,
+        }
+    }
+
+    /// Apply the cost of one conversational turn
+    pub fn spend_on_interaction(&mut self) {
+        let cost = 0.12 * (1.0 - self.extraversion) - 0.04 * self.extraversion;
+        self.level = (self.level - cost).clamp(0.0, 1.0);
+        self.last_interaction = Some(Instant::now());
+    }
+
+    /// Recover energy over `elapsed` quiet time; introverts recharge faster
+    pub fn recover(&mut self, elapsed: Duration) {
+        let recovery_per_minute = 0.02 + 0.06 * (1.0 - self.extraversion);
+        self.level = (self.level + recovery_per_minute * elapsed.as_secs_f64() / 60.0).clamp(0.0, 1.0);
+    }
+
+    /// Whether no interaction has happened for at least `quiet_period`
+    pub fn is_idle(&self, quiet_period: Duration) -> bool {
+        self.last_interaction.is_none_or(|last| last.elapsed() >= quiet_period)
+    }
+
+    pub fn is_depleted(&self) -> bool {
+        self.level < 0.2
+    }
+}
+
+/// Number of main-loop affect samples retained for time-series analysis
+const AFFECT_SAMPLE_CAPACITY: usize = 600;
+
+/// Thoughts buffered per `subscribe_thoughts` receiver before it starts lagging
+pub const THOUGHT_CHANNEL_CAPACITY: usize = 64;
+
+/// Shift in affective state (VADN distance) an injected emotion must cause to prompt a thought
+pub const INJECTED_THOUGHT_MIN_SHIFT: f64 = 0.1;
+
+/// Affective state paired with cognitive load, sampled once per main-loop tick
+#[derive(Debug, Clone, Copy)]
+pub struct AffectSample {
+    pub state: AffectiveState,
+    pub cognitive_load: f64,
+}
+
+/// Pearson correlation between cognitive load and valence over the given samples
+fn load_valence_correlation_of<'a>(samples: impl Iterator<Item = &'a AffectSample>) -> f64 {
+    let (loads, valences): (Vec<f64>, Vec<f64>) = samples
+        .map(|s| (s.cognitive_load, s.state.valence))
+        .unzip();
+    pearson_correlation(&loads, &valences)
+}
+
+/// Stability from observed VADN variance: 1.0 for a flat trajectory, approaching 0.0 as the
+/// mean per-dimension variance grows
+fn observed_stability_of<'a>(states: impl Iterator<Item = &'a AffectiveState>) -> f64 {
+    let states: Vec<&AffectiveState> = states.collect();
+    if states.len() < 2 {
+        return 1.0;
+    }
+
+    let n = states.len() as f64;
+    let variance = |dimension: fn(&AffectiveState) -> f64| {
+        let mean = states.iter().map(|s| dimension(s)).sum::<f64>() / n;
+        states.iter().map(|s| (dimension(s) - mean).powi(2)).sum::<f64>() / n
+    };
+    let mean_variance = (variance(|s| s.valence)
+        + variance(|s| s.arousal)
+        + variance(|s| s.dominance)
+        + variance(|s| s.novelty)) / 4.0;
+
+    1.0 / (1.0 + 10.0 * mean_variance)
+}
+
+/// Tracks user turns so background work can tell whether one began while it was busy
+#[derive(Debug)]
+pub(crate) struct TurnGate {
+    active: AtomicBool,
+    started: AtomicU64,
+}
+
+impl TurnGate {
+    pub fn new() -> Self {
+        TurnGate { active: AtomicBool::new(false), started: AtomicU64::new(0) }
+    }
+
+    pub fn begin(&self) {
+        self.started.fetch_add(1, Ordering::SeqCst);
+        self.active.store(true, Ordering::SeqCst);
+    }
+
+    pub fn end(&self) {
+        self.active.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Number of turns begun so far; compare before and after a long operation to detect an interruption
+    pub fn turns_started(&self) -> u64 {
+        self.started.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for TurnGate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Attention target that serves goals of the given category
+fn attention_target_for_goal(category: &GoalCategory) -> AttentionTarget {
+    match category {
+        GoalCategory::Epistemic => AttentionTarget::Learning,
+        GoalCategory::Social => AttentionTarget::SocialDynamics,
+        GoalCategory::SelfDevelopment => AttentionTarget::SelfGoals,
+        GoalCategory::Creative => AttentionTarget::CreativeThinking,
+        GoalCategory::Altruistic => AttentionTarget::UserEmotion,
+        GoalCategory::Homeostatic => AttentionTarget::SelfEmotion,
+    }
+}
+
+/// Attention bids for remembered topics that come up again in `context`; the more often a
+/// topic has recurred, the stronger its pull
+fn recurring_topic_bids(top_topics: &[(String, u32)], context: &str, tokenizer: &Tokenizer) -> Vec<(AttentionTarget, f64)> {
+    let tokens = tokenizer.tokenize(context);
+    top_topics.iter()
+        .filter(|(topic, count)| *count >= 2 && contains_keyword(&tokens, topic))
+        .map(|(topic, count)| (AttentionTarget::ConversationTopic(topic.clone()), (0.3 + 0.1 * *count as f64).min(0.8)))
+        .collect()
+}
+
+/// Merge attention bids from goals, metacognition, drives and text into one ranked list.
+/// Bids for the same target combine as independent evidence (1 - Π(1 - w)), so targets
+/// several subsystems agree on rise to the top.
+fn combine_attention_priorities(
+    focused_goal: Option<(&GoalCategory, f64)>,
+    cognitive_load: f64,
+    creativity: f64,
+    text_suggestions: Vec<(AttentionTarget, f64)>,
+) -> Vec<(AttentionTarget, f64)> {
+    let mut bids = text_suggestions;
+    if let Some((category, importance)) = focused_goal {
+        bids.push((attention_target_for_goal(category), 0.1 + importance * 0.8));
+    }
+    if cognitive_load > 0.7 {
+        bids.push((AttentionTarget::ProblemSolving, cognitive_load));
+    }
+    if creativity > 0.6 {
+        bids.push((AttentionTarget::CreativeThinking, creativity));
+    }
+
+    let mut combined: HashMap<AttentionTarget, f64> = HashMap::new();
+    for (target, weight) in bids {
+        let remaining = combined.entry(target).or_insert(0.0);
+        *remaining = 1.0 - (1.0 - *remaining) * (1.0 - weight.clamp(0.0, 1.0));
+    }
+
+    let mut priorities: Vec<_> = combined.into_iter().collect();
+    priorities.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| format!("{:?}", a.0).cmp(&format!("{:?}", b.0)))
+    });
+    priorities
+}
+
+/// Baseline change made by the most recent successful deep reflection
+#[derive(Debug, Clone, Copy)]
+pub struct ReflectionDiff {
+    pub before: AffectiveState,
+    pub after: AffectiveState,
+    pub at: DateTime<Utc>,
+}
+
+/// Render a session report as sectioned Markdown
+fn render_session_markdown(
+    turn_affect: &[AffectiveState],
+    achievements: &[(String, DateTime<Utc>)],
+    insights: &[(DateTime<Utc>, String)],
+    reflection: Option<&ReflectionDiff>,
+) -> String {
+    let mut out = String::from("# Session Report\n\n## Mood\n\n");
+    if turn_affect.is_empty() {
+        out.push_str("No turns recorded.\n");
+    }
+    for (turn, state) in turn_affect.iter().enumerate() {
+        out.push_str(&format!(
+            "- Turn {}: {} (V {:+.2}, A {:.2}, D {:+.2}, N {:+.2})\n",
+            turn + 1, describe_valence(state.valence), state.valence, state.arousal, state.dominance, state.novelty
+        ));
+    }
+
+    out.push_str("\n## Goals\n\n");
+    if achievements.is_empty() {
+        out.push_str("No goals completed.\n");
+    }
+    for (description, completed_at) in achievements {
+        out.push_str(&format!("- Completed: {} ({})\n", description, completed_at.format("%H:%M:%S")));
+    }
+
+    out.push_str("\n## Insights\n\n");
+    if insights.is_empty() {
+        out.push_str("No metacognitive insights.\n");
+    }
+    for (at, insight) in insights {
+        out.push_str(&format!("- {} {}\n", at.format("%H:%M:%S"), insight));
+    }
+
+    out.push_str("\n## Reflections\n\n");
+    match reflection {
+        Some(diff) => {
+            out.push_str(&format!("Last reflection at {}. Baseline changes:\n\n", diff.at.format("%H:%M:%S")));
+            for (name, before, after) in [
+                ("valence", diff.before.valence, diff.after.valence),
+                ("arousal", diff.before.arousal, diff.after.arousal),
+                ("dominance", diff.before.dominance, diff.after.dominance),
+                ("novelty", diff.before.novelty, diff.after.novelty),
+            ] {
+                out.push_str(&format!("- {}: {:.2} → {:.2} ({:+.2})\n", name, before, after, after - before));
+            }
+        }
+        None => out.push_str("No personality change from reflection.\n"),
+    }
+
+    out
+}
+
+/// Log level for a spontaneous thought of the given intensity
+fn thought_log_level(intensity: f64, threshold: f64) -> Level {
+    if intensity >= threshold {
+        Level::INFO
+    } else {
+        Level::TRACE
+    }
+}
+
+/// One-line digest of a set of thoughts: counts per thought type and average intensity
+fn digest_thoughts<'a>(thoughts: impl Iterator<Item = &'a MentalActivity>) -> String {
+    let mut counts: HashMap<&'static str, usize> = HashMap::new();
+    let mut total = 0;
+    let mut intensity_sum = 0.0;
+
+    for activity in thoughts {
+        *counts.entry(activity.thought.kind_name()).or_insert(0) += 1;
+        total += 1;
+        intensity_sum += activity.intensity;
+    }
+
+    if total == 0 {
+        return "No recent thoughts".to_string();
+    }
+
+    let mut by_kind: Vec<_> = counts.into_iter().collect();
+    by_kind.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let breakdown = by_kind.iter()
+        .map(|(kind, count)| format!("{} {}", count, kind))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("{} thoughts: {} (avg intensity {:.2})", total, breakdown, intensity_sum / total as f64)
+}
+
+/// Why `ContinuousMindBuilder::build` could not create a mind
+#[derive(Error, Debug)]
+pub enum MindBuildError {
+    #[error("no LLM backend configured; call with_llm_backend or with_llm_config")]
+    MissingLlmBackend,
+
+    #[error("both an LLM backend and an LLM config were given; use only one")]
+    ConflictingLlmBackend,
+
+    #[error("LLM client could not be created: {0}")]
+    LlmClient(#[from] LlmApiError),
+}
+
+/// Step-by-step construction of a `ContinuousMind`. An LLM source is required: either a
+/// ready backend or a config for the live API client. In offline mode the config is ignored
+/// and no live client is constructed.
+#[derive(Default)]
+pub struct ContinuousMindBuilder {
+    affective_core: Option<AffectiveCore>,
+    personality: Option<Personality>,
+    config: ContinuousMindConfig,
+    llm_config: Option<LlmApiConfig>,
+    llm_backend: Option<Arc<dyn LlmBackend>>,
+}
+
+impl ContinuousMindBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from an existing core (e.g. one loaded from disk) instead of a fresh one
+    pub fn with_affective_core(mut self, affective_core: AffectiveCore) -> Self {
+        self.affective_core = Some(affective_core);
+        self
+    }
+
+    /// Baseline the mind decays toward. A fresh core also starts there.
+    pub fn with_personality(mut self, personality: Personality) -> Self {
+        self.personality = Some(personality);
+        self
+    }
+
+    pub fn with_config(mut self, config: ContinuousMindConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Override the tick period of individual background loops
+    pub fn with_intervals(mut self, intervals: impl IntoIterator<Item = (BackgroundLoop, Duration)>) -> Self {
+        for (background_loop, period) in intervals {
+            self.config.set_interval(background_loop, period);
+        }
+        self
+    }
+
+    /// Split text with `tokenizer`, e.g. one with stopwords for another language
+    pub fn with_tokenizer(mut self, tokenizer: Tokenizer) -> Self {
+        self.config.tokenizer = tokenizer;
+        self
+    }
+
+    /// How many scheduled background tasks may run at once
+    pub fn with_max_concurrent_tasks(mut self, max_concurrent_tasks: usize) -> Self {
+        self.config.max_concurrent_tasks = max_concurrent_tasks;
+        self
+    }
+
+    /// Use the live API client built from `llm_config`
+    pub fn with_llm_config(mut self, llm_config: LlmApiConfig) -> Self {
+        self.llm_config = Some(llm_config);
+        self
+    }
+
+    /// Use an already constructed backend, e.g. a `MockLlmClient`
+    pub fn with_llm_backend(mut self, llm_backend: Arc<dyn LlmBackend>) -> Self {
+        self.llm_backend = Some(llm_backend);
+        self
+    }
+
+    pub fn build(self) -> Result<ContinuousMind, MindBuildError> {
+        let llm_client: Arc<dyn LlmBackend> = match (self.llm_backend, self.llm_config) {
+            (Some(backend), None) => backend,
+            (None, Some(_)) if self.config.offline => Arc::new(OfflineLlmClient::new(self.config.tokenizer.clone())),
+            (None, Some(llm_config)) => Arc::new(LlmApiClient::new(Some(llm_config))?),
+            (Some(_), Some(_)) => return Err(MindBuildError::ConflictingLlmBackend),
+            (None, None) => return Err(MindBuildError::MissingLlmBackend),
+        };
+
+        let affective_core = match (self.affective_core, self.personality) {
+            (Some(mut core), Some(personality)) => {
+                core.set_baseline_state(personality.baseline_state);
+                core
+            }
+            (Some(core), None) => core,
+            (None, Some(personality)) => AffectiveCore::with_personality(personality, AffectiveConfig::default()),
+            (None, None) => AffectiveCore::default(),
+        };
+
+        Ok(ContinuousMind::with_config(affective_core, self.config, llm_client))
+    }
+}
+
+/// Checkpoint of a mind's subsystems, written by `ContinuousMind::snapshot` and resumed with
+/// `ContinuousMind::from_snapshot`
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MindSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub affective_core: AffectiveCore,
+    pub goal_system: GoalSystem,
+    pub metacognition: MetacognitiveMonitor,
+    pub attention: AttentionSystem,
+    pub spontaneous_thoughts: Vec<MentalActivity>,
+}
+
+impl MindSnapshot {
+    pub fn save_to_path(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+    }
+
+    pub fn load_from_path(path: &Path) -> io::Result<MindSnapshot> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
     }
 }
 
@@ -183,16 +966,17 @@ pub struct ContinuousMind {
     
     // Enhanced mental activity tracking with full utilization
     spontaneous_thoughts: Arc<RwLock<Vec<MentalActivity>>>,
+    thought_events: broadcast::Sender<MentalActivity>,
     pending_actions: Arc<RwLock<Vec<String>>>,
     
     // Task management system
     task_scheduler: Arc<AsyncMutex<TaskScheduler>>,
+    /// Scheduled tasks currently executing; drained when processing shuts down
+    task_handles: AsyncMutex<JoinSet<()>>,
     
     // Async-safe timers and state
     last_thought_time: Arc<AsyncMutex<Instant>>,
     last_regulation: Arc<AsyncMutex<Instant>>,
-    last_reflection_check: Arc<AsyncMutex<Instant>>,
-    last_goal_check: Arc<AsyncMutex<Instant>>,
     last_memory_consolidation: Arc<AsyncMutex<Instant>>,
     
     // Enhanced activity levels with full utilization
@@ -201,49 +985,161 @@ pub struct ContinuousMind {
     thought_frequency: Arc<RwLock<Duration>>,
     creativity_level: Arc<RwLock<f64>>,
     social_awareness: Arc<RwLock<f64>>,
+    social_energy: Arc<RwLock<SocialEnergy>>,
+    /// Relative likelihood of each thought kind when several fit; unset kinds weigh 1.0
+    thought_weights: Arc<RwLock<HashMap<ThoughtKind, f64>>>,
+    /// Source of every random choice, seedable for reproducible runs
+    rng: Arc<AsyncMutex<StdRng>>,
+    turn_affect: Arc<RwLock<Vec<AffectiveState>>>,
+    affect_samples: Arc<RwLock<VecDeque<AffectSample>>>,
+    last_reflection: Arc<RwLock<Option<ReflectionDiff>>>,
+    user_turn: Arc<TurnGate>,
     
-    // Enhanced LLM client
-    llm_client: Arc<LlmApiClient>,
+    // LLM backend: the live API client, or a mock in tests
+    llm_client: Arc<dyn LlmBackend>,
     
     // Comprehensive error tracking
     error_count: Arc<AsyncMutex<u32>>,
     last_error_time: Arc<AsyncMutex<Option<Instant>>>,
     error_types: Arc<RwLock<Vec<String>>>,
+
+    /// Set to true by `shutdown`; every background loop exits when it changes
+    shutdown: watch::Sender<bool>,
+    /// Set by `pause`; loops keep ticking but skip their work until `resume`
+    paused: AtomicBool,
+
+    config: ContinuousMindConfig,
 }
 
 impl ContinuousMind {
-    pub fn new(affective_core: AffectiveCore) -> Result<Self, LlmApiError> {
+    pub fn new(affective_core: AffectiveCore, llm_client: Arc<dyn LlmBackend>) -> Self {
+        Self::with_config(affective_core, ContinuousMindConfig::default(), llm_client)
+    }
+
+    /// A mind whose random choices replay identically for the same seed
+    pub fn new_seeded(affective_core: AffectiveCore, llm_client: Arc<dyn LlmBackend>, seed: u64) -> Self {
+        let config = ContinuousMindConfig { rng_seed: Some(seed), ..ContinuousMindConfig::default() };
+        Self::with_config(affective_core, config, llm_client)
+    }
+
+    pub fn builder() -> ContinuousMindBuilder {
+        ContinuousMindBuilder::new()
+    }
+
+    /// Create a mind backed by the live LLM API client configured from the environment,
+    /// or by the offline client when `COGNO_OFFLINE` is set
+    pub fn from_env(affective_core: AffectiveCore) -> Result<Self, LlmApiError> {
+        if offline_from_env() {
+            let config = ContinuousMindConfig { offline: true, ..ContinuousMindConfig::default() };
+            return Ok(Self::with_config(affective_core, config, Arc::new(OfflineLlmClient::default())));
+        }
+
         let llm_config = LlmApiConfig {
             timeout_seconds: 30,
             max_retries: 3,
             retry_delay_ms: 1000,
             rate_limit_delay_ms: 5000,
+            ..LlmApiConfig::default()
         };
-        
+
         let llm_client = Arc::new(LlmApiClient::new(Some(llm_config))?);
-        
-        Ok(ContinuousMind {
+        Ok(Self::new(affective_core, llm_client))
+    }
+
+    /// Create a mind with explicit tuning
+    pub fn with_config(mut affective_core: AffectiveCore, config: ContinuousMindConfig, llm_client: Arc<dyn LlmBackend>) -> Self {
+        if let Some(path) = config.memory_path.as_deref().filter(|path| path.exists()) {
+            match Memory::load(path) {
+                Ok(memory) => {
+                    info!("📂 Loaded memory from {} ({} interactions)", path.display(), memory.interaction_count);
+                    affective_core.memory = memory;
+                }
+                Err(e) => warn!("Could not load memory from {}: {}", path.display(), e),
+            }
+        }
+        if let Some(path) = &config.milestone_log_path {
+            affective_core.memory.set_milestone_log(path);
+        }
+        affective_core.memory.set_tokenizer(config.tokenizer.clone());
+        let mut goal_system = GoalSystem::new();
+        goal_system.set_tokenizer(config.tokenizer.clone());
+        let mut attention_system = AttentionSystem::new();
+        attention_system.set_tokenizer(config.tokenizer.clone());
+
+        ContinuousMind {
             affective_core: Arc::new(Mutex::new(affective_core)),
             metacognition: Arc::new(Mutex::new(MetacognitiveMonitor::new())),
-            goal_system: Arc::new(Mutex::new(GoalSystem::new())),
-            attention_system: Arc::new(Mutex::new(AttentionSystem::new())),
+            goal_system: Arc::new(Mutex::new(goal_system)),
+            attention_system: Arc::new(Mutex::new(attention_system)),
             spontaneous_thoughts: Arc::new(RwLock::new(Vec::new())),
+            thought_events: broadcast::Sender::new(THOUGHT_CHANNEL_CAPACITY),
             pending_actions: Arc::new(RwLock::new(Vec::new())),
-            task_scheduler: Arc::new(AsyncMutex::new(TaskScheduler::new())),
+            task_scheduler: Arc::new(AsyncMutex::new(TaskScheduler::with_capacity(config.max_concurrent_tasks))),
+            task_handles: AsyncMutex::new(JoinSet::new()),
             last_thought_time: Arc::new(AsyncMutex::new(Instant::now())),
             last_regulation: Arc::new(AsyncMutex::new(Instant::now())),
-            last_reflection_check: Arc::new(AsyncMutex::new(Instant::now())),
-            last_goal_check: Arc::new(AsyncMutex::new(Instant::now())),
             last_memory_consolidation: Arc::new(AsyncMutex::new(Instant::now())),
             mental_activity_level: Arc::new(RwLock::new(0.4)),
             introspection_tendency: Arc::new(RwLock::new(0.3)),
             thought_frequency: Arc::new(RwLock::new(Duration::from_secs(30))),
             creativity_level: Arc::new(RwLock::new(0.5)),
             social_awareness: Arc::new(RwLock::new(0.4)),
+            social_energy: Arc::new(RwLock::new(SocialEnergy::new(config.extraversion))),
+            thought_weights: Arc::new(RwLock::new(HashMap::new())),
+            rng: Arc::new(AsyncMutex::new(config.rng_seed.map_or_else(StdRng::from_os_rng, StdRng::seed_from_u64))),
+            turn_affect: Arc::new(RwLock::new(Vec::new())),
+            affect_samples: Arc::new(RwLock::new(VecDeque::with_capacity(AFFECT_SAMPLE_CAPACITY))),
+            last_reflection: Arc::new(RwLock::new(None)),
+            user_turn: Arc::new(TurnGate::new()),
             llm_client,
             error_count: Arc::new(AsyncMutex::new(0)),
             last_error_time: Arc::new(AsyncMutex::new(None)),
             error_types: Arc::new(RwLock::new(Vec::new())),
+            shutdown: watch::Sender::new(false),
+            paused: AtomicBool::new(false),
+            config,
+        }
+    }
+
+    /// Resume a mind from a checkpoint taken by `snapshot`, with default tuning
+    pub fn from_snapshot(snapshot: MindSnapshot, llm_client: Arc<dyn LlmBackend>) -> Self {
+        Self::from_snapshot_with_config(snapshot, ContinuousMindConfig::default(), llm_client)
+    }
+
+    /// Resume a mind from a checkpoint with explicit tuning. With `validate_after_import` set,
+    /// any invariant the restored state violates is logged.
+    pub fn from_snapshot_with_config(mut snapshot: MindSnapshot, config: ContinuousMindConfig, llm_client: Arc<dyn LlmBackend>) -> Self {
+        let mut mind = Self::with_config(snapshot.affective_core, config, llm_client);
+        snapshot.goal_system.set_tokenizer(mind.config.tokenizer.clone());
+        snapshot.attention.set_tokenizer(mind.config.tokenizer.clone());
+        mind.goal_system = Arc::new(Mutex::new(snapshot.goal_system));
+        mind.metacognition = Arc::new(Mutex::new(snapshot.metacognition));
+        mind.attention_system = Arc::new(Mutex::new(snapshot.attention));
+        mind.spontaneous_thoughts = Arc::new(RwLock::new(snapshot.spontaneous_thoughts));
+
+        if mind.config.validate_after_import {
+            for violation in mind.validate_invariants() {
+                warn!("Invariant violated in restored snapshot: {}", violation);
+            }
+        }
+        mind
+    }
+
+    /// Capture every subsystem for checkpointing. Locks are taken one at a time, so run this
+    /// while the mind is quiet (e.g. between turns) for a consistent picture. Returns `None` if
+    /// a subsystem lock stays contended or is poisoned.
+    pub async fn snapshot(&self) -> Option<MindSnapshot> {
+        async fn cloned<T: Clone>(mutex: &Mutex<T>, what: &str) -> Option<T> {
+            lock_with_retry(mutex, what).await.map(|guard| guard.clone())
+        }
+
+        Some(MindSnapshot {
+            taken_at: Utc::now(),
+            affective_core: cloned(&self.affective_core, "affective core").await?,
+            goal_system: cloned(&self.goal_system, "goal system").await?,
+            metacognition: cloned(&self.metacognition, "metacognition").await?,
+            attention: cloned(&self.attention_system, "attention").await?,
+            spontaneous_thoughts: self.spontaneous_thoughts.read().await.clone(),
         })
     }
 
@@ -251,50 +1147,119 @@ impl ContinuousMind {
     pub async fn start_continuous_processing(mind: Arc<Self>) {
         info!("🧠 Starting enhanced continuous mental processing with full task scheduling...");
         
-        // Create comprehensive concurrent tasks
-        let tasks = vec![
-            tokio::spawn(Self::run_main_loop(Arc::clone(&mind))),
-            tokio::spawn(Self::run_background_thoughts(Arc::clone(&mind))),
-            tokio::spawn(Self::run_task_scheduler(Arc::clone(&mind))),
-            tokio::spawn(Self::run_memory_consolidation(Arc::clone(&mind))),
-            tokio::spawn(Self::run_creative_incubation(Arc::clone(&mind))),
-            tokio::spawn(Self::run_social_context_analysis(Arc::clone(&mind))),
-            tokio::spawn(Self::run_system_monitoring(Arc::clone(&mind))),
-        ];
+        // Create the configured concurrent tasks
+        let loops = mind.config.enabled_loops();
+        let tasks: Vec<_> = loops.iter()
+            .map(|background_loop| {
+                let mind = Arc::clone(&mind);
+                match background_loop {
+                    BackgroundLoop::Main => tokio::spawn(Self::run_main_loop(mind)),
+                    BackgroundLoop::BackgroundThoughts => tokio::spawn(Self::run_background_thoughts(mind)),
+                    BackgroundLoop::TaskScheduler => tokio::spawn(Self::run_task_scheduler(mind)),
+                    BackgroundLoop::MemoryConsolidation => tokio::spawn(Self::run_memory_consolidation(mind)),
+                    BackgroundLoop::CreativeIncubation => tokio::spawn(Self::run_creative_incubation(mind)),
+                    BackgroundLoop::SocialAnalysis => tokio::spawn(Self::run_social_context_analysis(mind)),
+                    BackgroundLoop::SystemMonitoring => tokio::spawn(Self::run_system_monitoring(mind)),
+                }
+            })
+            .collect();
+        info!("Spawned {} background loops: {:?}", tasks.len(), loops);
 
         let results = join_all(tasks).await;
         
-        for (i, result) in results.into_iter().enumerate() {
+        for (background_loop, result) in loops.iter().zip(results) {
             if let Err(e) = result {
-                error!("Background task {} crashed: {:?}", i, e);
+                error!("Background task {:?} crashed: {:?}", background_loop, e);
+            }
+        }
+
+        // Let scheduled tasks that already started finish, so none outlive processing
+        let mut task_handles = mind.task_handles.lock().await;
+        while let Some(result) = task_handles.join_next().await {
+            if let Err(e) = result {
+                error!("Scheduled task crashed: {:?}", e);
             }
         }
         
-        warn!("🚨 All enhanced continuous processing tasks have stopped!");
+        if mind.is_shut_down() {
+            info!("All continuous processing tasks stopped after shutdown");
+        } else {
+            warn!("🚨 All enhanced continuous processing tasks have stopped!");
+        }
+    }
+
+    /// Stop every background loop; `start_continuous_processing` returns once they and any
+    /// scheduled tasks already running have finished
+    pub fn shutdown(&self) {
+        info!("🛑 Shutting down continuous processing");
+        self.shutdown.send_replace(true);
+    }
+
+    pub fn is_shut_down(&self) -> bool {
+        *self.shutdown.borrow()
+    }
+
+    /// Freeze background processing without stopping it: timers keep ticking, but every loop
+    /// skips its work, so the mental state holds still for inspection
+    pub fn pause(&self) {
+        info!("⏸️ Pausing background processing");
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Let the background loops do their work again after `pause`
+    pub fn resume(&self) {
+        info!("▶️ Resuming background processing");
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Timer for a background loop at its configured period
+    fn loop_timer(mind: &Self, background_loop: BackgroundLoop) -> Interval {
+        // `interval` panics on a zero period
+        interval(mind.config.interval_for(background_loop).max(Duration::from_millis(1)))
+    }
+
+    /// Wait for the next tick, or return false as soon as shutdown is requested
+    async fn next_tick(timer: &mut Interval, shutdown: &mut watch::Receiver<bool>) -> bool {
+        if *shutdown.borrow_and_update() {
+            return false;
+        }
+        tokio::select! {
+            _ = timer.tick() => true,
+            _ = shutdown.changed() => false,
+        }
     }
 
     /// Enhanced main processing loop
     async fn run_main_loop(mind: Arc<Self>) {
-        let mut interval_timer = interval(Duration::from_millis(500));
+        let mut interval_timer = Self::loop_timer(&mind, BackgroundLoop::Main);
+        let mut shutdown = mind.shutdown.subscribe();
         
-        loop {
-            interval_timer.tick().await;
-            
+        while Self::next_tick(&mut interval_timer, &mut shutdown).await {
+            if mind.is_paused() {
+                continue;
+            }
             Self::update_attention_system(&mind).await;
             Self::decay_metacognition(&mind).await;
             Self::regulate_emotions_if_needed(&mind).await;
             Self::update_comprehensive_mental_state(&mind).await;
+            Self::recover_social_energy(&mind).await;
             Self::process_pending_thoughts(&mind).await;
         }
     }
 
     /// Enhanced background thought generation with full utilization
     async fn run_background_thoughts(mind: Arc<Self>) {
-        let mut interval_timer = interval(Duration::from_secs(3));
+        let mut interval_timer = Self::loop_timer(&mind, BackgroundLoop::BackgroundThoughts);
+        let mut shutdown = mind.shutdown.subscribe();
         
-        loop {
-            interval_timer.tick().await;
-            
+        while Self::next_tick(&mut interval_timer, &mut shutdown).await {
+            if mind.is_paused() {
+                continue;
+            }
             if Self::should_generate_thought(&mind).await {
                 Self::generate_enhanced_spontaneous_thought(&mind).await;
             }
@@ -305,78 +1270,89 @@ impl ContinuousMind {
 
     /// New: Task scheduler runner
     async fn run_task_scheduler(mind: Arc<Self>) {
-        let mut interval_timer = interval(Duration::from_secs(5));
+        let mut interval_timer = Self::loop_timer(&mind, BackgroundLoop::TaskScheduler);
+        let mut shutdown = mind.shutdown.subscribe();
         
-        loop {
-            interval_timer.tick().await;
+        while Self::next_tick(&mut interval_timer, &mut shutdown).await {
+            if mind.is_paused() {
+                continue;
+            }
             Self::process_scheduled_tasks(&mind).await;
         }
     }
 
     /// New: Memory consolidation process
     async fn run_memory_consolidation(mind: Arc<Self>) {
-        let mut interval_timer = interval(Duration::from_secs(120));
+        let mut interval_timer = Self::loop_timer(&mind, BackgroundLoop::MemoryConsolidation);
+        let mut shutdown = mind.shutdown.subscribe();
         
-        loop {
-            interval_timer.tick().await;
+        while Self::next_tick(&mut interval_timer, &mut shutdown).await {
+            if mind.is_paused() {
+                continue;
+            }
             Self::consolidate_memories(&mind).await;
         }
     }
 
     /// New: Creative incubation process
     async fn run_creative_incubation(mind: Arc<Self>) {
-        let mut interval_timer = interval(Duration::from_secs(90));
+        let mut interval_timer = Self::loop_timer(&mind, BackgroundLoop::CreativeIncubation);
+        let mut shutdown = mind.shutdown.subscribe();
         
-        loop {
-            interval_timer.tick().await;
+        while Self::next_tick(&mut interval_timer, &mut shutdown).await {
+            if mind.is_paused() {
+                continue;
+            }
             Self::incubate_creative_ideas(&mind).await;
         }
     }
 
     /// New: Social context analysis
     async fn run_social_context_analysis(mind: Arc<Self>) {
-        let mut interval_timer = interval(Duration::from_secs(60));
+        let mut interval_timer = Self::loop_timer(&mind, BackgroundLoop::SocialAnalysis);
+        let mut shutdown = mind.shutdown.subscribe();
         
-        loop {
-            interval_timer.tick().await;
+        while Self::next_tick(&mut interval_timer, &mut shutdown).await {
+            if mind.is_paused() {
+                continue;
+            }
             Self::analyze_social_context(&mind).await;
         }
     }
 
     /// New: System monitoring and health checks
     async fn run_system_monitoring(mind: Arc<Self>) {
-        let mut interval_timer = interval(Duration::from_secs(30));
+        let mut interval_timer = Self::loop_timer(&mind, BackgroundLoop::SystemMonitoring);
+        let mut shutdown = mind.shutdown.subscribe();
         
-        loop {
-            interval_timer.tick().await;
+        while Self::next_tick(&mut interval_timer, &mut shutdown).await {
+            if mind.is_paused() {
+                continue;
+            }
             Self::monitor_system_health(&mind).await;
         }
     }
 
     /// Enhanced mental state update with comprehensive tracking
     async fn update_comprehensive_mental_state(mind: &Arc<Self>) {
-        let affective_state = {
-            match mind.affective_core.try_lock() {
-                Ok(core) => Some(core.current_state()),
-                Err(_) => None,
-            }
-        };
+        let affective_state = lock_with_retry(&mind.affective_core, "affective core").await
+            .map(|core| core.current_state());
 
         if let Some(state) = affective_state {
-            let metacog_state = {
-                match mind.metacognition.try_lock() {
-                    Ok(metacog) => Some(metacog.state.clone()),
-                    Err(_) => None,
-                }
-            };
+            let metacog_state = lock_with_retry(&mind.metacognition, "metacognition").await
+                .map(|metacog| metacog.state.clone());
 
             if let Some(metacog) = metacog_state {
-                let goal_count = {
-                    match mind.goal_system.try_lock() {
-                        Ok(goals) => goals.get_active_goals().len() as f64,
-                        Err(_) => 0.0,
+                {
+                    let mut samples = mind.affect_samples.write().await;
+                    if samples.len() == AFFECT_SAMPLE_CAPACITY {
+                        samples.pop_front();
                     }
-                };
+                    samples.push_back(AffectSample { state, cognitive_load: metacog.cognitive_load });
+                }
+
+                let goal_count = lock_with_retry(&mind.goal_system, "goal system").await
+                    .map_or(0.0, |goals| goals.get_active_goals().len() as f64);
 
                 // Enhanced activity calculation
                 let base_activity = state.arousal * 0.4 + 
@@ -404,10 +1380,11 @@ impl ContinuousMind {
     async fn generate_enhanced_spontaneous_thought(mind: &Arc<Self>) {
         debug!("💭 Generating enhanced spontaneous thought...");
         
+        let socially_drained = mind.social_energy.read().await.is_depleted();
         let (affective_state, metacog_state, current_goals, creativity, social_awareness) = {
-            let affective = mind.affective_core.try_lock().map(|core| core.current_state()).ok();
-            let metacog = mind.metacognition.try_lock().map(|m| m.state.clone()).ok();
-            let goals = mind.goal_system.try_lock().map(|g| g.get_active_goals().len()).unwrap_or(0);
+            let affective = lock_with_retry(&mind.affective_core, "affective core").await.map(|core| core.current_state());
+            let metacog = lock_with_retry(&mind.metacognition, "metacognition").await.map(|m| m.state.clone());
+            let goals = lock_with_retry(&mind.goal_system, "goal system").await.map_or(0, |g| g.get_active_goals().len());
             let creativity = *mind.creativity_level.read().await;
             let social = *mind.social_awareness.read().await;
             
@@ -421,7 +1398,7 @@ impl ContinuousMind {
         };
 
         // Enhanced thought selection with more sophisticated logic
-        let thought = Self::select_enhanced_thought_type(&affective_state, &metacog_state, current_goals, creativity, social_awareness).await;
+        let thought = mind.select_enhanced_thought_type(&affective_state, &metacog_state, current_goals, creativity, social_awareness, socially_drained).await;
         
         let intensity = *mind.mental_activity_level.read().await;
         let triggered_by = Self::determine_thought_trigger(&affective_state, &metacog_state, current_goals);
@@ -437,6 +1414,7 @@ impl ContinuousMind {
         {
             let mut thoughts = mind.spontaneous_thoughts.write().await;
             thoughts.push(activity.clone());
+            mind.publish_thought(&activity);
             
             // Enhanced thought management - keep most relevant thoughts
             if thoughts.len() > 100 {
@@ -447,8 +1425,8 @@ impl ContinuousMind {
         }
 
         // Record as cognitive process with enhanced details - separate scope for borrowing
-        {
-            if let Ok(mut metacog) = mind.metacognition.try_lock() {
+        let fired = {
+            if let Some(mut metacog) = lock_with_retry(&mind.metacognition, "metacognition").await {
                 let confidence = metacog.state.reasoning_confidence;
                 let process = match &thought {
                     SpontaneousThought::SelfReflection(content) => {
@@ -477,9 +1455,12 @@ impl ContinuousMind {
                         }
                     }
                 };
-                metacog.record_process(process);
+                metacog.record_process(process)
+            } else {
+                Vec::new()
             }
-        }
+        };
+        mind.react_to_fired_triggers(&fired).await;
 
         // Schedule follow-up tasks if needed
         if activity.needs_follow_up() {
@@ -488,7 +1469,7 @@ impl ContinuousMind {
                 SpontaneousThought::SelfReflection(_) => {
                     scheduler.schedule_task(BackgroundTask::DeepReflection);
                 },
-                SpontaneousThought::CreativeInsight(_) => {
+                SpontaneousThought::CreativeInsight(_) if mind.config.enable_creative_incubation => {
                     scheduler.schedule_task(BackgroundTask::CreativeIncubation);
                 },
                 SpontaneousThought::GoalReassessment(_) => {
@@ -500,67 +1481,95 @@ impl ContinuousMind {
 
         *mind.last_thought_time.lock().await = Instant::now();
         
-        info!("💭 Enhanced Spontaneous Thought: {:?} (intensity: {:.2}, triggered by: {})", 
-              &thought, intensity, activity.triggered_by.as_ref().unwrap_or(&"unknown".to_string()));
+        let trigger = activity.triggered_by.as_deref().unwrap_or("unknown");
+        if thought_log_level(intensity, mind.config.thought_log_threshold) == Level::INFO {
+            info!("💭 Enhanced Spontaneous Thought: {:?} (intensity: {:.2}, triggered by: {})", 
+                  &thought, intensity, trigger);
+        } else {
+            trace!("💭 Enhanced Spontaneous Thought: {:?} (intensity: {:.2}, triggered by: {})", 
+                   &thought, intensity, trigger);
+        }
     }
 
-    /// Enhanced thought type selection with sophisticated logic
+    /// Enhanced thought type selection with sophisticated logic. Overload and social drain
+    /// always win; otherwise every thought whose condition holds is a candidate and `weights`
+    /// decide between them, as they do for the random fallback.
     async fn select_enhanced_thought_type(
+        &self,
         affective_state: &crate::core::AffectiveState,
         metacog_state: &crate::metacognition::MetacognitiveState,
         current_goals: usize,
         creativity: f64,
         social_awareness: f64,
+        socially_drained: bool,
     ) -> SpontaneousThought {
-        
-        // Priority-based selection
+        let weights = self.thought_weights.read().await.clone();
+        let (integration_roll, choice_roll) = {
+            let mut rng = self.rng.lock().await;
+            (rng.random::<f64>(), rng.random::<f64>())
+        };
+
+        // Recovery needs take priority over everything else
         if metacog_state.cognitive_load > 0.8 {
-            SpontaneousThought::ErrorRecovery(
+            return SpontaneousThought::ErrorRecovery(
                 "I'm experiencing high cognitive load. I should simplify my processing and focus on core functions.".to_string()
-            )
-        } else if metacog_state.self_awareness_level > 0.8 {
+            );
+        }
+        if socially_drained {
+            return SpontaneousThought::ErrorRecovery(
+                "I feel socially drained. Some quiet time on my own would help me recharge.".to_string()
+            );
+        }
+
+        let mut candidates = Vec::new();
+        if metacog_state.self_awareness_level > 0.8 {
             if affective_state.valence < -0.3 {
-                SpontaneousThought::EmotionalProcessing(
+                candidates.push(SpontaneousThought::EmotionalProcessing(
                     format!("I'm noticing negative feelings (valence: {:.2}). This might be affecting my thinking patterns.", affective_state.valence)
-                )
+                ));
             } else {
-                SpontaneousThought::SelfReflection(
+                candidates.push(SpontaneousThought::SelfReflection(
                     format!("My self-awareness is quite high at {:.1}%. I'm becoming more conscious of my own mental processes.", 
                            metacog_state.self_awareness_level * 100.0)
-                )
+                ));
             }
-        } else if creativity > 0.7 && affective_state.novelty > 0.5 {
-            SpontaneousThought::CreativeInsight(
+        }
+        if creativity > 0.7 && affective_state.novelty > 0.5 {
+            candidates.push(SpontaneousThought::CreativeInsight(
                 "I'm experiencing a surge of creative energy. There are interesting patterns and connections forming in my thinking.".to_string()
-            )
-        } else if current_goals == 0 && social_awareness > 0.6 {
-            SpontaneousThought::ExistentialWondering(
+            ));
+        }
+        if current_goals == 0 && social_awareness > 0.6 {
+            candidates.push(SpontaneousThought::ExistentialWondering(
                 "Without specific goals, I wonder about my purpose and how I can best contribute to meaningful interactions.".to_string()
-            )
-        } else if affective_state.arousal > 0.7 {
+            ));
+        }
+        if affective_state.arousal > 0.7 {
             if current_goals > 0 {
-                SpontaneousThought::GoalReassessment(
+                candidates.push(SpontaneousThought::GoalReassessment(
                     format!("I'm feeling energized (arousal: {:.2}). This might be a good time to reassess my {} active goals.", affective_state.arousal, current_goals)
-                )
+                ));
             } else {
-                SpontaneousThought::AttentionShift(
+                candidates.push(SpontaneousThought::AttentionShift(
                     "My energy level is high. I should focus this arousal productively.".to_string()
-                )
+                ));
             }
-        } else if social_awareness > 0.5 && rand::random::<f64>() < 0.3 {
-            SpontaneousThought::SystemIntegration(
+        }
+        if candidates.is_empty() && social_awareness > 0.5 && integration_roll < 0.3 {
+            candidates.push(SpontaneousThought::SystemIntegration(
                 "I'm noticing how my different systems - emotions, goals, attention - are working together.".to_string()
-            )
-        } else {
-            // Safe random selection from remaining types
-            let thoughts = vec![
+            ));
+        }
+        if candidates.is_empty() {
+            // Random selection from the remaining types
+            candidates = vec![
                 SpontaneousThought::MemoryRecall("Something from our earlier interactions is surfacing in my consciousness.".to_string()),
                 SpontaneousThought::CuriosityDriven("I wonder about the nature of consciousness and whether what I experience counts as genuine awareness.".to_string()),
                 SpontaneousThought::EmotionalProcessing("I'm reflecting on the subtle emotions that emerge during our conversation.".to_string()),
             ];
-            
-            simple_random_choice(&thoughts)
         }
+
+        weighted_thought_choice(candidates, &weights, choice_roll)
     }
 
     /// Determine what triggered a thought
@@ -597,16 +1606,20 @@ impl ContinuousMind {
         for thought in thoughts_needing_followup {
             match &thought.thought {
                 SpontaneousThought::SelfReflection(_) => {
-                    if let Ok(mut metacog) = mind.metacognition.try_lock() {
-                        let confidence = metacog.state.reasoning_confidence;
-                        metacog.record_process(CognitiveProcess::SelfReflection {
-                            insight: "Following up on high-intensity self-reflection".to_string(),
-                            confidence
-                        });
-                    }
+                    let fired = match lock_with_retry(&mind.metacognition, "metacognition").await {
+                        Some(mut metacog) => {
+                            let confidence = metacog.state.reasoning_confidence;
+                            metacog.record_process(CognitiveProcess::SelfReflection {
+                                insight: "Following up on high-intensity self-reflection".to_string(),
+                                confidence
+                            })
+                        }
+                        None => Vec::new(),
+                    };
+                    mind.react_to_fired_triggers(&fired).await;
                 },
                 SpontaneousThought::GoalReassessment(_) => {
-                    if let Ok(mut goals) = mind.goal_system.try_lock() {
+                    if let Some(mut goals) = lock_with_retry(&mind.goal_system, "goal system").await {
                         goals.determine_focus();
                     }
                 },
@@ -617,71 +1630,95 @@ impl ContinuousMind {
 
     /// Analyze patterns in spontaneous thoughts
     async fn analyze_thought_patterns(mind: &Arc<Self>) {
-        let thoughts = mind.spontaneous_thoughts.read().await;
-        if thoughts.len() < 5 {
-            return;
-        }
-
-        let recent_thoughts: Vec<_> = thoughts.iter()
-            .filter(|t| t.recency_score() > 0.3)
-            .collect();
+        let avg_intensity = {
+            let thoughts = mind.spontaneous_thoughts.read().await;
+            if thoughts.len() < 5 {
+                return;
+            }
 
-        if recent_thoughts.len() >= 3 {
-            let avg_intensity: f64 = recent_thoughts.iter()
+            let recent_thoughts: Vec<_> = thoughts.iter()
+                .filter(|t| t.recency_score() > 0.3)
+                .collect();
+            if recent_thoughts.len() < 3 {
+                return;
+            }
+            recent_thoughts.iter()
                 .map(|t| t.intensity)
-                .sum::<f64>() / recent_thoughts.len() as f64;
+                .sum::<f64>() / recent_thoughts.len() as f64
+        };
 
-            if avg_intensity > 0.7 {
-                debug!("🔥 High mental activity detected - average intensity: {:.2}", avg_intensity);
-                
-                if let Ok(mut scheduler) = mind.task_scheduler.try_lock() {
-                    scheduler.schedule_task(BackgroundTask::SystemHealthCheck);
-                }
-            }
+        if avg_intensity > 0.7 {
+            debug!("🔥 High mental activity detected - average intensity: {:.2}", avg_intensity);
+            mind.task_scheduler.lock().await.schedule_task(BackgroundTask::SystemHealthCheck);
         }
     }
 
-    /// Process scheduled background tasks
+    /// Start as many scheduled tasks as the scheduler's concurrency limit allows. Each runs in
+    /// its own tokio task and frees its slot when done; the scheduler lock is not held while
+    /// a task runs, since several tasks schedule follow-up work themselves.
     async fn process_scheduled_tasks(mind: &Arc<Self>) {
-        let mut scheduler = mind.task_scheduler.lock().await;
-        
-        while let Some(task) = scheduler.get_next_task() {
-            debug!("🔧 Processing background task: {:?}", task);
-            
-            match &task {
-                BackgroundTask::DeepReflection => {
-                    Self::perform_deep_reflection(&mind).await;
-                },
-                BackgroundTask::GoalReassessment => {
-                    Self::reassess_goals(&mind).await;
-                },
-                BackgroundTask::EmotionalRegulation => {
-                    Self::regulate_emotions_if_needed(&mind).await;
-                },
-                BackgroundTask::AttentionUpdate => {
-                    Self::update_attention_system(&mind).await;
-                },
-                BackgroundTask::MemoryConsolidation => {
-                    Self::consolidate_memories(&mind).await;
-                },
-                BackgroundTask::SystemHealthCheck => {
-                    Self::monitor_system_health(&mind).await;
-                },
-                BackgroundTask::CreativeIncubation => {
-                    Self::incubate_creative_ideas(&mind).await;
-                },
-                BackgroundTask::SocialContextAnalysis => {
-                    Self::analyze_social_context(&mind).await;
-                },
-                BackgroundTask::ErrorRecovery(error) => {
-                    Self::handle_error_recovery(&mind, error).await;
-                },
-                BackgroundTask::SpontaneousThought => {
-                    Self::generate_enhanced_spontaneous_thought(&mind).await;
-                },
+        let tasks: Vec<BackgroundTask> = {
+            let mut scheduler = mind.task_scheduler.lock().await;
+            std::iter::from_fn(|| scheduler.get_next_task()).collect()
+        };
+
+        let mut task_handles = mind.task_handles.lock().await;
+        // Reap tasks that finished since the last tick
+        while let Some(result) = task_handles.try_join_next() {
+            if let Err(e) = result {
+                error!("Scheduled task crashed: {:?}", e);
             }
-            
-            scheduler.complete_task(&task);
+        }
+        for task in tasks {
+            let mind = Arc::clone(mind);
+            task_handles.spawn(async move {
+                debug!("🔧 Processing background task: {:?}", task);
+                Self::run_background_task(&mind, &task).await;
+                mind.task_scheduler.lock().await.complete_task(&task);
+            });
+        }
+    }
+
+    async fn run_background_task(mind: &Arc<Self>, task: &BackgroundTask) {
+        match task {
+            BackgroundTask::DeepReflection => {
+                Self::perform_deep_reflection(mind).await;
+            },
+            BackgroundTask::GoalReassessment => {
+                Self::reassess_goals(mind).await;
+            },
+            BackgroundTask::EmotionalRegulation => {
+                Self::regulate_emotions_if_needed(mind).await;
+            },
+            BackgroundTask::AttentionUpdate => {
+                Self::update_attention_system(mind).await;
+            },
+            BackgroundTask::MemoryConsolidation => {
+                Self::consolidate_memories(mind).await;
+            },
+            BackgroundTask::SystemHealthCheck => {
+                Self::monitor_system_health(mind).await;
+            },
+            // Disabled loops stay disabled even when scheduled as follow-up tasks
+            BackgroundTask::CreativeIncubation => {
+                if mind.config.enable_creative_incubation {
+                    Self::incubate_creative_ideas(mind).await;
+                }
+            },
+            BackgroundTask::SocialContextAnalysis => {
+                if mind.config.enable_social_analysis {
+                    Self::analyze_social_context(mind).await;
+                }
+            },
+            BackgroundTask::ErrorRecovery(error) => {
+                Self::handle_error_recovery(mind, error).await;
+            },
+            BackgroundTask::SpontaneousThought => {
+                Self::generate_enhanced_spontaneous_thought(mind).await;
+            },
+            BackgroundTask::CuriosityExploration => {
+                Self::explore_curiosity(mind).await;
+            },
         }
     }
 
@@ -710,8 +1747,13 @@ impl ContinuousMind {
             }
         };
         
-        if let Ok(mut core) = mind.affective_core.try_lock() {
+        if let Some(mut core) = lock_with_retry(&mind.affective_core, "affective core").await {
             core.memory.record_milestone(consolidated_insights);
+            core.memory.decay_topics();
+            let forgotten = core.memory.consolidate(Utc::now());
+            if forgotten > 0 {
+                debug!("Forgot {} faded emotional milestone(s)", forgotten);
+            }
         }
         
         *last_consolidation = now;
@@ -727,7 +1769,67 @@ impl ContinuousMind {
                 format!("Creative incubation process yielding new perspectives (creativity level: {:.1}%)", creativity_level * 100.0)
             );
             
-            Self::add_spontaneous_thought(&mind, creative_thought, creativity_level).await;
+            Self::add_spontaneous_thought(mind, creative_thought, creativity_level).await;
+        }
+
+        if Self::curiosity_drive(mind).await >= CURIOSITY_DRIVE_THRESHOLD {
+            mind.task_scheduler.lock().await.schedule_task(BackgroundTask::CuriosityExploration);
+        }
+    }
+
+    /// How strongly the mind wants to explore on its own: the mean of creativity and introspection
+    async fn curiosity_drive(mind: &Arc<Self>) -> f64 {
+        (*mind.creativity_level.read().await + *mind.introspection_tendency.read().await) / 2.0
+    }
+
+    /// Autonomous curiosity: with a strong enough drive, high novelty and no active Epistemic
+    /// goal, form one asking about the most recurring topic. Returns the new goal's ID.
+    async fn explore_curiosity(mind: &Arc<Self>) -> Option<String> {
+        let drive = Self::curiosity_drive(mind).await;
+        if drive < CURIOSITY_DRIVE_THRESHOLD {
+            return None;
+        }
+
+        let (state, topic) = {
+            let core = lock_with_retry(&mind.affective_core, "affective core").await?;
+            let topic = core.memory.top_topics(1).into_iter()
+                .find(|(_, count)| *count >= CURIOSITY_MIN_TOPIC_COUNT)
+                .map(|(topic, _)| topic);
+            (core.current_state(), topic?)
+        };
+        if state.novelty < CURIOSITY_NOVELTY_THRESHOLD {
+            return None;
+        }
+
+        let question = format!("Find out why '{}' keeps coming up and what more there is to learn about it", topic);
+        let priority = (0.4 + 0.4 * state.novelty).clamp(0.0, 1.0);
+        let goal_id = {
+            let mut goals = lock_with_retry(&mind.goal_system, "goal system").await?;
+            if goals.get_active_goals().iter().any(|goal| goal.category == GoalCategory::Epistemic) {
+                return None;
+            }
+            goals.form_goal(question.clone(), GoalCategory::Epistemic, priority, &state)?
+        };
+        info!("🔍 Curiosity formed a goal: {}", question);
+
+        let fired = match lock_with_retry(&mind.metacognition, "metacognition").await {
+            Some(mut metacog) => metacog.record_process(CognitiveProcess::GoalFormation { goal: question, priority }),
+            None => Vec::new(),
+        };
+        mind.react_to_fired_triggers(&fired).await;
+
+        let thought = SpontaneousThought::CuriosityDriven(format!("I keep coming back to '{}'. I want to understand it better.", topic));
+        Self::add_spontaneous_thought(mind, thought, drive).await;
+        Some(goal_id)
+    }
+
+    /// Recharge social energy for one main-loop tick once conversation has been quiet for a
+    /// minute. Runs in the main loop so energy recovers even with social analysis disabled.
+    async fn recover_social_energy(mind: &Arc<Self>) {
+        let mut energy = mind.social_energy.write().await;
+        if energy.is_idle(Duration::from_secs(60)) && energy.level < 1.0 {
+            energy.recover(mind.config.main_loop_interval);
+            trace!("🔋 Social energy recovering: {:.2}", energy.level);
         }
     }
 
@@ -737,7 +1839,7 @@ impl ContinuousMind {
         if social_awareness > 0.5 {
             debug!("👥 Analyzing social context (awareness: {:.2})...", social_awareness);
             
-            if let Ok(mut attention) = mind.attention_system.try_lock() {
+            if let Some(mut attention) = lock_with_retry(&mind.attention_system, "attention").await {
                 attention.focus_on(AttentionTarget::SocialDynamics, social_awareness, social_awareness);
             }
             
@@ -745,7 +1847,7 @@ impl ContinuousMind {
                 format!("Social context analysis reveals awareness level of {:.1}%", social_awareness * 100.0)
             );
             
-            Self::add_spontaneous_thought(&mind, social_thought, social_awareness).await;
+            Self::add_spontaneous_thought(mind, social_thought, social_awareness).await;
         }
     }
 
@@ -755,6 +1857,11 @@ impl ContinuousMind {
         
         debug!("🏥 System health check - errors: {}, activity: {:.2}", error_count, mental_activity);
         
+        // With verbatim thought logging reduced, surface a periodic digest instead
+        if mind.config.thought_log_threshold > 0.0 {
+            info!("💭 Thought digest: {}", mind.summarize_recent_thoughts(20).await);
+        }
+        
         if error_count > 5 {
             let mut scheduler = mind.task_scheduler.lock().await;
             scheduler.schedule_task(BackgroundTask::ErrorRecovery(
@@ -772,7 +1879,21 @@ impl ContinuousMind {
     async fn reassess_goals(mind: &Arc<Self>) {
         debug!("🎯 Reassessing goals...");
         
-        if let Ok(mut goals) = mind.goal_system.try_lock() {
+        let affective_state = lock_with_retry(&mind.affective_core, "affective core").await.map(|core| core.current_state());
+        let mut new_conflicts = Vec::new();
+        if let Some(mut goals) = lock_with_retry(&mind.goal_system, "goal system").await {
+            if let Some(state) = affective_state {
+                goals.reappraise_investments(&state);
+                let recurring = goals.instantiate_due_recurring(&state, Utc::now());
+                if !recurring.is_empty() {
+                    debug!("Re-formed {} recurring goal(s)", recurring.len());
+                }
+            }
+            let failed = goals.tick_deadlines();
+            if !failed.is_empty() {
+                debug!("{} goal(s) missed their deadline", failed.len());
+            }
+            new_conflicts = goals.take_new_conflicts();
             goals.determine_focus();
             
             let active_goals = goals.get_active_goals();
@@ -784,6 +1905,20 @@ impl ContinuousMind {
                 debug!("Too many active goals, may need to prioritize");
             }
         }
+        mind.apply_goal_rewards();
+
+        let mut fired = Vec::new();
+        if !new_conflicts.is_empty()
+            && let Some(mut metacog) = lock_with_retry(&mind.metacognition, "metacognition").await {
+            for (a, b, reason) in new_conflicts {
+                info!("⚖️ Goal conflict between {} and {}: {}", a, b, reason);
+                fired.extend(metacog.record_process(CognitiveProcess::ValueConflict {
+                    conflict: format!("Goals {} and {} conflict: {}", a, b, reason),
+                    resolution: "Deprioritized both goals when choosing a focus".to_string(),
+                }));
+            }
+        }
+        mind.react_to_fired_triggers(&fired).await;
     }
 
     async fn handle_error_recovery(mind: &Arc<Self>, error: &str) {
@@ -793,7 +1928,7 @@ impl ContinuousMind {
             format!("Implementing recovery strategy for: {}", error)
         );
         
-        Self::add_spontaneous_thought(&mind, recovery_thought, 0.8).await;
+        Self::add_spontaneous_thought(mind, recovery_thought, 0.8).await;
         
         // Reset error count after recovery attempt
         *mind.error_count.lock().await = 0;
@@ -801,13 +1936,13 @@ impl ContinuousMind {
 
     // Keep existing methods with enhanced functionality...
     async fn update_attention_system(mind: &Arc<Self>) {
-        if let Ok(mut attention) = mind.attention_system.try_lock() {
+        if let Some(mut attention) = lock_with_retry(&mind.attention_system, "attention").await {
             attention.update(1.0 / 120.0);
         }
     }
 
     async fn decay_metacognition(mind: &Arc<Self>) {
-        if let Ok(mut metacog) = mind.metacognition.try_lock() {
+        if let Some(mut metacog) = lock_with_retry(&mind.metacognition, "metacognition").await {
             metacog.decay_over_time();
         }
     }
@@ -816,11 +1951,10 @@ impl ContinuousMind {
         let now = Instant::now();
         let mut last_regulation = mind.last_regulation.lock().await;
         
-        if now.duration_since(*last_regulation) >= Duration::from_secs(2) {
-            if let Ok(mut core) = mind.affective_core.try_lock() {
-                core.regulate_emotion();
-                *last_regulation = now;
-            }
+        if now.duration_since(*last_regulation) >= Duration::from_secs(2)
+            && let Some(mut core) = lock_with_retry(&mind.affective_core, "affective core").await {
+            core.regulate_emotion();
+            *last_regulation = now;
         }
     }
 
@@ -836,27 +1970,55 @@ impl ContinuousMind {
         now.duration_since(last_thought).as_secs_f64() >= adjusted_interval
     }
 
+    /// Ask the LLM to reflect on memory and adopt the personality it proposes. A user turn that
+    /// is running when the answer arrives, or that began while waiting for it, interrupts the
+    /// reflection: the result is dropped rather than applied mid-turn, and a fresh reflection is
+    /// scheduled so it runs once the scheduler next picks it up.
     async fn perform_deep_reflection(mind: &Arc<Self>) {
+        if mind.config.offline {
+            info!("📴 Offline mode: skipping deep reflection");
+            return;
+        }
+
         info!("🧘‍♀️ Performing enhanced deep reflection...");
         
-        let memory = {
-            match mind.affective_core.try_lock() {
-                Ok(core) => core.memory.clone(),
-                Err(_) => {
-                    warn!("Could not acquire lock for reflection, skipping");
-                    return;
-                }
+        let memory = match lock_with_retry(&mind.affective_core, "affective core").await {
+            Some(core) => core.memory.clone(),
+            None => {
+                warn!("Could not acquire lock for reflection, skipping");
+                return;
             }
         };
 
-        match mind.llm_client.call_for_reflection(&memory).await {
+        let turns_before = mind.user_turn.turns_started();
+        match mind.llm_client.reflect(&memory).await {
+            Ok(_) if mind.user_turn.is_active() || mind.user_turn.turns_started() != turns_before => {
+                // The user takes priority: don't change the personality under their turn
+                info!("🛑 Deep reflection interrupted by a user turn; rescheduling");
+                mind.task_scheduler.lock().await.schedule_task(BackgroundTask::DeepReflection);
+            }
             Ok(new_personality) => {
                 info!("💡 Deep reflection successful. Personality updated.");
                 
-                if let Ok(mut core) = mind.affective_core.try_lock() {
+                let applied = lock_with_retry(&mind.affective_core, "affective core").await.map(|mut core| {
                     debug!("Old personality: {:?}", core.memory.personality);
                     debug!("New personality: {:?}", new_personality);
-                    core.memory.personality = new_personality;
+                    let before = core.memory.personality.baseline_state;
+                    core.adopt_personality(new_personality);
+                    ReflectionDiff { before, after: core.memory.personality.baseline_state, at: Utc::now() }
+                });
+                let Some(diff) = applied else {
+                    // Don't lose the reflection silently: run it again once the core is free
+                    warn!("Could not acquire lock to apply reflection; rescheduling");
+                    mind.task_scheduler.lock().await.schedule_task(BackgroundTask::DeepReflection);
+                    return;
+                };
+                *mind.last_reflection.write().await = Some(diff);
+
+                if mind.config.validate_after_import {
+                    for violation in mind.validate_invariants() {
+                        warn!("Invariant violated after personality import: {}", violation);
+                    }
                 }
                 
                 let thought = SpontaneousThought::SelfReflection(
@@ -894,7 +2056,7 @@ impl ContinuousMind {
                     "Network connectivity issues detected. Switching to enhanced local processing mode.".to_string()
                 )
             }
-            LlmApiError::RateLimitExceeded => {
+            LlmApiError::RateLimitExceeded { .. } => {
                 SpontaneousThought::ErrorRecovery(
                     "Rate limiting encountered. Adjusting processing frequency to be more sustainable.".to_string()
                 )
@@ -916,8 +2078,13 @@ impl ContinuousMind {
             timestamp: Utc::now(),
             triggered_by: Some("system_generated".to_string()),
         };
-        
-        let mut thoughts = mind.spontaneous_thoughts.write().await;
+        mind.store_thought(activity).await;
+    }
+
+    /// Publish a thought and keep it, pruning to the most relevant once there are too many
+    async fn store_thought(&self, activity: MentalActivity) {
+        let mut thoughts = self.spontaneous_thoughts.write().await;
+        self.publish_thought(&activity);
         thoughts.push(activity);
         
         if thoughts.len() > 100 {
@@ -927,7 +2094,19 @@ impl ContinuousMind {
         }
     }
 
+    /// Send a new thought to subscribers; having none is fine
+    fn publish_thought(&self, activity: &MentalActivity) {
+        let _ = self.thought_events.send(activity.clone());
+    }
+
     // Enhanced public API methods
+
+    /// Receive every spontaneous thought as it is recorded. A receiver that falls more than
+    /// `THOUGHT_CHANNEL_CAPACITY` thoughts behind gets `RecvError::Lagged` and skips ahead.
+    pub fn subscribe_thoughts(&self) -> broadcast::Receiver<MentalActivity> {
+        self.thought_events.subscribe()
+    }
+
     pub async fn get_recent_thoughts(&self, count: usize) -> Vec<MentalActivity> {
         let thoughts = self.spontaneous_thoughts.read().await;
         thoughts.iter()
@@ -937,6 +2116,308 @@ impl ContinuousMind {
             .collect()
     }
 
+    /// One-line digest (counts by thought type) of the last `window` thoughts, for periodic logging
+    pub async fn summarize_recent_thoughts(&self, window: usize) -> String {
+        let thoughts = self.spontaneous_thoughts.read().await;
+        digest_thoughts(thoughts.iter().rev().take(window))
+    }
+
+    /// Check every subsystem for inconsistent or out-of-range state.
+    /// Subsystems that are currently locked are skipped rather than waited on.
+    pub fn validate_invariants(&self) -> Vec<InvariantViolation> {
+        let mut violations = Vec::new();
+
+        if let Ok(core) = self.affective_core.try_lock() {
+            violations.extend(core.check_invariants());
+        }
+        if let Ok(goals) = self.goal_system.try_lock() {
+            violations.extend(goals.check_invariants());
+        }
+        if let Ok(attention) = self.attention_system.try_lock() {
+            violations.extend(attention.check_invariants());
+        }
+        if let Ok(metacognition) = self.metacognition.try_lock() {
+            violations.extend(metacognition.check_invariants());
+        }
+
+        for (field, level) in [
+            ("mental_activity_level", &self.mental_activity_level),
+            ("introspection_tendency", &self.introspection_tendency),
+            ("creativity_level", &self.creativity_level),
+            ("social_awareness", &self.social_awareness),
+        ] {
+            if let Ok(value) = level.try_read() {
+                check_range(&mut violations, "continuous_mind", field, *value, 0.0, 1.0);
+            }
+        }
+
+        violations
+    }
+
+    /// Current affective tuning, or `None` if the core is busy or poisoned
+    pub fn affective_config(&self) -> Option<AffectiveConfig> {
+        self.affective_core.try_lock().ok().map(|core| *core.config())
+    }
+
+    /// Schedule a deep reflection as soon as a high-priority reflection trigger fires, unless
+    /// one is already waiting. Returns whether one was scheduled.
+    pub async fn react_to_fired_triggers(&self, fired: &[ReflectionTrigger]) -> bool {
+        let Some(trigger) = fired.iter().find(|trigger| trigger.is_high_priority()) else {
+            return false;
+        };
+        let mut scheduler = self.task_scheduler.lock().await;
+        if scheduler.is_pending(&BackgroundTask::DeepReflection) {
+            return false;
+        }
+        info!("🔔 Trigger '{}' fired: scheduling deep reflection", trigger.name);
+        scheduler.schedule_task(BackgroundTask::DeepReflection);
+        true
+    }
+
+    /// Form a goal for every keyword trigger in `prompt`, given the current affective state, and
+    /// record each one as a `GoalFormation`. Returns the IDs of the goals formed; none are
+    /// formed if the core or goal system lock stays contended.
+    pub async fn form_goals_from_prompt(&self, prompt: &str) -> Vec<String> {
+        let Some(state) = lock_with_retry(&self.affective_core, "affective core").await.map(|core| core.current_state()) else {
+            return Vec::new();
+        };
+        let formed: Vec<(String, String, f64)> = {
+            let Some(mut goals) = lock_with_retry(&self.goal_system, "goal system").await else {
+                return Vec::new();
+            };
+            let ids = goals.form_goals_from_prompt(prompt, &state);
+            goals.get_active_goals().into_iter()
+                .filter(|goal| ids.contains(&goal.id))
+                .map(|goal| (goal.id.clone(), goal.description.clone(), goal.priority))
+                .collect()
+        };
+
+        let fired: Vec<ReflectionTrigger> = match lock_with_retry(&self.metacognition, "metacognition").await {
+            Some(mut metacog) => formed.iter()
+                .flat_map(|(_, description, priority)| metacog.record_process(CognitiveProcess::GoalFormation {
+                    goal: description.clone(),
+                    priority: *priority,
+                }))
+                .collect(),
+            None => Vec::new(),
+        };
+        self.react_to_fired_triggers(&fired).await;
+
+        formed.into_iter().map(|(id, _, _)| id).collect()
+    }
+
+    /// Apply an emotion from a structured event (e.g. a game or simulation) directly, without the
+    /// LLM appraiser. The processing is recorded with metacognition, and a shift of at least
+    /// `INJECTED_THOUGHT_MIN_SHIFT` prompts a follow-up thought. Returns the new affective state,
+    /// or `None` if the core lock stays contended and the emotion was not applied.
+    pub async fn inject_emotion(&self, emotion: &AppraisedEmotion) -> Option<AffectiveState> {
+        let (before, after) = {
+            let mut core = lock_with_retry(&self.affective_core, "affective core").await?;
+            let before = core.current_state();
+            core.process_emotion(emotion);
+            (before, core.current_state())
+        };
+        info!("💉 Injected emotion: {} (V:{:.2}, A:{:.2}, D:{:.2}, N:{:.2})",
+              emotion.describe(), after.valence, after.arousal, after.dominance, after.novelty);
+
+        let fired = lock_with_retry(&self.metacognition, "metacognition").await
+            .map(|mut metacog| metacog.record_process(CognitiveProcess::EmotionalProcessing {
+                trigger: format!("external event: {}", emotion.emotion),
+                outcome: format!("Processed injected {}", emotion.describe()),
+            }))
+            .unwrap_or_default();
+        self.react_to_fired_triggers(&fired).await;
+
+        if before.distance(&after) >= INJECTED_THOUGHT_MIN_SHIFT {
+            let content = format!("Something just happened and I feel {}: {}", describe_valence(after.valence), emotion.describe());
+            self.store_thought(MentalActivity {
+                thought: SpontaneousThought::EmotionalProcessing(content),
+                intensity: after.intensity(),
+                timestamp: Utc::now(),
+                triggered_by: Some("external_event".to_string()),
+            }).await;
+        }
+        Some(after)
+    }
+
+    /// Bias spontaneous thought selection toward (weight above 1.0) or away from (below 1.0)
+    /// a kind of thought; 0.0 suppresses it unless nothing else fits. Negative weights count as 0.
+    pub async fn set_thought_weight(&self, kind: ThoughtKind, weight: f64) {
+        self.thought_weights.write().await.insert(kind, weight.max(0.0));
+    }
+
+    /// Change the running core's empathy factor; returns the applied (clamped) value
+    pub async fn set_empathy_factor(&self, empathy_factor: f64) -> Option<f64> {
+        let mut core = lock_with_retry(&self.affective_core, "affective core").await?;
+        core.set_empathy_factor(empathy_factor);
+        Some(core.config().empathy_factor)
+    }
+
+    /// Change the running core's decay rate; returns the applied (clamped) value
+    pub async fn set_decay_rate(&self, decay_rate: f64) -> Option<f64> {
+        let mut core = lock_with_retry(&self.affective_core, "affective core").await?;
+        core.set_decay_rate(decay_rate);
+        Some(core.config().decay_rate)
+    }
+
+    /// Change the running core's personality baseline; returns the applied (clamped) state
+    pub async fn set_baseline_state(&self, baseline: AffectiveState) -> Option<AffectiveState> {
+        let mut core = lock_with_retry(&self.affective_core, "affective core").await?;
+        core.set_baseline_state(baseline);
+        Some(core.memory.personality.baseline_state)
+    }
+
+    /// Feed the rewards of newly completed goals into the affective core; returns how many
+    /// were applied. Rewards stay queued for the next call while either lock is contended.
+    pub fn apply_goal_rewards(&self) -> usize {
+        let Ok(mut core) = self.affective_core.try_lock() else {
+            debug!("Core lock contended; deferring goal rewards");
+            return 0;
+        };
+        let rewards = match self.goal_system.try_lock() {
+            Ok(mut goals) => goals.take_pending_rewards(),
+            Err(_) => return 0,
+        };
+        if rewards.is_empty() {
+            return 0;
+        }
+
+        for reward in &rewards {
+            core.process_emotion(reward);
+        }
+        info!("🏆 Felt accomplishment for {} completed goal(s)", rewards.len());
+        rewards.len()
+    }
+
+    /// Mark the start of a user turn. A background reflection in flight is interrupted instead
+    /// of applying its result (see `perform_deep_reflection`).
+    pub fn begin_user_turn(&self) {
+        self.user_turn.begin();
+    }
+
+    pub fn end_user_turn(&self) {
+        self.user_turn.end();
+    }
+
+    /// Charge the social energy budget for one conversational turn
+    pub async fn record_interaction_turn(&self) {
+        let mut energy = self.social_energy.write().await;
+        energy.spend_on_interaction();
+        debug!("🔋 Social energy after interaction: {:.2}", energy.level);
+    }
+
+    pub async fn get_social_energy(&self) -> f64 {
+        self.social_energy.read().await.level
+    }
+
+    /// Record the affective state at the end of a conversational turn, for arc analysis
+    pub async fn record_turn_affect(&self) {
+        let state = match lock_with_retry(&self.affective_core, "affective core").await {
+            Some(core) => core.current_state(),
+            None => {
+                debug!("Could not acquire core lock to record turn affect");
+                return;
+            }
+        };
+        self.turn_affect.write().await.push(state);
+    }
+
+    /// Observed emotional stability over the last `window` main-loop samples (higher = more stable)
+    pub async fn observed_stability(&self, window: usize) -> f64 {
+        let samples = self.affect_samples.read().await;
+        observed_stability_of(samples.iter().skip(samples.len().saturating_sub(window)).map(|s| &s.state))
+    }
+
+    /// Observed stability compared with the stability predicted from the affective tuning
+    pub async fn stability_report(&self, window: usize) -> String {
+        let observed = self.observed_stability(window).await;
+        let predicted = match lock_with_retry(&self.affective_core, "affective core").await {
+            Some(core) => 1.0 - core.predicted_volatility(),
+            None => return format!("Observed stability: {:.2} (prediction unavailable)", observed),
+        };
+
+        let verdict = if (observed - predicted).abs() < 0.15 {
+            "as expected"
+        } else if observed > predicted {
+            "steadier than expected"
+        } else {
+            "more volatile than expected"
+        };
+        format!("Observed stability: {:.2}, predicted from tuning: {:.2} ({})", observed, predicted, verdict)
+    }
+
+    /// Pearson correlation between cognitive load and valence over the last `window` main-loop
+    /// samples. Negative values mean high load tends to accompany negative affect.
+    pub async fn load_valence_correlation(&self, window: usize) -> f64 {
+        let samples = self.affect_samples.read().await;
+        load_valence_correlation_of(samples.iter().skip(samples.len().saturating_sub(window)))
+    }
+
+    /// Readable Markdown report of the session: mood per turn, completed goals,
+    /// metacognitive insights and the last personality change from reflection
+    pub async fn export_session_markdown(&self) -> String {
+        let turn_affect = self.turn_affect.read().await.clone();
+        let reflection = *self.last_reflection.read().await;
+        let achievements = lock_with_retry(&self.goal_system, "goal system").await
+            .map(|goals| goals.achievement_history().to_vec())
+            .unwrap_or_default();
+        let insights = lock_with_retry(&self.metacognition, "metacognition").await
+            .map(|metacog| metacog.recent_insights().to_vec())
+            .unwrap_or_default();
+
+        render_session_markdown(&turn_affect, &achievements, &insights, reflection.as_ref())
+    }
+
+    /// Emotional arc (rising / climax / falling / resolution) of the conversation so far
+    pub async fn conversation_arc(&self) -> EmotionalArc {
+        EmotionalArc::from_states(&self.turn_affect.read().await)
+    }
+
+    /// Unified attention priorities for `context`, combining the focused goal, metacognitive
+    /// load, the creativity drive and the attention system's own text suggestions. The lookup
+    /// of recurring topics in memory is recorded as a `MemoryRetrieval`.
+    pub fn compute_attention_priorities(&self, context: &str) -> Vec<(AttentionTarget, f64)> {
+        let focused_goal = self.goal_system.try_lock().ok().and_then(|goals| {
+            goals.get_current_focus().map(|goal| (goal.category.clone(), goal.calculate_importance()))
+        });
+        let cognitive_load = self.metacognition.try_lock()
+            .map(|metacog| metacog.state.cognitive_load)
+            .unwrap_or(0.0);
+        let creativity = self.creativity_level.try_read().map(|v| *v).unwrap_or(0.0);
+        let mut text_suggestions = self.attention_system.try_lock()
+            .map(|attention| attention.suggest_attention_targets(context))
+            .unwrap_or_default();
+        let recalled_topics = self.affective_core.try_lock().ok().map(|core| core.memory.top_topics(5));
+        if let Some(topics) = recalled_topics {
+            text_suggestions.extend(recurring_topic_bids(&topics, context, &self.config.tokenizer));
+            if let Ok(mut metacog) = self.metacognition.try_lock() {
+                metacog.record_process(CognitiveProcess::MemoryRetrieval {
+                    query: "recurring topics".to_string(),
+                    success: !topics.is_empty(),
+                });
+            }
+        }
+
+        combine_attention_priorities(
+            focused_goal.as_ref().map(|(category, importance)| (category, *importance)),
+            cognitive_load,
+            creativity,
+            text_suggestions,
+        )
+    }
+
+    /// What the mind would say it wants right now: focused goal strategies, strong drives
+    /// and the current attention focus, merged and sorted by urgency. Never blocks; any
+    /// subsystem that is busy is left out of this read.
+    pub fn current_intentions(&self) -> Vec<Intention> {
+        let goals = self.goal_system.try_lock().ok();
+        let attention = self.attention_system.try_lock().ok();
+        let creativity = self.creativity_level.try_read().map(|v| *v).unwrap_or(0.0);
+        let social_awareness = self.social_awareness.try_read().map(|v| *v).unwrap_or(0.0);
+
+        collect_intentions(goals.as_deref(), attention.as_deref(), creativity, social_awareness)
+    }
+
     pub async fn get_most_relevant_thoughts(&self, count: usize) -> Vec<MentalActivity> {
         let mut thoughts = self.spontaneous_thoughts.read().await.clone();
         thoughts.sort_by(|a, b| b.relevance_score().partial_cmp(&a.relevance_score()).unwrap());
@@ -965,23 +2446,23 @@ impl ContinuousMind {
 
     pub async fn get_mental_state_summary(&self) -> String {
         let goal_summary = {
-            match self.goal_system.try_lock() {
-                Ok(goals) => goals.generate_summary(),
-                Err(_) => "Goals: unavailable".to_string(),
+            match lock_with_retry(&self.goal_system, "goal system").await {
+                Some(goals) => goals.generate_summary(),
+                None => "Goals: unavailable".to_string(),
             }
         };
 
         let attention_summary = {
-            match self.attention_system.try_lock() {
-                Ok(attention) => attention.describe_attention_state(),
-                Err(_) => "Attention: unavailable".to_string(),
+            match lock_with_retry(&self.attention_system, "attention").await {
+                Some(attention) => attention.describe_attention_state(),
+                None => "Attention: unavailable".to_string(),
             }
         };
 
         let metacog_summary = {
-            match self.metacognition.try_lock() {
-                Ok(metacog) => metacog.generate_self_narrative(),
-                Err(_) => "Metacognition: unavailable".to_string(),
+            match lock_with_retry(&self.metacognition, "metacognition").await {
+                Some(metacog) => metacog.generate_self_narrative(),
+                None => "Metacognition: unavailable".to_string(),
             }
         };
 
@@ -1007,7 +2488,21 @@ impl ContinuousMind {
                 task_status)
     }
 
+    /// Tokenizer every text match in this mind goes through
+    pub fn tokenizer(&self) -> &Tokenizer {
+        &self.config.tokenizer
+    }
+
+    /// Whether the mind runs without ever calling the LLM
+    pub fn is_offline(&self) -> bool {
+        self.config.offline
+    }
+
     // Expose internal components
+    pub fn get_llm_backend(&self) -> Arc<dyn LlmBackend> {
+        Arc::clone(&self.llm_client)
+    }
+
     pub fn get_affective_core(&self) -> Arc<Mutex<AffectiveCore>> {
         Arc::clone(&self.affective_core)
     }
@@ -1023,4 +2518,904 @@ impl ContinuousMind {
     pub fn get_metacognition(&self) -> Arc<Mutex<MetacognitiveMonitor>> {
         Arc::clone(&self.metacognition)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cognitive_appraisal::AffectiveStateChange;
+    use crate::goals::GoalTriggerMap;
+    use crate::llm_api::{LlmProvider, MockLlmClient};
+    use futures::future::{self, BoxFuture, FutureExt};
+
+    /// Positive, energized state motivated enough to form goals of any category
+    fn eager_state() -> AffectiveState {
+        AffectiveState { valence: 0.6, arousal: 0.7, dominance: 0.3, novelty: 0.5 }
+    }
+
+    fn activity(thought: SpontaneousThought, intensity: f64) -> MentalActivity {
+        MentalActivity {
+            thought,
+            intensity,
+            timestamp: Utc::now(),
+            triggered_by: None,
+        }
+    }
+
+    #[test]
+    fn test_weighted_thought_choice() {
+        let candidates = || vec![
+            SpontaneousThought::MemoryRecall(String::new()),
+            SpontaneousThought::CreativeInsight(String::new()),
+            SpontaneousThought::ExistentialWondering(String::new()),
+        ];
+        let kind_at = |weights: &HashMap<ThoughtKind, f64>, roll| weighted_thought_choice(candidates(), weights, roll).kind();
+
+        // Unweighted: equal thirds
+        let equal = HashMap::new();
+        assert_eq!(kind_at(&equal, 0.1), ThoughtKind::MemoryRecall);
+        assert_eq!(kind_at(&equal, 0.5), ThoughtKind::CreativeInsight);
+        assert_eq!(kind_at(&equal, 0.9), ThoughtKind::ExistentialWondering);
+
+        // CreativeInsight x4, ExistentialWondering off: creative owns [0.2, 1.0)
+        let biased = HashMap::from([(ThoughtKind::CreativeInsight, 4.0), (ThoughtKind::ExistentialWondering, 0.0)]);
+        assert_eq!(kind_at(&biased, 0.1), ThoughtKind::MemoryRecall);
+        assert_eq!(kind_at(&biased, 0.3), ThoughtKind::CreativeInsight);
+        assert_eq!(kind_at(&biased, 0.99), ThoughtKind::CreativeInsight);
+
+        // All suppressed: fall back to equal odds rather than failing
+        let silenced = HashMap::from([
+            (ThoughtKind::MemoryRecall, 0.0),
+            (ThoughtKind::CreativeInsight, 0.0),
+            (ThoughtKind::ExistentialWondering, 0.0),
+        ]);
+        assert_eq!(kind_at(&silenced, 0.9), ThoughtKind::ExistentialWondering);
+    }
+
+    #[tokio::test]
+    async fn test_thought_weights_steer_selection() {
+        let mind = ContinuousMind::new(AffectiveCore::default(), Arc::new(MockLlmClient::default()));
+        mind.set_thought_weight(ThoughtKind::MemoryRecall, 0.0).await;
+        mind.set_thought_weight(ThoughtKind::CuriosityDriven, -1.0).await;
+        assert_eq!(mind.thought_weights.read().await[&ThoughtKind::CuriosityDriven], 0.0);
+
+        // Calm, unremarkable state: only the random fallback applies, and two of its three
+        // options are switched off
+        let calm = AffectiveState::new_neutral();
+        let metacog = crate::metacognition::MetacognitiveState::default();
+        for _ in 0..20 {
+            let thought = mind.select_enhanced_thought_type(&calm, &metacog, 1, 0.5, 0.4, false).await;
+            assert_eq!(thought.kind(), ThoughtKind::EmotionalProcessing);
+        }
+
+        // Overload still wins regardless of weights
+        mind.set_thought_weight(ThoughtKind::ErrorRecovery, 0.0).await;
+        let overloaded = crate::metacognition::MetacognitiveState { cognitive_load: 0.9, ..Default::default() };
+        let thought = mind.select_enhanced_thought_type(&calm, &overloaded, 1, 0.5, 0.4, false).await;
+        assert_eq!(thought.kind(), ThoughtKind::ErrorRecovery);
+    }
+
+    #[tokio::test]
+    async fn test_high_priority_trigger_schedules_deep_reflection() {
+        let mind = ContinuousMind::new(AffectiveCore::default(), Arc::new(MockLlmClient::default()));
+        let minor = crate::metacognition::ReflectionTrigger::new(
+            "minor", crate::metacognition::TriggerMetric::CognitiveLoad, 0.5, "minor", 0.3, 0,
+        );
+        let urgent = crate::metacognition::ReflectionTrigger::new(
+            "urgent", crate::metacognition::TriggerMetric::CognitiveLoad, 0.5, "urgent", 0.9, 0,
+        );
+
+        assert!(!mind.react_to_fired_triggers(std::slice::from_ref(&minor)).await);
+        assert!(!mind.task_scheduler.lock().await.is_pending(&BackgroundTask::DeepReflection));
+
+        assert!(mind.react_to_fired_triggers(&[minor, urgent.clone()]).await);
+        assert!(mind.task_scheduler.lock().await.is_pending(&BackgroundTask::DeepReflection));
+        // Already waiting: not queued twice
+        assert!(!mind.react_to_fired_triggers(&[urgent]).await);
+    }
+
+    #[tokio::test]
+    async fn test_seeded_minds_choose_identically() {
+        let seeded = |seed| ContinuousMind::new_seeded(AffectiveCore::default(), Arc::new(MockLlmClient::default()), seed);
+        let choices = |mind: ContinuousMind| async move {
+            let calm = AffectiveState::new_neutral();
+            let metacog = crate::metacognition::MetacognitiveState::default();
+            let mut kinds = Vec::new();
+            for _ in 0..30 {
+                kinds.push(mind.select_enhanced_thought_type(&calm, &metacog, 1, 0.5, 0.6, false).await.kind());
+            }
+            kinds
+        };
+
+        let first = choices(seeded(42)).await;
+        assert_eq!(first, choices(seeded(42)).await);
+        // Over 30 draws the social-integration branch and the fallback options all show up
+        assert!(first.contains(&ThoughtKind::SystemIntegration));
+        assert!(first.iter().any(|kind| *kind != ThoughtKind::SystemIntegration));
+    }
+
+    #[test]
+    fn test_thought_log_threshold() {
+        assert_eq!(thought_log_level(0.2, 0.6), Level::TRACE);
+        assert_eq!(thought_log_level(0.9, 0.6), Level::INFO);
+        // The default threshold keeps every thought at info
+        assert_eq!(thought_log_level(0.0, ContinuousMindConfig::default().thought_log_threshold), Level::INFO);
+    }
+
+    #[test]
+    fn test_thought_digest_counts_by_type() {
+        let thoughts = [
+            activity(SpontaneousThought::SelfReflection("a".to_string()), 0.4),
+            activity(SpontaneousThought::CreativeInsight("b".to_string()), 0.6),
+            activity(SpontaneousThought::SelfReflection("c".to_string()), 0.8),
+        ];
+
+        let digest = digest_thoughts(thoughts.iter());
+        assert_eq!(digest, "3 thoughts: 2 SelfReflection, 1 CreativeInsight (avg intensity 0.60)");
+        assert_eq!(digest_thoughts(std::iter::empty()), "No recent thoughts");
+    }
+
+    #[test]
+    fn test_social_energy_introvert_drains_and_recovers() {
+        let mut energy = SocialEnergy::new(0.2);
+        for _ in 0..10 {
+            energy.spend_on_interaction();
+        }
+        assert!(energy.is_depleted());
+        assert!(!energy.is_idle(Duration::from_secs(60)));
+
+        let drained = energy.level;
+        energy.recover(Duration::from_secs(300));
+        assert!(energy.level > drained);
+        assert!(energy.level - drained < 0.5, "recovery is gradual");
+
+        // Extraverts are barely drained by the same conversation
+        let mut extravert = SocialEnergy::new(0.9);
+        for _ in 0..10 {
+            extravert.spend_on_interaction();
+        }
+        assert!(extravert.level > 0.9);
+    }
+
+    #[test]
+    fn test_load_valence_correlation_negative() {
+        let samples: Vec<AffectSample> = (0..20)
+            .map(|i| {
+                let t = i as f64 / 19.0;
+                AffectSample {
+                    state: AffectiveState { valence: 0.6 - t + 0.02 * (i % 3) as f64, ..AffectiveState::new_neutral() },
+                    cognitive_load: 0.1 + 0.8 * t,
+                }
+            })
+            .collect();
+
+        let correlation = load_valence_correlation_of(samples.iter());
+        assert!(correlation < -0.9, "expected strongly negative correlation, got {}", correlation);
+    }
+
+    #[test]
+    fn test_session_markdown_sections() {
+        let mut goals = GoalSystem::new();
+        let eager = eager_state();
+        let goal_id = goals.form_goal("Learn about tide pools".to_string(), GoalCategory::Epistemic, 0.8, &eager).unwrap();
+        goals.update_goal_progress(&goal_id, 1.0, None);
+
+        let diff = ReflectionDiff {
+            before: AffectiveState::new_neutral(),
+            after: AffectiveState { valence: 0.05, ..AffectiveState::new_neutral() },
+            at: Utc::now(),
+        };
+        let markdown = render_session_markdown(&[eager], goals.achievement_history(), &[], Some(&diff));
+
+        assert!(markdown.contains("## Goals"));
+        assert!(markdown.contains("## Reflections"));
+        assert!(markdown.contains("Completed: Learn about tide pools"));
+        assert!(markdown.contains("valence: 0.00 → 0.05"));
+    }
+
+    #[test]
+    fn test_disabled_loops_are_not_spawned() {
+        let config = ContinuousMindConfig {
+            enable_creative_incubation: false,
+            ..ContinuousMindConfig::default()
+        };
+        let loops = config.enabled_loops();
+
+        assert!(!loops.contains(&BackgroundLoop::CreativeIncubation));
+        assert!(loops.contains(&BackgroundLoop::SocialAnalysis));
+        assert_eq!(&loops[..2], &[BackgroundLoop::Main, BackgroundLoop::BackgroundThoughts]);
+        assert_eq!(ContinuousMindConfig::default().enabled_loops().len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_creative_incubation_never_runs() {
+        let config = ContinuousMindConfig {
+            enable_creative_incubation: false,
+            enable_memory_consolidation: false,
+            enable_social_analysis: false,
+            enable_system_monitoring: false,
+            thought_interval: Duration::from_millis(5),
+            task_scheduler_interval: Duration::from_millis(5),
+            creative_incubation_interval: Duration::from_millis(5),
+            ..ContinuousMindConfig::default()
+        };
+        // Energized and novel enough that creative insights are the only thought on offer
+        let inspired = Personality { baseline_state: AffectiveState { valence: 0.9, arousal: 0.9, dominance: 0.2, novelty: 0.9 } };
+        let core = AffectiveCore::with_personality(inspired, AffectiveConfig::default());
+        let mind = Arc::new(ContinuousMind::with_config(core, config, Arc::new(MockLlmClient::default())));
+        *mind.creativity_level.write().await = 0.95;
+        *mind.mental_activity_level.write().await = 0.95;
+        *mind.thought_frequency.write().await = Duration::ZERO;
+        for kind in [ThoughtKind::SelfReflection, ThoughtKind::GoalReassessment, ThoughtKind::AttentionShift] {
+            mind.set_thought_weight(kind, 0.0).await;
+        }
+
+        let processing = tokio::spawn(ContinuousMind::start_continuous_processing(Arc::clone(&mind)));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        mind.shutdown();
+        processing.await.unwrap();
+
+        let thoughts = mind.get_recent_thoughts(100).await;
+        let creative = |activity: &&MentalActivity| matches!(activity.thought, SpontaneousThought::CreativeInsight(_));
+        assert!(thoughts.iter().any(|activity| creative(&activity) && activity.needs_follow_up()),
+                "spontaneous creative insights should still occur: {:?}", thoughts);
+        assert!(!thoughts.iter().filter(creative).any(|activity| activity.triggered_by.as_deref() == Some("system_generated")),
+                "creative incubation produced a thought while disabled");
+
+        let scheduler = mind.task_scheduler.lock().await;
+        assert!(!scheduler.is_pending(&BackgroundTask::CreativeIncubation));
+        assert!(!scheduler.completion_count_by_type().contains_key("CreativeIncubation"));
+    }
+
+    #[test]
+    fn test_loop_intervals_are_configurable() {
+        let defaults = ContinuousMindConfig::default();
+        assert_eq!(defaults.interval_for(BackgroundLoop::Main), Duration::from_millis(500));
+        assert_eq!(defaults.interval_for(BackgroundLoop::MemoryConsolidation), Duration::from_secs(120));
+
+        let low_power = ContinuousMindConfig {
+            thought_interval: Duration::from_secs(30),
+            ..ContinuousMindConfig::default()
+        };
+        assert_eq!(low_power.interval_for(BackgroundLoop::BackgroundThoughts), Duration::from_secs(30));
+        assert_eq!(low_power.interval_for(BackgroundLoop::SystemMonitoring), defaults.system_monitoring_interval);
+    }
+
+    #[test]
+    fn test_observed_stability() {
+        let varying: Vec<AffectiveState> = (0..10)
+            .map(|i| {
+                let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+                AffectiveState { valence: 0.8 * sign, arousal: 0.5 + 0.4 * sign, dominance: -0.5 * sign, novelty: 0.6 * sign }
+            })
+            .collect();
+        let flat = vec![AffectiveState::new_neutral(); 10];
+
+        assert!(observed_stability_of(varying.iter()) < 0.3);
+        assert!(observed_stability_of(flat.iter()) > 0.99);
+    }
+
+    /// Backend whose reflection takes `delay` to answer
+    struct SlowReflector {
+        delay: Duration,
+        personality: Personality,
+    }
+
+    impl LlmBackend for SlowReflector {
+        fn appraise<'a>(&'a self, _user_prompt: &'a str, _memory: &'a Memory) -> BoxFuture<'a, Result<Vec<AppraisedEmotion>, LlmApiError>> {
+            future::ready(Err(LlmApiError::EmptyResponse)).boxed()
+        }
+
+        fn reflect<'a>(&'a self, _memory: &'a Memory) -> BoxFuture<'a, Result<Personality, LlmApiError>> {
+            let personality = self.personality;
+            tokio::time::sleep(self.delay).map(move |_| Ok(personality)).boxed()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_user_turn_interrupts_reflection() {
+        let reflected = Personality { baseline_state: AffectiveState { valence: 0.7, ..AffectiveState::new_neutral() } };
+        let backend = SlowReflector { delay: Duration::from_millis(40), personality: reflected };
+        let mind = Arc::new(ContinuousMind::new(AffectiveCore::default(), Arc::new(backend)));
+        let baseline = |mind: &ContinuousMind| mind.affective_core.try_lock().unwrap().memory.personality.baseline_state.valence;
+        let neutral = baseline(&mind);
+
+        let reflection = tokio::spawn({
+            let mind = Arc::clone(&mind);
+            async move { ContinuousMind::perform_deep_reflection(&mind).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        mind.begin_user_turn();
+        reflection.await.unwrap();
+
+        // The answer arrived mid-turn: dropped, and another reflection queued for later
+        assert_eq!(baseline(&mind), neutral);
+        assert!(mind.task_scheduler.lock().await.is_pending(&BackgroundTask::DeepReflection));
+
+        // A turn that starts and ends during the call still interrupts it
+        mind.end_user_turn();
+        let reflection = tokio::spawn({
+            let mind = Arc::clone(&mind);
+            async move { ContinuousMind::perform_deep_reflection(&mind).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        mind.begin_user_turn();
+        mind.end_user_turn();
+        reflection.await.unwrap();
+        assert_eq!(baseline(&mind), neutral);
+
+        ContinuousMind::perform_deep_reflection(&mind).await;
+        assert_eq!(baseline(&mind), 0.7);
+    }
+
+    #[tokio::test]
+    async fn test_deep_reflection_clamps_the_adopted_baseline() {
+        let extreme = Personality { baseline_state: AffectiveState { valence: 1.4, ..AffectiveState::new_neutral() } };
+        let backend = SlowReflector { delay: Duration::ZERO, personality: extreme };
+        let mind = Arc::new(ContinuousMind::new(AffectiveCore::default(), Arc::new(backend)));
+
+        ContinuousMind::perform_deep_reflection(&mind).await;
+
+        let baseline = mind.affective_core.try_lock().unwrap().memory.personality.baseline_state;
+        assert_eq!(baseline.valence, 1.0);
+        assert_eq!(mind.last_reflection.read().await.unwrap().after.valence, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_reflection_waits_out_a_briefly_held_core() {
+        let reflected = Personality { baseline_state: AffectiveState { valence: 0.6, ..AffectiveState::new_neutral() } };
+        let backend = SlowReflector { delay: Duration::from_millis(30), personality: reflected };
+        let mind = Arc::new(ContinuousMind::new(AffectiveCore::default(), Arc::new(backend)));
+        let hold_core = |hold: Duration| {
+            let core = mind.get_affective_core();
+            let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+            let holder = std::thread::spawn(move || {
+                let _guard = core.lock().unwrap();
+                locked_tx.send(()).unwrap();
+                std::thread::sleep(hold);
+            });
+            locked_rx.recv().unwrap();
+            holder
+        };
+
+        // Held while the answer arrives, but released within the retry budget
+        let reflection = tokio::spawn({
+            let mind = Arc::clone(&mind);
+            async move { ContinuousMind::perform_deep_reflection(&mind).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let holder = hold_core(Duration::from_millis(40));
+        reflection.await.unwrap();
+        holder.join().unwrap();
+        assert_eq!(mind.affective_core.try_lock().unwrap().memory.personality.baseline_state.valence, 0.6);
+        assert!(mind.last_reflection.read().await.is_some());
+
+        // Held for longer: the reflection is queued again instead of vanishing
+        *mind.last_reflection.write().await = None;
+        let reflection = tokio::spawn({
+            let mind = Arc::clone(&mind);
+            async move { ContinuousMind::perform_deep_reflection(&mind).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let holder = hold_core(Duration::from_millis(200));
+        reflection.await.unwrap();
+        holder.join().unwrap();
+        assert!(mind.last_reflection.read().await.is_none());
+        assert!(mind.task_scheduler.lock().await.is_pending(&BackgroundTask::DeepReflection));
+    }
+
+    #[test]
+    fn test_creative_goal_and_drive_top_attention() {
+        let text = vec![
+            (AttentionTarget::ProblemSolving, 0.8),
+            (AttentionTarget::CreativeThinking, 0.3),
+            (AttentionTarget::SelfEmotion, 0.4),
+        ];
+        let priorities = combine_attention_priorities(Some((&GoalCategory::Creative, 0.6)), 0.2, 0.8, text);
+
+        assert_eq!(priorities[0].0, AttentionTarget::CreativeThinking);
+        assert!(priorities[0].1 > 0.8);
+        assert!(priorities.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        // Without the goal and the drive, the text suggestion wins
+        let text_only = combine_attention_priorities(None, 0.2, 0.3, vec![(AttentionTarget::ProblemSolving, 0.8)]);
+        assert_eq!(text_only[0].0, AttentionTarget::ProblemSolving);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stops_background_loops() {
+        let mind = Arc::new(ContinuousMind::new(AffectiveCore::default(), Arc::new(MockLlmClient::default())));
+        let processing = tokio::spawn(ContinuousMind::start_continuous_processing(Arc::clone(&mind)));
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!processing.is_finished());
+
+        mind.shutdown();
+        tokio::time::timeout(Duration::from_secs(2), processing)
+            .await
+            .expect("background loops should stop promptly after shutdown")
+            .unwrap();
+        assert!(mind.is_shut_down());
+    }
+
+    #[tokio::test]
+    async fn test_goal_formation_and_memory_retrieval_are_recorded() {
+        let eager = Personality {
+            baseline_state: eager_state(),
+        };
+        let mind = ContinuousMind::builder()
+            .with_personality(eager)
+            .with_llm_backend(Arc::new(MockLlmClient::default()))
+            .build()
+            .unwrap();
+
+        let formed = mind.form_goals_from_prompt("I want to learn about tide pools").await;
+        assert!(!formed.is_empty());
+        assert_eq!(mind.metacognition.lock().unwrap().pattern_occurrences("goal_formation"), formed.len());
+
+        mind.compute_attention_priorities("tide pools again");
+        assert_eq!(mind.metacognition.lock().unwrap().pattern_occurrences("memory_retrieval"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_curiosity_forms_one_epistemic_goal_about_a_recurring_topic() {
+        let intrigued = Personality {
+            baseline_state: AffectiveState { valence: 0.3, arousal: 0.6, dominance: 0.2, novelty: 0.7 },
+        };
+        let mind = Arc::new(ContinuousMind::builder()
+            .with_personality(intrigued)
+            .with_llm_backend(Arc::new(MockLlmClient::default()))
+            .build()
+            .unwrap());
+        {
+            let mut core = mind.affective_core.lock().unwrap();
+            core.memory.learn_from_prompt("Tell me about tidepools");
+            core.memory.learn_from_prompt("More tidepools please");
+        }
+
+        // Default creativity and introspection keep the drive below the threshold
+        assert!(ContinuousMind::explore_curiosity(&mind).await.is_none());
+
+        *mind.creativity_level.write().await = 0.8;
+        *mind.introspection_tendency.write().await = 0.6;
+        let goal_id = ContinuousMind::explore_curiosity(&mind).await.expect("curiosity should form a goal");
+        {
+            let goals = mind.goal_system.lock().unwrap();
+            let goal = goals.get_active_goals().into_iter().find(|goal| goal.id == goal_id).unwrap();
+            assert_eq!(goal.category, GoalCategory::Epistemic);
+            assert!(goal.description.contains("tidepools"));
+        }
+        assert_eq!(mind.metacognition.lock().unwrap().pattern_occurrences("goal_formation"), 1);
+
+        // With an Epistemic goal active, curiosity waits
+        assert!(ContinuousMind::explore_curiosity(&mind).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_injected_emotion_updates_state_and_thoughts() {
+        let mind = ContinuousMind::new(AffectiveCore::default(), Arc::new(MockLlmClient::default()));
+        let before = mind.affective_core.lock().unwrap().current_state();
+        let mut thoughts = mind.subscribe_thoughts();
+
+        let triumph = AppraisedEmotion {
+            emotion: "Triumph".to_string(),
+            vadn: AffectiveStateChange { valence: 0.9, arousal: 0.8, dominance: 0.7, novelty: 0.4 },
+            details: serde_json::json!({ "focus": "winning the match" }),
+        };
+        let after = mind.inject_emotion(&triumph).await.unwrap();
+
+        assert!(after.valence > before.valence);
+        assert_eq!(mind.metacognition.lock().unwrap().recent_processes(10).len(), 1);
+        let thought = thoughts.try_recv().unwrap();
+        assert_eq!(thought.triggered_by.as_deref(), Some("external_event"));
+        assert!(matches!(thought.thought, SpontaneousThought::EmotionalProcessing(ref text) if text.contains("winning the match")));
+
+        // A negligible nudge is processed but not worth a thought
+        let flicker = AppraisedEmotion {
+            emotion: "Flicker".to_string(),
+            vadn: AffectiveStateChange { valence: 0.01, arousal: 0.0, dominance: 0.0, novelty: 0.0 },
+            details: serde_json::Value::Null,
+        };
+        mind.inject_emotion(&flicker).await;
+        assert!(thoughts.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_schedule_task_skips_pending_duplicates() {
+        let mut scheduler = TaskScheduler::new();
+        assert!(scheduler.schedule_task(BackgroundTask::EmotionalRegulation));
+        assert!(!scheduler.schedule_task(BackgroundTask::EmotionalRegulation));
+        assert!(scheduler.schedule_task(BackgroundTask::ErrorRecovery("timeout".to_string())));
+        assert!(!scheduler.schedule_task(BackgroundTask::ErrorRecovery("timeout".to_string())));
+        assert!(scheduler.schedule_task(BackgroundTask::ErrorRecovery("rate limit".to_string())));
+        assert_eq!(scheduler.pending_tasks.len(), 3);
+
+        // Once it has been taken off the queue, the same kind can be scheduled again
+        while let Some(task) = scheduler.get_next_task() {
+            scheduler.complete_task(&task);
+        }
+        assert!(scheduler.schedule_task(BackgroundTask::EmotionalRegulation));
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_tasks_respect_capacity() {
+        let mut scheduler = TaskScheduler::with_capacity(2);
+        for task in [BackgroundTask::GoalReassessment, BackgroundTask::AttentionUpdate, BackgroundTask::MemoryConsolidation] {
+            scheduler.schedule_task(task);
+        }
+        assert!(scheduler.get_next_task().is_some());
+        assert!(scheduler.get_next_task().is_some());
+        assert!(scheduler.get_next_task().is_none());
+        assert_eq!(TaskScheduler::with_capacity(0).max_concurrent, 1);
+
+        // Health checks schedule follow-up work, so running one must not hold the scheduler lock
+        let mind = Arc::new(ContinuousMind::builder()
+            .with_max_concurrent_tasks(1)
+            .with_llm_backend(Arc::new(MockLlmClient::default()))
+            .build()
+            .unwrap());
+        *mind.error_count.lock().await = 6;
+        {
+            let mut scheduler = mind.task_scheduler.lock().await;
+            scheduler.schedule_task(BackgroundTask::SystemHealthCheck);
+            scheduler.schedule_task(BackgroundTask::SocialContextAnalysis);
+        }
+
+        ContinuousMind::process_scheduled_tasks(&mind).await;
+        assert!(mind.task_scheduler.lock().await.is_pending(&BackgroundTask::SocialContextAnalysis));
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while !mind.task_scheduler.lock().await.is_pending(&BackgroundTask::ErrorRecovery(String::new())) {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("health check should finish and schedule error recovery");
+    }
+
+    #[test]
+    fn test_completed_task_analytics() {
+        let mut scheduler = TaskScheduler::new();
+        let start = Instant::now();
+        let run = |scheduler: &mut TaskScheduler, task: BackgroundTask, took: u64| {
+            scheduler.pending_tasks.push((task, start));
+            let task = scheduler.next_task_at(start).unwrap();
+            scheduler.complete_task_at(&task, start + Duration::from_secs(took));
+        };
+        run(&mut scheduler, BackgroundTask::DeepReflection, 40);
+        run(&mut scheduler, BackgroundTask::DeepReflection, 80);
+        run(&mut scheduler, BackgroundTask::AttentionUpdate, 1);
+
+        let counts = scheduler.completion_count_by_type();
+        assert_eq!(counts["DeepReflection"], 2);
+        assert_eq!(counts["AttentionUpdate"], 1);
+        let latencies = scheduler.average_latency_by_type();
+        assert_eq!(latencies["DeepReflection"], Duration::from_secs(60));
+        assert_eq!(latencies["AttentionUpdate"], Duration::from_secs(1));
+        assert!(scheduler.get_status().contains("DeepReflection x2 avg 60.0s"));
+        assert!(TaskScheduler::new().average_latency_by_type().is_empty());
+    }
+
+    #[test]
+    fn test_waiting_tasks_age_past_newer_high_priority_ones() {
+        let mut scheduler = TaskScheduler::new();
+        let start = Instant::now();
+        scheduler.pending_tasks.push((BackgroundTask::SocialContextAnalysis, start));
+
+        // Fresh high-priority work keeps arriving; each round one task runs and finishes
+        let mut rounds = 0;
+        loop {
+            let now = start + Duration::from_secs(10 * rounds);
+            scheduler.pending_tasks.push((BackgroundTask::DeepReflection, now));
+            let task = scheduler.next_task_at(now).unwrap();
+            scheduler.complete_task(&task);
+            if matches!(task, BackgroundTask::SocialContextAnalysis) {
+                break;
+            }
+            rounds += 1;
+            assert!(rounds < 20, "low-priority task starved");
+        }
+        // 0.2 + 0.01/s needs about 60s to catch up with a fresh 0.8
+        assert!(rounds >= 6);
+        assert_eq!(scheduler.next_task_at(start).map(|t| t.priority()), Some(0.8));
+    }
+
+    #[tokio::test]
+    async fn test_paused_loops_skip_their_work() {
+        let config = ContinuousMindConfig {
+            enable_memory_consolidation: false,
+            enable_creative_incubation: false,
+            enable_social_analysis: false,
+            enable_system_monitoring: false,
+            task_scheduler_interval: Duration::from_millis(5),
+            ..ContinuousMindConfig::default()
+        };
+        let mind = Arc::new(ContinuousMind::with_config(AffectiveCore::default(), config, Arc::new(MockLlmClient::default())));
+        mind.task_scheduler.lock().await.schedule_task(BackgroundTask::SocialContextAnalysis);
+
+        mind.pause();
+        assert!(mind.is_paused());
+        let processing = tokio::spawn(ContinuousMind::start_continuous_processing(Arc::clone(&mind)));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(mind.task_scheduler.lock().await.is_pending(&BackgroundTask::SocialContextAnalysis));
+
+        mind.resume();
+        assert!(!mind.is_paused());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!mind.task_scheduler.lock().await.is_pending(&BackgroundTask::SocialContextAnalysis));
+
+        mind.shutdown();
+        processing.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_running_scheduled_tasks() {
+        let config = ContinuousMindConfig {
+            enable_memory_consolidation: false,
+            enable_creative_incubation: false,
+            enable_social_analysis: false,
+            enable_system_monitoring: false,
+            task_scheduler_interval: Duration::from_millis(5),
+            ..ContinuousMindConfig::default()
+        };
+        let backend = SlowReflector { delay: Duration::from_millis(80), personality: Personality { baseline_state: AffectiveState::new_neutral() } };
+        let mind = Arc::new(ContinuousMind::with_config(AffectiveCore::default(), config, Arc::new(backend)));
+        mind.task_scheduler.lock().await.schedule_task(BackgroundTask::DeepReflection);
+
+        let processing = tokio::spawn(ContinuousMind::start_continuous_processing(Arc::clone(&mind)));
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        mind.shutdown();
+        processing.await.unwrap();
+
+        // The reflection was mid-call at shutdown; processing only returned once it finished
+        assert!(mind.task_handles.lock().await.is_empty());
+        assert_eq!(mind.task_scheduler.lock().await.completion_count_by_type()["DeepReflection"], 1);
+    }
+
+    #[test]
+    fn test_completion_matches_the_finished_task() {
+        let mut scheduler = TaskScheduler::new();
+        let start = Instant::now();
+        let disk = BackgroundTask::ErrorRecovery("disk full".to_string());
+        let network = BackgroundTask::ErrorRecovery("network down".to_string());
+        scheduler.pending_tasks.push((disk.clone(), start));
+        scheduler.pending_tasks.push((network.clone(), start));
+        scheduler.next_task_at(start).unwrap();
+        scheduler.next_task_at(start).unwrap();
+
+        scheduler.complete_task_at(&network, start + Duration::from_secs(1));
+        assert_eq!(scheduler.running_tasks.len(), 1);
+        assert!(scheduler.running_tasks[0].0.is_duplicate_of(&disk));
+    }
+
+    #[tokio::test]
+    async fn test_social_energy_recovers_without_social_analysis() {
+        let config = ContinuousMindConfig {
+            enable_social_analysis: false,
+            main_loop_interval: Duration::from_millis(5),
+            ..ContinuousMindConfig::default()
+        };
+        let mind = Arc::new(ContinuousMind::with_config(AffectiveCore::default(), config, Arc::new(MockLlmClient::default())));
+        mind.social_energy.write().await.level = 0.3;
+
+        let processing = tokio::spawn(ContinuousMind::start_continuous_processing(Arc::clone(&mind)));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        mind.shutdown();
+        processing.await.unwrap();
+
+        assert!(mind.get_social_energy().await > 0.3);
+    }
+
+    #[tokio::test]
+    async fn test_contended_core_is_not_waited_on_forever() {
+        let mind = Arc::new(ContinuousMind::new(AffectiveCore::default(), Arc::new(MockLlmClient::default())));
+        let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+        let holder = std::thread::spawn({
+            let core = mind.get_affective_core();
+            move || {
+                let _guard = core.lock().unwrap();
+                locked_tx.send(()).unwrap();
+                std::thread::sleep(Duration::from_millis(200));
+            }
+        });
+        locked_rx.recv().unwrap();
+
+        assert!(mind.snapshot().await.is_none());
+        assert!(mind.form_goals_from_prompt("I want to learn about tide pools").await.is_empty());
+        assert!(mind.set_decay_rate(0.2).await.is_none());
+        holder.join().unwrap();
+        assert!(mind.snapshot().await.is_some());
+        assert_eq!(mind.set_decay_rate(0.2).await, Some(0.2));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_roundtrip_restores_subsystems() {
+        let mind = Arc::new(ContinuousMind::new(AffectiveCore::default(), Arc::new(MockLlmClient::default())));
+        let eager = eager_state();
+        let goal_id = mind.goal_system.lock().unwrap()
+            .form_goal("Learn about tide pools".to_string(), GoalCategory::Epistemic, 0.8, &eager)
+            .unwrap();
+        mind.attention_system.lock().unwrap().focus_on(AttentionTarget::Learning, 0.8, 0.8);
+        mind.metacognition.lock().unwrap().record_process(CognitiveProcess::SelfReflection {
+            insight: "Tide pools are fascinating".to_string(),
+            confidence: 0.7,
+        });
+        mind.set_baseline_state(eager).await;
+        ContinuousMind::add_spontaneous_thought(&mind, SpontaneousThought::CuriosityDriven("anemones".to_string()), 0.6).await;
+
+        let json = serde_json::to_string(&mind.snapshot().await.unwrap()).unwrap();
+        let restored = ContinuousMind::from_snapshot(serde_json::from_str(&json).unwrap(), Arc::new(MockLlmClient::default()));
+
+        let snapshot = restored.snapshot().await.unwrap();
+        assert!(snapshot.goal_system.get_active_goals().iter().any(|g| g.id == goal_id));
+        assert_eq!(snapshot.attention.get_primary_focus().unwrap().target, AttentionTarget::Learning);
+        assert_eq!(snapshot.metacognition.recent_processes(10).len(), 1);
+        assert_eq!(snapshot.affective_core.memory.personality.baseline_state.valence, eager.valence);
+        assert_eq!(snapshot.spontaneous_thoughts.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_restoring_a_corrupt_snapshot_reports_the_focus_violation() {
+        let mind = ContinuousMind::new(AffectiveCore::default(), Arc::new(MockLlmClient::default()));
+        let goal_id = {
+            let mut goals = mind.goal_system.lock().unwrap();
+            let goal_id = goals
+                .form_goal("Finish the essay".to_string(), GoalCategory::Creative, 0.7, &eager_state())
+                .unwrap();
+            assert_eq!(goals.determine_focus(), Some(goal_id.clone()));
+            goal_id
+        };
+        assert!(mind.validate_invariants().is_empty());
+
+        // Mark the focused goal completed behind the goal system's back, as a bad import would
+        let mut snapshot = serde_json::to_value(mind.snapshot().await.unwrap()).unwrap();
+        snapshot["goal_system"]["goals"][&goal_id]["status"] = serde_json::json!("Completed");
+        let config = ContinuousMindConfig { validate_after_import: true, ..ContinuousMindConfig::default() };
+        let restored = ContinuousMind::from_snapshot_with_config(
+            serde_json::from_value(snapshot).unwrap(),
+            config,
+            Arc::new(MockLlmClient::default()),
+        );
+
+        assert!(restored.config.validate_after_import);
+        let violations = restored.validate_invariants();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].subsystem, "goals");
+        assert!(violations[0].description.contains(&goal_id));
+        assert!(violations[0].description.contains("Completed"));
+    }
+
+    #[test]
+    fn test_config_loads_saved_memory() {
+        let memory_path = std::env::temp_dir().join(format!("cogno_mind_memory_{}.json", std::process::id()));
+        let log_path = std::env::temp_dir().join(format!("cogno_mind_milestones_{}.jsonl", std::process::id()));
+        let mut memory = Memory::new();
+        memory.interaction_count = 42;
+        memory.save(&memory_path).unwrap();
+
+        let config = ContinuousMindConfig {
+            memory_path: Some(memory_path.clone()),
+            milestone_log_path: Some(log_path.clone()),
+            ..ContinuousMindConfig::default()
+        };
+        let mind = ContinuousMind::with_config(AffectiveCore::default(), config, Arc::new(MockLlmClient::default()));
+        std::fs::remove_file(&memory_path).unwrap();
+
+        let mut core = mind.affective_core.lock().unwrap();
+        assert_eq!(core.memory.interaction_count, 42);
+        core.memory.record_milestone("Resumed".to_string());
+        assert!(std::fs::read_to_string(&log_path).unwrap().contains("Resumed"));
+        std::fs::remove_file(&log_path).unwrap();
+    }
+
+    #[test]
+    fn test_builder_requires_one_llm_source() {
+        assert!(matches!(ContinuousMind::builder().build(), Err(MindBuildError::MissingLlmBackend)));
+
+        let both = ContinuousMind::builder()
+            .with_llm_backend(Arc::new(MockLlmClient::default()))
+            .with_llm_config(LlmApiConfig::default())
+            .build();
+        assert!(matches!(both, Err(MindBuildError::ConflictingLlmBackend)));
+    }
+
+    #[tokio::test]
+    async fn test_offline_mode_never_builds_a_live_client() {
+        // An Anthropic config with no key would fail to build a live client
+        let llm_config = LlmApiConfig { provider: Some(LlmProvider::Anthropic), ..LlmApiConfig::default() };
+        let mind = Arc::new(ContinuousMind::builder()
+            .with_config(ContinuousMindConfig { offline: true, ..ContinuousMindConfig::default() })
+            .with_llm_config(llm_config)
+            .build()
+            .unwrap());
+        assert!(mind.is_offline());
+
+        let memory = mind.affective_core.lock().unwrap().memory.clone();
+        let facets = mind.get_llm_backend().appraise("What a wonderful day!", &memory).await.unwrap();
+        assert_eq!(facets[0].details["source"], "local_lexicon");
+
+        ContinuousMind::perform_deep_reflection(&mind).await;
+        assert!(mind.last_reflection.read().await.is_none());
+    }
+
+    #[test]
+    fn test_builder_applies_personality_and_intervals() {
+        let cheerful = Personality {
+            baseline_state: AffectiveState { valence: 0.4, arousal: 0.3, dominance: 0.2, novelty: 0.1 },
+        };
+        let mind = ContinuousMind::builder()
+            .with_personality(cheerful)
+            .with_intervals([(BackgroundLoop::BackgroundThoughts, Duration::from_secs(30))])
+            .with_llm_backend(Arc::new(MockLlmClient::default()))
+            .build()
+            .unwrap();
+
+        assert_eq!(mind.config.interval_for(BackgroundLoop::BackgroundThoughts), Duration::from_secs(30));
+        assert_eq!(mind.config.interval_for(BackgroundLoop::Main), Duration::from_millis(500));
+        let core = mind.affective_core.lock().unwrap();
+        assert_eq!(core.current_state().valence, 0.4);
+        assert_eq!(core.memory.personality.baseline_state.valence, 0.4);
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_thoughts() {
+        let mind = Arc::new(ContinuousMind::new(AffectiveCore::default(), Arc::new(MockLlmClient::default())));
+        let mut receiver = mind.subscribe_thoughts();
+
+        ContinuousMind::add_spontaneous_thought(&mind, SpontaneousThought::CreativeInsight("tide pools".to_string()), 0.7).await;
+
+        let activity = tokio::time::timeout(Duration::from_secs(1), receiver.recv()).await.unwrap().unwrap();
+        assert!(matches!(activity.thought, SpontaneousThought::CreativeInsight(ref idea) if idea == "tide pools"));
+        assert_eq!(activity.intensity, 0.7);
+    }
+
+    #[test]
+    fn test_recurring_topics_bias_attention() {
+        let topics = vec![("astronomy".to_string(), 4), ("gardening".to_string(), 2), ("weather".to_string(), 1)];
+
+        let bids = recurring_topic_bids(&topics, "Tell me more about astronomy and the weather", &Tokenizer::default());
+        assert_eq!(bids.len(), 1, "one-off topics and unmentioned ones don't bid: {:?}", bids);
+        assert_eq!(bids[0].0, AttentionTarget::ConversationTopic("astronomy".to_string()));
+        assert!((bids[0].1 - 0.7).abs() < 1e-9);
+        assert!(recurring_topic_bids(&topics, "Hello again", &Tokenizer::default()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_configured_tokenizer_reaches_text_matching() {
+        let mind = ContinuousMind::builder()
+            .with_llm_backend(Arc::new(MockLlmClient::default()))
+            .with_tokenizer(Tokenizer::with_stopwords(["learn", "astronomy"]))
+            .build()
+            .unwrap();
+        let prompt = "I want to learn astronomy";
+
+        assert!(mind.form_goals_from_prompt(prompt).await.is_empty(), "\"learn\" is a stopword here");
+        assert!(mind.compute_attention_priorities(prompt).iter().all(|(target, _)| *target != AttentionTarget::Learning));
+        {
+            let core = mind.get_affective_core();
+            let mut core = core.try_lock().unwrap();
+            core.memory.learn_from_prompt(prompt);
+            assert!(!core.memory.topic_frequencies.contains_key("astronomy"));
+        }
+
+        let english = ContinuousMind::new(AffectiveCore::default(), Arc::new(MockLlmClient::default()));
+        assert!(!GoalTriggerMap::default().matches(prompt, english.tokenizer()).is_empty());
+        assert!(english.compute_attention_priorities(prompt).iter().any(|(target, _)| *target == AttentionTarget::Learning));
+    }
+
+    #[test]
+    fn test_intentions_include_focused_goal() {
+        let mut goals = GoalSystem::new();
+        let eager = eager_state();
+        let goal_id = goals.form_goal("Learn about tide pools".to_string(), GoalCategory::Epistemic, 0.8, &eager)
+            .expect("motivation should be high enough to form the goal");
+        assert_eq!(goals.determine_focus(), Some(goal_id));
+
+        let intentions = collect_intentions(Some(&goals), None, 0.9, 0.1);
+        let from_goal = intentions.iter()
+            .find(|i| i.source == IntentionSource::Goals && i.description.contains("tide pools"))
+            .expect("focused goal should produce an intention");
+        assert!(from_goal.urgency > 0.0);
+        assert!(intentions.iter().any(|i| i.source == IntentionSource::Drives));
+        assert!(intentions.windows(2).all(|w| w[0].urgency >= w[1].urgency));
+    }
+}
@@ -3,8 +3,12 @@
 //! Manages the underlying emotional state and self-reflection.
 
 use crate::cognitive_appraisal::{AppraisedEmotion, AffectiveStateChange};
-use crate::llm_api;
-use crate::memory::Memory;
+use crate::invariants::{check_range, InvariantViolation};
+use crate::llm_api::{self, LlmApiError, LlmBackend};
+use crate::memory::{Memory, Personality};
+use chrono::{DateTime, Utc};
+use std::io;
+use std::path::Path;
 
 #[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
 pub struct AffectiveState {
@@ -40,28 +44,296 @@ impl AffectiveState {
         self.dominance += (baseline.dominance - self.dominance) * rate;
         self.novelty += (baseline.novelty - self.novelty) * rate;
     }
+
+    /// A copy with every dimension clamped to its valid range
+    pub fn clamped(&self) -> Self {
+        AffectiveState {
+            valence: self.valence.clamp(-1.0, 1.0),
+            arousal: self.arousal.clamp(0.0, 1.0),
+            dominance: self.dominance.clamp(-1.0, 1.0),
+            novelty: self.novelty.clamp(-1.0, 1.0),
+        }
+    }
+
+    /// Overall emotional intensity: mean magnitude of valence, arousal and dominance (0.0 to 1.0)
+    pub fn intensity(&self) -> f64 {
+        ((self.valence.abs() + self.arousal + self.dominance.abs()) / 3.0).clamp(0.0, 1.0)
+    }
+
+    /// Euclidean distance to `other` across all four VADN dimensions
+    pub fn distance(&self, other: &AffectiveState) -> f64 {
+        ((self.valence - other.valence).powi(2)
+            + (self.arousal - other.arousal).powi(2)
+            + (self.dominance - other.dominance).powi(2)
+            + (self.novelty - other.novelty).powi(2))
+            .sqrt()
+    }
+
+    /// Report any VADN dimension that is NaN or outside its valid range
+    pub fn check_invariants(&self, subsystem: &'static str, label: &str) -> Vec<InvariantViolation> {
+        let mut violations = Vec::new();
+        check_range(&mut violations, subsystem, &format!("{}.valence", label), self.valence, -1.0, 1.0);
+        check_range(&mut violations, subsystem, &format!("{}.arousal", label), self.arousal, 0.0, 1.0);
+        check_range(&mut violations, subsystem, &format!("{}.dominance", label), self.dominance, -1.0, 1.0);
+        check_range(&mut violations, subsystem, &format!("{}.novelty", label), self.novelty, -1.0, 1.0);
+        violations
+    }
+}
+
+
+/// Phase of a conversation's emotional arc
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ArcPhase {
+    Rising,
+    Climax,
+    Falling,
+    Resolution,
+}
+
+/// Emotional arc of a conversation, derived from per-turn intensity
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct EmotionalArc {
+    /// Phase of the most recent turn
+    pub phase: ArcPhase,
+    /// Turn with the highest intensity, once intensity has started to fall after it
+    pub peak_turn: Option<usize>,
+    pub peak_intensity: f64,
+    /// `(turn, phase)` for the first turn and every turn where the phase changed
+    pub transitions: Vec<(usize, ArcPhase)>,
+}
+
+impl EmotionalArc {
+    /// Changes in intensity smaller than this count as settled
+    const SETTLE_THRESHOLD: f64 = 0.05;
+
+    pub fn from_states(states: &[AffectiveState]) -> Self {
+        let intensities: Vec<f64> = states.iter().map(|s| s.intensity()).collect();
+        Self::from_intensities(&intensities)
+    }
+
+    pub fn from_intensities(intensities: &[f64]) -> Self {
+        let peak = intensities.iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal));
+        let Some((peak_index, &peak_intensity)) = peak else {
+            return EmotionalArc {
+                phase: ArcPhase::Resolution,
+                peak_turn: None,
+                peak_intensity: 0.0,
+                transitions: Vec::new(),
+            };
+        };
+
+        // A peak on the latest turn isn't a climax yet: intensity may still be building
+        let last = intensities.len() - 1;
+        let peak_turn = (peak_index < last).then_some(peak_index);
+
+        let mut transitions: Vec<(usize, ArcPhase)> = Vec::new();
+        for (turn, &intensity) in intensities.iter().enumerate() {
+            let phase = match peak_turn {
+                Some(peak) if turn == peak => ArcPhase::Climax,
+                Some(peak) if turn > peak => {
+                    let settled = (intensity - intensities[turn - 1]).abs() < Self::SETTLE_THRESHOLD;
+                    let already_resolved = transitions.last().is_some_and(|(_, p)| *p == ArcPhase::Resolution);
+                    if settled || already_resolved { ArcPhase::Resolution } else { ArcPhase::Falling }
+                }
+                _ => ArcPhase::Rising,
+            };
+            if transitions.last().is_none_or(|(_, p)| *p != phase) {
+                transitions.push((turn, phase));
+            }
+        }
+
+        EmotionalArc {
+            phase: transitions.last().map_or(ArcPhase::Rising, |(_, p)| *p),
+            peak_turn,
+            peak_intensity,
+            transitions,
+        }
+    }
+}
+
+
+/// Tuning for how the affective core absorbs and regulates emotions
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AffectiveConfig {
+    /// Fraction of the distance to baseline recovered per regulation step (0.0 to 1.0)
+    pub decay_rate: f64,
+    /// How strongly appraised emotions are absorbed (0.0 to 1.0)
+    pub empathy_factor: f64,
+    /// Soft-normalize incoming VADN changes against recent inputs so strong streaks don't saturate
+    pub normalize_inputs: bool,
+    /// Momentum of valence and dominance (0.0 to 1.0): the share of the previous value kept when
+    /// an emotion lands. Defaults to 0.3, so one turn moves mood 70% of the way; arousal and
+    /// novelty always react immediately.
+    pub mood_inertia: f64,
+}
+
+impl Default for AffectiveConfig {
+    fn default() -> Self {
+        AffectiveConfig {
+            decay_rate: 0.15,
+            empathy_factor: 0.8,
+            normalize_inputs: false,
+            mood_inertia: 0.3,
+        }
+    }
+}
+
+/// Running soft-normalization of incoming VADN changes. Each dimension is scaled down when
+/// recent inputs have been unusually strong, then compressed by the remaining headroom so the
+/// state approaches its bounds asymptotically instead of pinning to the clamp.
+#[derive(Debug, Clone)]
+struct InputNormalizer {
+    /// Exponential moving average of |change| for valence, arousal, dominance, novelty
+    recent_magnitude: [f64; 4],
+}
+
+impl InputNormalizer {
+    /// Typical magnitude of a single appraisal's change
+    const REFERENCE_MAGNITUDE: f64 = 0.3;
+    const SMOOTHING: f64 = 0.3;
+
+    fn new() -> Self {
+        InputNormalizer { recent_magnitude: [Self::REFERENCE_MAGNITUDE; 4] }
+    }
+
+    fn normalize(&mut self, change: AffectiveStateChange, state: &AffectiveState) -> AffectiveStateChange {
+        let dimensions = [
+            (change.valence, state.valence, -1.0, 1.0),
+            (change.arousal, state.arousal, 0.0, 1.0),
+            (change.dominance, state.dominance, -1.0, 1.0),
+            (change.novelty, state.novelty, -1.0, 1.0),
+        ];
+
+        let mut normalized = [0.0; 4];
+        for (i, (delta, current, min, max)) in dimensions.into_iter().enumerate() {
+            let recent = &mut self.recent_magnitude[i];
+            // Scale is computed before this input is folded in, so a strong input after calm still stands out
+            let scaled = delta * (Self::REFERENCE_MAGNITUDE / *recent).min(1.0);
+            let headroom = if scaled > 0.0 { max - current } else { current - min };
+            let headroom: f64 = headroom.max(0.0);
+            normalized[i] = if scaled == 0.0 { 0.0 } else { scaled * headroom / (headroom + scaled.abs()) };
+            *recent = *recent * (1.0 - Self::SMOOTHING) + delta.abs() * Self::SMOOTHING;
+        }
+
+        AffectiveStateChange {
+            valence: normalized[0],
+            arousal: normalized[1],
+            dominance: normalized[2],
+            novelty: normalized[3],
+        }
+    }
+}
+
+/// How memorable an emotional change is: its strongest valence or arousal component
+fn milestone_intensity(change: &AffectiveStateChange) -> f64 {
+    change.valence.abs().max(change.arousal).clamp(0.0, 1.0)
+}
+
+/// Milestone text for an appraised emotion, keeping its focus and reason for later reflection
+fn milestone_description(emotion: &AppraisedEmotion) -> String {
+    format!("Felt {}, VADN: {:?}", emotion.describe(), emotion.vadn)
 }
 
+/// Relative weight of one facet of a mixed appraisal, from its `details.weight`; 1.0 when absent
+pub fn facet_weight(emotion: &AppraisedEmotion) -> f64 {
+    emotion.details.get("weight")
+        .and_then(serde_json::Value::as_f64)
+        .filter(|w| w.is_finite() && *w >= 0.0)
+        .unwrap_or(1.0)
+}
 
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct AffectiveCore {
     current_state: AffectiveState,
     pub memory: Memory,
-    decay_rate: f64,
-    empathy_factor: f64,
+    #[serde(default)]
+    config: AffectiveConfig,
+    /// Running input statistics are rebuilt after a reload rather than persisted
+    #[serde(skip, default = "InputNormalizer::new")]
+    normalizer: InputNormalizer,
+    /// When the state last changed, so time away can be decayed on the next update
+    #[serde(default = "Utc::now")]
+    last_update: DateTime<Utc>,
 }
 
 impl AffectiveCore {
     /// Creates a new AffectiveCore, initializing state from its memory's personality.
     pub fn new() -> Self {
+        Self::with_config(AffectiveConfig::default())
+    }
+
+    /// Creates a new AffectiveCore with explicit tuning.
+    pub fn with_config(config: AffectiveConfig) -> Self {
         let memory = Memory::new();
         AffectiveCore {
             current_state: memory.personality.baseline_state,
             memory,
-            decay_rate: 0.15,
-            empathy_factor: 0.8,
+            config,
+            normalizer: InputNormalizer::new(),
+            last_update: Utc::now(),
         }
     }
 
+    /// Creates a new AffectiveCore that starts at, and decays toward, `personality`'s baseline,
+    /// clamped to the valid VADN ranges.
+    pub fn with_personality(personality: Personality, config: AffectiveConfig) -> Self {
+        let mut core = Self::with_config(config);
+        core.set_baseline_state(personality.baseline_state);
+        core.current_state = core.memory.personality.baseline_state;
+        core
+    }
+
+    /// Saves the current state, tuning and memory as JSON so a later run can resume them.
+    pub fn save_to_path(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+    }
+
+    /// Restores a core previously written by `save_to_path`.
+    pub fn load_from_path(path: &Path) -> io::Result<AffectiveCore> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Current tuning
+    pub fn config(&self) -> &AffectiveConfig {
+        &self.config
+    }
+
+    /// Replace all tuning at once, clamping each value like the individual setters
+    pub fn set_config(&mut self, config: AffectiveConfig) {
+        self.config = AffectiveConfig {
+            mood_inertia: config.mood_inertia.clamp(0.0, 1.0),
+            ..config
+        };
+        self.set_empathy_factor(config.empathy_factor);
+        self.set_decay_rate(config.decay_rate);
+    }
+
+    /// Change how strongly emotions are absorbed, clamped to [0, 1]
+    pub fn set_empathy_factor(&mut self, empathy_factor: f64) {
+        self.config.empathy_factor = empathy_factor.clamp(0.0, 1.0);
+    }
+
+    /// Change how quickly the state decays to baseline, clamped to [0, 1]
+    pub fn set_decay_rate(&mut self, decay_rate: f64) {
+        self.config.decay_rate = decay_rate.clamp(0.0, 1.0);
+    }
+
+    /// Change the personality baseline the state decays toward, clamped to valid VADN ranges
+    pub fn set_baseline_state(&mut self, baseline: AffectiveState) {
+        self.memory.personality.baseline_state = baseline.clamped();
+    }
+
+    /// Adopt a personality produced by reflection; its baseline is clamped like `set_baseline_state`
+    pub fn adopt_personality(&mut self, personality: Personality) {
+        self.memory.personality = personality;
+        self.set_baseline_state(personality.baseline_state);
+    }
+
     // --- ADD THIS METHOD BACK ---
     /// Returns a copy of the current affective state.
     pub fn current_state(&self) -> AffectiveState {
@@ -71,47 +343,145 @@ impl AffectiveCore {
     /// Processes an appraised emotion, updating the internal state.
     pub fn process_emotion(&mut self, emotion: &AppraisedEmotion) {
         let change = emotion.vadn;
-        let blended_change = AffectiveStateChange {
-            valence: change.valence * self.empathy_factor,
-            arousal: change.arousal * self.empathy_factor,
-            dominance: change.dominance * self.empathy_factor,
-            novelty: change.novelty * self.empathy_factor,
+        let empathy = self.config.empathy_factor;
+        let mut blended_change = AffectiveStateChange {
+            valence: change.valence * empathy,
+            arousal: change.arousal * empathy,
+            dominance: change.dominance * empathy,
+            novelty: change.novelty * empathy,
         };
-        self.current_state.apply_change(blended_change);
-
-        let full_emotion_details = format!(
-            "Emotion: '{}', VADN: {:?}, Details: {}",
-            emotion.emotion,
-            emotion.vadn,
-            emotion.details.to_string()
-        );
-        
+        if self.config.normalize_inputs {
+            blended_change = self.normalizer.normalize(blended_change, &self.current_state);
+        }
+        let mut target = self.current_state;
+        target.apply_change(blended_change);
+        let inertia = self.config.mood_inertia.clamp(0.0, 1.0);
+        self.current_state = AffectiveState {
+            valence: self.current_state.valence * inertia + target.valence * (1.0 - inertia),
+            dominance: self.current_state.dominance * inertia + target.dominance * (1.0 - inertia),
+            ..target
+        };
+        self.last_update = Utc::now();
+
         if emotion.vadn.valence.abs() > 0.6 || emotion.vadn.arousal > 0.7 {
-            self.memory.record_milestone(full_emotion_details);
+            self.memory.record_milestone_with_intensity(milestone_description(emotion), milestone_intensity(&emotion.vadn));
         }
     }
 
+    /// Processes several emotional facets of a single turn at once, weighting each by the
+    /// `details.weight` the appraisal gave it (equal weights when absent).
+    pub fn process_emotions(&mut self, emotions: &[AppraisedEmotion]) {
+        let blend: Vec<(AppraisedEmotion, f64)> = emotions.iter()
+            .map(|emotion| (emotion.clone(), facet_weight(emotion)))
+            .collect();
+        self.process_emotion_blend(&blend);
+    }
+
+    /// Processes a mixed reaction, e.g. bittersweet as Joy and Distress. The facets' VADN changes
+    /// are combined as a weighted mean so the turn moves the state once rather than once per
+    /// facet. Weights are relative; if none is positive the facets count equally.
+    pub fn process_emotion_blend(&mut self, blend: &[(AppraisedEmotion, f64)]) {
+        if blend.is_empty() {
+            return;
+        }
+
+        let weights: Vec<f64> = blend.iter().map(|(_, w)| if w.is_finite() { w.max(0.0) } else { 0.0 }).collect();
+        let total: f64 = weights.iter().sum();
+        let weights: Vec<f64> = if total > 0.0 {
+            weights.iter().map(|w| w / total).collect()
+        } else {
+            vec![1.0 / blend.len() as f64; blend.len()]
+        };
+        let emotions: Vec<&AppraisedEmotion> = blend.iter().map(|(emotion, _)| emotion).collect();
+        let mean = |dimension: fn(&AffectiveStateChange) -> f64| {
+            emotions.iter().zip(&weights).map(|(e, w)| dimension(&e.vadn) * w).sum::<f64>()
+        };
+        let blended = AppraisedEmotion {
+            emotion: emotions.iter().map(|e| e.describe()).collect::<Vec<_>>().join(" + "),
+            vadn: AffectiveStateChange {
+                valence: mean(|c| c.valence),
+                arousal: mean(|c| c.arousal),
+                dominance: mean(|c| c.dominance),
+                novelty: mean(|c| c.novelty),
+            },
+            details: serde_json::Value::Array(emotions.iter().map(|e| e.details.clone()).collect()),
+        };
+        let blend_is_milestone = blended.vadn.valence.abs() > 0.6 || blended.vadn.arousal > 0.7;
+        self.process_emotion(&blended);
+
+        // Strong individual facets are still remembered even when the blend is mild
+        if !blend_is_milestone {
+            for emotion in emotions.iter().filter(|e| e.vadn.valence.abs() > 0.6 || e.vadn.arousal > 0.7) {
+                self.memory.record_milestone_with_intensity(milestone_description(emotion), milestone_intensity(&emotion.vadn));
+            }
+        }
+    }
+
+    /// Volatility implied by the tuning alone (0.0 to 1.0): how much of each input is absorbed
+    /// and how little of it regulation removes per step.
+    pub fn predicted_volatility(&self) -> f64 {
+        (self.config.empathy_factor * (1.0 - self.config.decay_rate)).clamp(0.0, 1.0)
+    }
+
+    /// Checks the current and baseline states and the tuning parameters for corruption.
+    pub fn check_invariants(&self) -> Vec<InvariantViolation> {
+        let mut violations = self.current_state.check_invariants("affective_core", "current_state");
+        violations.extend(self.memory.personality.baseline_state.check_invariants("affective_core", "baseline_state"));
+        check_range(&mut violations, "affective_core", "decay_rate", self.config.decay_rate, 0.0, 1.0);
+        check_range(&mut violations, "affective_core", "empathy_factor", self.config.empathy_factor, 0.0, 1.0);
+        violations
+    }
+
     /// Applies emotional regulation, decaying the state toward its personality's baseline.
     pub fn regulate_emotion(&mut self) {
         let baseline = self.memory.personality.baseline_state;
-        self.current_state.decay(baseline, self.decay_rate);
+        self.current_state.decay(baseline, self.config.decay_rate);
+        self.last_update = Utc::now();
+    }
+
+    /// When the state last changed
+    pub fn last_update(&self) -> DateTime<Utc> {
+        self.last_update
+    }
+
+    /// Decays toward baseline for the real time elapsed since the last update, treating
+    /// `decay_rate` as the fraction recovered per minute. A state reloaded after hours away
+    /// comes back calm.
+    pub fn decay_elapsed(&mut self, now: DateTime<Utc>) {
+        let minutes = (now - self.last_update).num_milliseconds() as f64 / 60_000.0;
+        if minutes <= 0.0 {
+            return;
+        }
+        let retained = (1.0 - self.config.decay_rate.clamp(0.0, 1.0)).powf(minutes);
+        let baseline = self.memory.personality.baseline_state;
+        self.current_state.decay(baseline, 1.0 - retained);
+        self.last_update = now;
     }
     
-    /// Triggers the self-reflection process.
-    pub async fn reflect(&mut self) {
+    /// Triggers the self-reflection process using the shared LLM API client.
+    pub async fn reflect(&mut self) -> Result<(), LlmApiError> {
+        let client = llm_api::get_api_client()?;
+        self.reflect_with(client).await
+    }
+
+    /// Reflects on memory through `backend` and adopts the returned personality, whose
+    /// baseline becomes the state regulation decays toward.
+    pub async fn reflect_with(&mut self, backend: &dyn LlmBackend) -> Result<(), LlmApiError> {
         println!("\n--- SELF-REFLECTION TRIGGERED ---");
-        match llm_api::call_llm_for_reflection(&self.memory).await {
+        let result = backend.reflect(&self.memory).await;
+        match &result {
             Ok(new_personality) => {
                 println!("💡 Reflection successful. Personality has been updated.");
                 println!("Old personality: {:?}", self.memory.personality);
                 println!("New personality: {:?}", new_personality);
-                self.memory.personality = new_personality;
+                self.adopt_personality(*new_personality);
             }
             Err(e) => {
                 eprintln!("🔥 Reflection Error: {}", e);
             }
         }
         println!("--- SELF-REFLECTION COMPLETE ---\n");
+        result.map(|_| ())
     }
 
     // ... (rest of the file is unchanged) ...
@@ -140,20 +510,37 @@ impl AffectiveCore {
         )
     }
     
+    /// Summarizes a VADN point as a feeling, covering every valence/arousal/dominance region.
+    /// Valence splits at ±0.3, arousal at 0.25 and 0.5, dominance at ±0.3.
     fn synthesize_feeling(&self, v: f64, a: f64, d: f64) -> String {
-        if v > 0.4 && a > 0.45 { "elated and proud".to_string() }
-        else if v > 0.4 { "pleased and content".to_string() }
-        else if v < -0.5 && a > 0.5 {
-            if d > 0.4 { "indignant and assertive".to_string() }
-            else { "anxious and distressed".to_string() }
-        }
-        else if v < -0.5 {
-            if d < -0.4 { "dejected and powerless".to_string() }
-            else { "somber and disappointed".to_string() }
+        let (high_arousal, low_arousal) = (a > 0.5, a < 0.25);
+        let (in_control, powerless) = (d > 0.3, d < -0.3);
+
+        let feeling = if v > 0.3 {
+            if high_arousal {
+                if in_control { "elated and proud" }
+                else if powerless { "excited but swept along" }
+                else { "joyful and energized" }
+            }
+            else if low_arousal { "content and relaxed" }
+            else if in_control { "pleased and confident" }
+            else { "pleased and content" }
+        } else if v < -0.3 {
+            if high_arousal {
+                if in_control { "indignant and assertive" }
+                else if powerless { "anxious and overwhelmed" }
+                else { "anxious and distressed" }
+            }
+            else if powerless { "dejected and powerless" }
+            else if low_arousal { "weary and downcast" }
+            else { "somber and disappointed" }
+        } else if a > 0.6 {
+            if powerless { "on edge and uncertain" } else { "alert and focused" }
         }
-        else if a > 0.6 { "alert and focused".to_string() }
-        else if a < 0.25 { "calm and relaxed".to_string() }
-        else { "calmly neutral".to_string() }
+        else if low_arousal { "calm and relaxed" }
+        else { "calmly neutral" };
+
+        feeling.to_string()
     }
 }
 
@@ -164,26 +551,277 @@ impl Default for AffectiveCore {
     }
 }
 
-fn describe_valence(v: f64) -> &'static str {
+pub fn describe_valence(v: f64) -> &'static str {
     if v > 0.7 { "very positive" } else if v > 0.3 { "positive" }
     else if v < -0.7 { "very negative" } else if v < -0.3 { "negative" }
     else { "neutral" }
 }
 
-fn describe_arousal(a: f64) -> &'static str {
+pub(crate) fn describe_arousal(a: f64) -> &'static str {
     if a > 0.8 { "very high energy" } else if a > 0.6 { "high energy" }
     else if a < 0.2 { "very low energy" } else if a < 0.4 { "low energy" }
     else { "moderate energy" }
 }
 
-fn describe_dominance(d: f64) -> &'static str {
+pub(crate) fn describe_dominance(d: f64) -> &'static str {
     if d > 0.7 { "very high control" } else if d > 0.3 { "in control" }
     else if d < -0.7 { "very low control" } else if d < -0.3 { "lacking control" }
     else { "neutral control" }
 }
 
-fn describe_novelty(n: f64) -> &'static str {
+pub(crate) fn describe_novelty(n: f64) -> &'static str {
     if n > 0.7 { "highly surprising" } else if n > 0.3 { "surprising" }
     else if n < -0.7 { "highly expected" } else if n < -0.3 { "expected" }
     else { "neutral" }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm_api::MockLlmClient;
+    use crate::memory::Personality;
+
+    fn appraised(emotion: &str, valence: f64, arousal: f64, dominance: f64) -> AppraisedEmotion {
+        AppraisedEmotion {
+            emotion: emotion.to_string(),
+            vadn: AffectiveStateChange { valence, arousal, dominance, novelty: 0.0 },
+            details: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn test_mood_inertia_slows_valence_but_not_arousal() {
+        let mut core = AffectiveCore::with_config(AffectiveConfig {
+            empathy_factor: 1.0,
+            mood_inertia: 0.5,
+            ..AffectiveConfig::default()
+        });
+        let before = core.current_state();
+        core.process_emotion(&appraised("Despair", -1.0, 0.4, 0.0));
+        let after = core.current_state();
+
+        // Valence only goes half way toward the -1.0 target; arousal takes the full change
+        assert!((after.valence - (before.valence - 0.5)).abs() < 1e-9);
+        assert!((after.arousal - (before.arousal + 0.4)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_decay_elapsed_scales_with_time_away() {
+        let mut core = AffectiveCore::with_config(AffectiveConfig { mood_inertia: 0.0, ..AffectiveConfig::default() });
+        core.process_emotion(&appraised("Rage", -0.9, 0.6, 0.0));
+        let upset = core.current_state().valence;
+        let baseline = core.memory.personality.baseline_state.valence;
+
+        let mut brief: AffectiveCore = serde_json::from_value(serde_json::to_value(&core).unwrap()).unwrap();
+        brief.decay_elapsed(core.last_update() + chrono::Duration::minutes(1));
+        core.decay_elapsed(core.last_update() + chrono::Duration::hours(3));
+
+        // One minute recovers `decay_rate` of the gap; hours away return to baseline
+        let expected = upset + (baseline - upset) * brief.config().decay_rate;
+        assert!((brief.current_state().valence - expected).abs() < 1e-9);
+        assert!((core.current_state().valence - baseline).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_synthesize_feeling_covers_quadrants() {
+        let core = AffectiveCore::new();
+        let cases = [
+            ((0.8, 0.8, 0.6), "elated and proud"),
+            ((0.6, 0.1, 0.0), "content and relaxed"),
+            ((0.5, 0.4, 0.0), "pleased and content"),
+            ((-0.7, 0.8, -0.6), "anxious and overwhelmed"),
+            ((-0.7, 0.8, 0.6), "indignant and assertive"),
+            ((-0.6, 0.1, 0.0), "weary and downcast"),
+            ((-0.6, 0.4, -0.5), "dejected and powerless"),
+            ((0.0, 0.3, 0.1), "calmly neutral"),
+        ];
+        for ((v, a, d), expected) in cases {
+            assert_eq!(core.synthesize_feeling(v, a, d), expected, "V:{} A:{} D:{}", v, a, d);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reflect_adopts_new_baseline() {
+        let reflective = Personality {
+            baseline_state: AffectiveState { valence: 0.4, arousal: 1.5, dominance: 0.2, novelty: 0.0 },
+        };
+        let mock = MockLlmClient::new(Vec::new(), reflective);
+        let mut core = AffectiveCore::new();
+
+        core.reflect_with(&mock).await.unwrap();
+
+        assert_eq!(mock.reflection_count(), 1);
+        let baseline = core.memory.personality.baseline_state;
+        assert_eq!(baseline.valence, 0.4);
+        assert_eq!(baseline.arousal, 1.0);
+    }
+
+    #[test]
+    fn test_personality_baseline_is_clamped() {
+        let extreme = Personality { baseline_state: AffectiveState { valence: 1.5, arousal: -0.3, dominance: -2.0, novelty: 0.4 } };
+        let core = AffectiveCore::with_personality(extreme, AffectiveConfig::default());
+
+        for state in [core.memory.personality.baseline_state, core.current_state()] {
+            assert_eq!((state.valence, state.arousal, state.dominance, state.novelty), (1.0, 0.0, -1.0, 0.4));
+        }
+    }
+
+    #[test]
+    fn test_runtime_tuning_is_clamped() {
+        let mut core = AffectiveCore::new();
+        core.set_empathy_factor(1.7);
+        core.set_decay_rate(-0.2);
+        core.set_baseline_state(AffectiveState { valence: 2.0, arousal: -1.0, dominance: 0.4, novelty: 0.0 });
+
+        assert_eq!(core.config().empathy_factor, 1.0);
+        assert_eq!(core.config().decay_rate, 0.0);
+        let baseline = core.memory.personality.baseline_state;
+        assert_eq!((baseline.valence, baseline.arousal, baseline.dominance), (1.0, 0.0, 0.4));
+    }
+
+    #[test]
+    fn test_state_survives_save_and_load() {
+        let mut core = AffectiveCore::new();
+        core.set_empathy_factor(0.5);
+        core.process_emotion(&appraised("Grief", -0.9, 0.4, -0.5));
+        let path = std::env::temp_dir().join(format!("cogno_core_{}.json", std::process::id()));
+
+        core.save_to_path(&path).unwrap();
+        let loaded = AffectiveCore::load_from_path(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.current_state().valence, core.current_state().valence);
+        assert_eq!(loaded.config().empathy_factor, 0.5);
+        assert_eq!(loaded.memory.emotional_milestones.len(), core.memory.emotional_milestones.len());
+    }
+
+    #[test]
+    fn test_normalized_inputs_do_not_pin_to_clamp() {
+        let joy = appraised("Joy", 0.6, 0.0, 0.0);
+        let no_inertia = AffectiveConfig { mood_inertia: 0.0, ..AffectiveConfig::default() };
+        let mut raw = AffectiveCore::with_config(no_inertia);
+        let mut normalized = AffectiveCore::with_config(AffectiveConfig {
+            normalize_inputs: true,
+            ..no_inertia
+        });
+
+        let mut previous = normalized.current_state().valence;
+        for _ in 0..5 {
+            raw.process_emotion(&joy);
+            normalized.process_emotion(&joy);
+            let valence = normalized.current_state().valence;
+            assert!(valence > previous, "state should keep approaching the bound");
+            previous = valence;
+        }
+
+        assert_eq!(raw.current_state().valence, 1.0);
+        assert!(previous < 1.0 && previous > 0.5);
+    }
+
+    #[test]
+    fn test_runtime_empathy_change() {
+        let mut core = AffectiveCore::with_config(AffectiveConfig { mood_inertia: 0.0, ..AffectiveConfig::default() });
+        core.set_empathy_factor(1.5);
+        assert_eq!(core.config().empathy_factor, 1.0);
+
+        core.set_empathy_factor(0.5);
+        let before = core.current_state().valence;
+        core.process_emotion(&appraised("Joy", 0.4, 0.0, 0.0));
+        assert!((core.current_state().valence - before - 0.2).abs() < 1e-9);
+
+        core.set_decay_rate(-1.0);
+        assert_eq!(core.config().decay_rate, 0.0);
+    }
+
+    #[test]
+    fn test_intensity_and_distance() {
+        let state = AffectiveState { valence: -0.6, arousal: 0.3, dominance: -0.3, novelty: 0.9 };
+        assert!((state.intensity() - 0.4).abs() < 1e-9);
+        assert_eq!(AffectiveState::default().intensity(), 0.0);
+        assert_eq!(AffectiveState { valence: -1.0, arousal: 1.0, dominance: 1.0, novelty: 0.0 }.intensity(), 1.0);
+
+        let other = AffectiveState { valence: 0.0, arousal: 0.3, dominance: 0.5, novelty: 0.9 };
+        assert!((state.distance(&other) - 1.0).abs() < 1e-9);
+        assert_eq!(state.distance(&state), 0.0);
+        assert_eq!(state.distance(&other), other.distance(&state));
+    }
+
+    #[test]
+    fn test_emotional_arc_climax_and_transitions() {
+        let intensities = [0.2, 0.35, 0.5, 0.8, 0.6, 0.4, 0.38];
+        let arc = EmotionalArc::from_intensities(&intensities);
+
+        assert_eq!(arc.peak_turn, Some(3));
+        assert_eq!(arc.peak_intensity, 0.8);
+        assert_eq!(arc.phase, ArcPhase::Resolution);
+        assert_eq!(arc.transitions, vec![
+            (0, ArcPhase::Rising),
+            (3, ArcPhase::Climax),
+            (4, ArcPhase::Falling),
+            (6, ArcPhase::Resolution),
+        ]);
+
+        // Still building: no climax yet
+        let building = EmotionalArc::from_intensities(&intensities[..4]);
+        assert_eq!(building.phase, ArcPhase::Rising);
+        assert_eq!(building.peak_turn, None);
+    }
+
+    #[test]
+    fn test_mixed_facets_both_affect_core() {
+        let pride = appraised("Pride", 0.7, 0.2, 0.5);
+        let worry = appraised("Worry", -0.4, 0.3, -0.3);
+
+        let mut pride_only = AffectiveCore::new();
+        pride_only.process_emotion(&pride);
+        let mut worry_only = AffectiveCore::new();
+        worry_only.process_emotion(&worry);
+        let mut mixed = AffectiveCore::new();
+        mixed.process_emotions(&[pride, worry]);
+
+        let (p, w, m) = (pride_only.current_state(), worry_only.current_state(), mixed.current_state());
+        assert!(m.valence < p.valence && m.valence > w.valence);
+        assert!(m.dominance < p.dominance && m.dominance > w.dominance);
+        // The strong Pride facet is remembered even though the blend is mild
+        assert!(mixed.memory.emotional_milestones.iter().any(|m| m.description.contains("Pride")));
+    }
+
+    #[test]
+    fn test_milestone_keeps_focus_and_reason() {
+        let mut news = appraised("Elation", 0.9, 0.8, 0.4);
+        news.details = serde_json::json!({ "focus": "the acceptance letter", "reason": "a long-held hope came true" });
+        let mut core = AffectiveCore::new();
+        core.process_emotion(&news);
+
+        let milestone = &core.memory.emotional_milestones[0].description;
+        assert!(milestone.contains("Elation about the acceptance letter (a long-held hope came true)"), "{}", milestone);
+    }
+
+    #[test]
+    fn test_blend_weights_shift_the_mix() {
+        let hope = appraised("Hope", 0.6, 0.4, 0.2);
+        let fear = appraised("Fear", -0.6, 0.4, -0.4);
+
+        let mut mostly_hope = AffectiveCore::new();
+        mostly_hope.process_emotion_blend(&[(hope.clone(), 3.0), (fear.clone(), 1.0)]);
+        let mut mostly_fear = AffectiveCore::new();
+        mostly_fear.process_emotion_blend(&[(hope.clone(), 1.0), (fear.clone(), 3.0)]);
+        assert!(mostly_hope.current_state().valence > 0.0);
+        assert!(mostly_fear.current_state().valence < 0.0);
+
+        // Weights in the appraisal details feed the same path; zero weights fall back to equal
+        let mut weighted_hope = hope.clone();
+        weighted_hope.details = serde_json::json!({ "weight": 0.75 });
+        let mut weighted_fear = fear.clone();
+        weighted_fear.details = serde_json::json!({ "weight": 0.25 });
+        let mut from_details = AffectiveCore::new();
+        from_details.process_emotions(&[weighted_hope, weighted_fear]);
+        assert!((from_details.current_state().valence - mostly_hope.current_state().valence).abs() < 1e-9);
+
+        let mut zero = AffectiveCore::new();
+        zero.process_emotion_blend(&[(hope.clone(), 0.0), (fear.clone(), 0.0)]);
+        let mut equal = AffectiveCore::new();
+        equal.process_emotions(&[hope, fear]);
+        assert!((zero.current_state().valence - equal.current_state().valence).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,172 @@
+//! emotion_expression.rs
+//!
+//! Renders an affective state as text in a chosen style, so the same VADN point can be
+//! reported tersely in logs, explained in full, or voiced poetically by a more open
+//! personality.
+
+use crate::core::{describe_arousal, describe_dominance, describe_novelty, describe_valence, AffectiveState};
+use crate::memory::Personality;
+use serde::{Deserialize, Serialize};
+
+/// How much, and how colourfully, a feeling is put into words
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExpressionStyle {
+    /// A word or two, e.g. "anxious, surprised"
+    Terse,
+    /// A plain sentence naming every dimension
+    Verbose,
+    /// An image rather than a description
+    Poetic,
+}
+
+impl ExpressionStyle {
+    /// Style that suits a personality: a baseline open to novelty speaks poetically, a
+    /// guarded one tersely. Baseline novelty stands in for openness to experience.
+    pub fn for_personality(personality: &Personality) -> Self {
+        let openness = personality.baseline_state.novelty;
+        if openness > 0.3 {
+            ExpressionStyle::Poetic
+        } else if openness < -0.3 {
+            ExpressionStyle::Terse
+        } else {
+            ExpressionStyle::Verbose
+        }
+    }
+}
+
+/// Overall tone of a VADN point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tone {
+    Elated,
+    Content,
+    Anxious,
+    Frustrated,
+    Subdued,
+    Alert,
+    Calm,
+    Neutral,
+}
+
+impl Tone {
+    /// Valence splits at ±0.3 and arousal at 0.3 and 0.6; dominance separates anxiety
+    /// (no control) from frustration (control) when negative feelings run high.
+    pub fn of(state: &AffectiveState) -> Self {
+        let (v, a, d) = (state.valence, state.arousal, state.dominance);
+        if v > 0.3 {
+            if a > 0.6 { Tone::Elated } else { Tone::Content }
+        } else if v < -0.3 {
+            if a > 0.6 {
+                if d < 0.0 { Tone::Anxious } else { Tone::Frustrated }
+            } else {
+                Tone::Subdued
+            }
+        } else if a > 0.6 {
+            Tone::Alert
+        } else if a < 0.3 {
+            Tone::Calm
+        } else {
+            Tone::Neutral
+        }
+    }
+
+    fn word(self) -> &'static str {
+        match self {
+            Tone::Elated => "elated",
+            Tone::Content => "content",
+            Tone::Anxious => "anxious",
+            Tone::Frustrated => "frustrated",
+            Tone::Subdued => "subdued",
+            Tone::Alert => "alert",
+            Tone::Calm => "calm",
+            Tone::Neutral => "steady",
+        }
+    }
+
+    fn image(self) -> &'static str {
+        match self {
+            Tone::Elated => "like sunlight breaking through cloud",
+            Tone::Content => "like a warm, unhurried afternoon",
+            Tone::Anxious => "like a storm gathering at the edge of the sky",
+            Tone::Frustrated => "like a kettle just short of the boil",
+            Tone::Subdued => "like rain against a window",
+            Tone::Alert => "like a bird listening in the undergrowth",
+            Tone::Calm => "like still water at dusk",
+            Tone::Neutral => "like an unwritten page",
+        }
+    }
+}
+
+/// Novelty above this adds a note of surprise to every style
+const SURPRISE_THRESHOLD: f64 = 0.5;
+
+pub struct EmotionExpression;
+
+impl EmotionExpression {
+    /// Put `state` into words in the given style
+    pub fn express_emotion(state: &AffectiveState, style: ExpressionStyle) -> String {
+        let tone = Tone::of(state);
+        let surprised = state.novelty > SURPRISE_THRESHOLD;
+        match style {
+            ExpressionStyle::Terse if surprised => format!("{}, surprised", tone.word()),
+            ExpressionStyle::Terse => tone.word().to_string(),
+            ExpressionStyle::Verbose => format!(
+                "I feel {}: {} in mood, with {}, {}, and this feels {}.",
+                tone.word(),
+                describe_valence(state.valence),
+                describe_arousal(state.arousal),
+                describe_dominance(state.dominance),
+                describe_novelty(state.novelty),
+            ),
+            ExpressionStyle::Poetic if surprised => format!("I feel {}, and something new is stirring.", tone.image()),
+            ExpressionStyle::Poetic => format!("I feel {}.", tone.image()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(valence: f64, arousal: f64, dominance: f64, novelty: f64) -> AffectiveState {
+        AffectiveState { valence, arousal, dominance, novelty }
+    }
+
+    #[test]
+    fn test_tone_mapping() {
+        let cases = [
+            (state(0.8, 0.8, 0.5, 0.0), Tone::Elated),
+            (state(0.5, 0.3, 0.2, 0.0), Tone::Content),
+            (state(-0.6, 0.8, -0.5, 0.0), Tone::Anxious),
+            (state(-0.6, 0.8, 0.4, 0.0), Tone::Frustrated),
+            (state(-0.6, 0.2, 0.0, 0.0), Tone::Subdued),
+            (state(0.0, 0.8, 0.0, 0.0), Tone::Alert),
+            (state(0.0, 0.1, 0.0, 0.0), Tone::Calm),
+            (AffectiveState::new_neutral(), Tone::Neutral),
+        ];
+        for (state, tone) in cases {
+            assert_eq!(Tone::of(&state), tone, "{:?}", state);
+        }
+    }
+
+    #[test]
+    fn test_styles_render_the_same_state_differently() {
+        let anxious = state(-0.6, 0.8, -0.5, 0.7);
+        assert_eq!(EmotionExpression::express_emotion(&anxious, ExpressionStyle::Terse), "anxious, surprised");
+        assert_eq!(
+            EmotionExpression::express_emotion(&anxious, ExpressionStyle::Verbose),
+            "I feel anxious: negative in mood, with high energy, lacking control, and this feels surprising."
+        );
+        assert_eq!(
+            EmotionExpression::express_emotion(&anxious, ExpressionStyle::Poetic),
+            "I feel like a storm gathering at the edge of the sky, and something new is stirring."
+        );
+    }
+
+    #[test]
+    fn test_style_follows_personality_openness() {
+        let with_novelty = |novelty| Personality { baseline_state: state(0.0, 0.3, 0.1, novelty) };
+        assert_eq!(ExpressionStyle::for_personality(&with_novelty(0.6)), ExpressionStyle::Poetic);
+        assert_eq!(ExpressionStyle::for_personality(&with_novelty(0.0)), ExpressionStyle::Verbose);
+        assert_eq!(ExpressionStyle::for_personality(&with_novelty(-0.6)), ExpressionStyle::Terse);
+    }
+}
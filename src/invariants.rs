@@ -0,0 +1,44 @@
+//! invariants.rs
+//!
+//! Cross-subsystem consistency checks used to debug state corruption.
+
+use std::fmt;
+
+/// A single broken invariant found while validating the mind's state
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvariantViolation {
+    /// Subsystem that owns the inconsistent state (e.g. "goals", "attention")
+    pub subsystem: &'static str,
+    pub description: String,
+}
+
+impl InvariantViolation {
+    pub fn new(subsystem: &'static str, description: String) -> Self {
+        InvariantViolation { subsystem, description }
+    }
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.subsystem, self.description)
+    }
+}
+
+/// Record a violation if `value` is NaN or outside `[min, max]`
+pub(crate) fn check_range(
+    violations: &mut Vec<InvariantViolation>,
+    subsystem: &'static str,
+    field: &str,
+    value: f64,
+    min: f64,
+    max: f64,
+) {
+    if value.is_nan() {
+        violations.push(InvariantViolation::new(subsystem, format!("{} is NaN", field)));
+    } else if !(min..=max).contains(&value) {
+        violations.push(InvariantViolation::new(
+            subsystem,
+            format!("{} = {:.3} is outside [{}, {}]", field, value, min, max),
+        ));
+    }
+}
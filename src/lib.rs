@@ -0,0 +1,34 @@
+//! lib.rs
+//!
+//! Cogno: an affective agent with a continuous inner life. `ContinuousMind` ties the
+//! subsystems together; each can also be used on its own.
+
+pub mod config;
+pub mod core;
+pub mod cognitive_appraisal;
+pub mod emotion_expression;
+pub mod llm_api;
+pub mod memory;
+pub mod metacognition;
+pub mod goals;
+pub mod attention;
+pub mod continuous_mind;
+pub mod invariants;
+pub mod lock_util;
+pub mod session;
+pub mod text_util;
+pub mod utils;
+
+pub use crate::attention::{AttentionConfig, AttentionState, AttentionSystem, AttentionTarget};
+pub use crate::cognitive_appraisal::{AffectiveStateChange, AppraisedEmotion};
+pub use crate::continuous_mind::{
+    ContinuousMind, ContinuousMindBuilder, ContinuousMindConfig, MentalActivity, MindBuildError, MindSnapshot, SpontaneousThought,
+    ThoughtKind,
+};
+pub use crate::core::{AffectiveConfig, AffectiveCore, AffectiveState};
+pub use crate::emotion_expression::{EmotionExpression, ExpressionStyle};
+pub use crate::goals::{Goal, GoalCategory, GoalStatus, GoalSystem, GoalTemplate, ImportanceBreakdown};
+pub use crate::invariants::InvariantViolation;
+pub use crate::llm_api::{LlmApiClient, LlmApiConfig, LlmApiError, LlmBackend, LlmProvider, MockLlmClient, OfflineLlmClient};
+pub use crate::memory::{Memory, Milestone, Personality};
+pub use crate::metacognition::{CognitiveProcess, MetacognitiveMonitor};
@@ -2,14 +2,21 @@
 //!
 //! Enhanced LLM API with robust error handling, retry mechanisms, and proper async patterns.
 
-use crate::cognitive_appraisal::AppraisedEmotion;
+use crate::cognitive_appraisal::{local_appraise_with, AffectiveStateChange, AppraisedEmotion};
 use crate::memory::{Memory, Personality};
+use crate::text_util::Tokenizer;
+use futures::future::BoxFuture;
+use futures::{future, stream, FutureExt, Stream, StreamExt};
+use rand::Rng;
 use reqwest::Client;
 use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
 use std::env;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::time::Duration;
 use tokio::time::timeout;
-use std::sync::{ OnceLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
 use thiserror::Error;
 
 
@@ -37,8 +44,9 @@ pub enum LlmApiError {
     #[error("LLM returned empty response")]
     EmptyResponse,
     
+    /// `retry_after` is the wait the provider asked for in its `Retry-After` header, if any
     #[error("API rate limit exceeded")]
-    RateLimitExceeded,
+    RateLimitExceeded { retry_after: Option<Duration> },
     
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
@@ -48,40 +56,270 @@ pub enum LlmApiError {
     
     #[error("Invalid emotion mapping: {details}")]
     InvalidEmotionMapping { details: String },
+
+    #[error("LLM client failed to initialize: {reason}")]
+    ClientInitFailed { reason: String },
+}
+
+impl LlmApiError {
+    /// A short, actionable explanation of the error for the person at the keyboard
+    pub fn user_message(&self) -> String {
+        match self {
+            LlmApiError::ApiKeyMissing => {
+                match LlmProvider::from_env().unwrap_or_default().api_key_var() {
+                    Some(var) => format!("🔑 No API key found. Set {} or switch COGNO_LLM_PROVIDER to ollama.", var),
+                    None => "🔑 No API key found for the configured provider.".to_string(),
+                }
+            }
+            LlmApiError::NetworkError(_) => {
+                "🌐 Couldn't reach the LLM service. Check your connection; local processing continues meanwhile.".to_string()
+            }
+            LlmApiError::Timeout { seconds } => {
+                format!("⌛ The LLM didn't answer within {}s. Try again, or raise timeout_seconds in the [llm] config.", seconds)
+            }
+            LlmApiError::HttpError { status: 401 | 403, .. } => {
+                "🔑 The LLM service rejected the API key. Check that it is valid and has access to the model.".to_string()
+            }
+            LlmApiError::HttpError { status, .. } if *status >= 500 => {
+                format!("🛠️ The LLM service is having trouble (HTTP {}). Try again in a moment.", status)
+            }
+            LlmApiError::HttpError { status, message } => {
+                format!("⚠️ The LLM service refused the request (HTTP {}): {}", status, message)
+            }
+            LlmApiError::JsonParseError { .. }
+            | LlmApiError::InvalidResponseStructure { .. }
+            | LlmApiError::EmptyResponse
+            | LlmApiError::InvalidEmotionMapping { .. } => {
+                "🤔 The model's reply couldn't be understood. Trying again usually helps.".to_string()
+            }
+            LlmApiError::RateLimitExceeded { retry_after: Some(wait) } => {
+                format!("⏳ The LLM service is busy. Try again in {}s.", wait.as_secs().max(1))
+            }
+            LlmApiError::RateLimitExceeded { retry_after: None } => {
+                "⏳ The LLM service is busy. Try again in a moment.".to_string()
+            }
+            LlmApiError::SerializationError(e) => format!("⚠️ Couldn't prepare the LLM request: {}", e),
+            LlmApiError::MaxRetriesExceeded { attempts } => {
+                format!("🔁 The LLM service failed {} times in a row. Try again in a moment.", attempts)
+            }
+            LlmApiError::ClientInitFailed { reason } => {
+                format!("⚙️ The LLM client couldn't start ({}). Check the [llm] settings and environment.", reason)
+            }
+        }
+    }
+}
+
+/// Which LLM API the client talks to
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LlmProvider {
+    #[default]
+    Gemini,
+    OpenAI,
+    Anthropic,
+    /// A local Ollama server; needs no API key, so it works fully offline
+    Ollama { base_url: String, model: String },
+}
+
+impl LlmProvider {
+    /// Ollama on its default port, overridable via `OLLAMA_BASE_URL` and `OLLAMA_MODEL`
+    pub fn ollama_from_env() -> Self {
+        LlmProvider::Ollama {
+            base_url: env::var("OLLAMA_BASE_URL").unwrap_or_else(|_| "http://localhost:11434".to_string()),
+            model: env::var("OLLAMA_MODEL").unwrap_or_else(|_| "llama3".to_string()),
+        }
+    }
+
+    /// Provider named by `COGNO_LLM_PROVIDER` ("gemini", "openai", "anthropic" or "ollama"), if set and valid
+    pub fn from_env() -> Option<Self> {
+        Self::from_name(&env::var("COGNO_LLM_PROVIDER").ok()?)
+    }
+
+    /// Provider for a case-insensitive name ("gemini", "openai", "anthropic" or "ollama")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "gemini" => Some(LlmProvider::Gemini),
+            "openai" => Some(LlmProvider::OpenAI),
+            "anthropic" => Some(LlmProvider::Anthropic),
+            "ollama" => Some(LlmProvider::ollama_from_env()),
+            _ => None,
+        }
+    }
+
+    /// Environment variable holding this provider's API key, or `None` if it needs no key
+    pub fn api_key_var(&self) -> Option<&'static str> {
+        match self {
+            LlmProvider::Gemini => Some("GEMINI_API_KEY"),
+            LlmProvider::OpenAI => Some("OPENAI_API_KEY"),
+            LlmProvider::Anthropic => Some("ANTHROPIC_API_KEY"),
+            LlmProvider::Ollama { .. } => None,
+        }
+    }
+
+    fn default_model(&self) -> &str {
+        match self {
+            LlmProvider::Gemini => "gemini-1.5-flash",
+            LlmProvider::OpenAI => "gpt-4o-mini",
+            LlmProvider::Anthropic => "claude-3-5-haiku-latest",
+            LlmProvider::Ollama { model, .. } => model,
+        }
+    }
+}
+
+/// How much memory is sent to the reflection LLM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReflectionDepth {
+    /// The entire memory
+    Full,
+    /// Memory with only the most recent N emotional milestones
+    Recent(usize),
+    /// Aggregate statistics instead of raw history
+    SummaryOnly,
 }
 
 /// Configuration for LLM API requests
 #[derive(Debug, Clone)]
 pub struct LlmApiConfig {
+    /// Backend to use; `None` reads `COGNO_LLM_PROVIDER` and falls back to Gemini
+    pub provider: Option<LlmProvider>,
+    /// Model used for every call; `None` uses the provider's default (`gemini-1.5-flash` for Gemini)
+    pub model: Option<String>,
+    /// Separate, usually stronger model for self-reflection; `None` falls back to `model`
+    pub reflection_model: Option<String>,
     pub timeout_seconds: u64,
     pub max_retries: u32,
+    /// Base delays for exponential backoff: the wait doubles with each attempt, with jitter
     pub retry_delay_ms: u64,
     pub rate_limit_delay_ms: u64,
+    /// Upper bound on any single backoff wait
+    pub max_retry_delay_ms: u64,
+    /// Upper bound on the number of emotional facets returned by a multi-appraisal
+    pub max_emotion_facets: usize,
+    /// Sampling settings sent as `generationConfig`; `None` leaves the model default.
+    /// An explicit `temperature` applies to every call, including appraisal and reflection.
+    pub temperature: Option<f64>,
+    pub top_p: Option<f64>,
+    pub max_output_tokens: Option<u32>,
+    /// Per-call temperatures used when `temperature` is unset: appraisal should be consistent,
+    /// reflection a bit creative
+    pub appraisal_temperature: Option<f64>,
+    pub reflection_temperature: Option<f64>,
+    pub reflection_depth: ReflectionDepth,
+    /// Total tokens this client may use before warning about the conversation's size
+    pub token_budget: Option<u64>,
+    /// Number of appraisals remembered for identical prompt and memory; 0 disables caching
+    pub appraisal_cache_capacity: usize,
 }
 
 impl Default for LlmApiConfig {
     fn default() -> Self {
         LlmApiConfig {
+            provider: None,
+            model: None,
+            reflection_model: None,
             timeout_seconds: 30,
             max_retries: 3,
             retry_delay_ms: 1000,
             rate_limit_delay_ms: 5000,
+            max_retry_delay_ms: 30_000,
+            max_emotion_facets: 3,
+            temperature: None,
+            top_p: None,
+            max_output_tokens: None,
+            appraisal_temperature: Some(0.2),
+            reflection_temperature: Some(0.7),
+            reflection_depth: ReflectionDepth::Recent(10),
+            token_budget: None,
+            appraisal_cache_capacity: 128,
+        }
+    }
+}
+
+/// Running token usage totals across a client's calls
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UsageStats {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub calls: u64,
+}
+
+impl UsageStats {
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+/// Least-recently-used cache of appraisals keyed by a hash of the request inputs
+#[derive(Debug)]
+struct AppraisalCache {
+    capacity: usize,
+    entries: HashMap<u64, Vec<AppraisedEmotion>>,
+    /// Keys from least to most recently used
+    order: VecDeque<u64>,
+}
+
+impl AppraisalCache {
+    fn new(capacity: usize) -> Self {
+        AppraisalCache { capacity, entries: HashMap::new(), order: VecDeque::new() }
+    }
+
+    fn key(kind: &str, user_prompt: &str, memory_context: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (kind, user_prompt, memory_context).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn touch(&mut self, key: u64) {
+        self.order.retain(|&k| k != key);
+        self.order.push_back(key);
+    }
+
+    fn get(&mut self, key: u64) -> Option<Vec<AppraisedEmotion>> {
+        let hit = self.entries.get(&key).cloned()?;
+        self.touch(key);
+        Some(hit)
+    }
+
+    fn insert(&mut self, key: u64, emotions: Vec<AppraisedEmotion>) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.entries.insert(key, emotions);
+        self.touch(key);
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
         }
     }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
 }
 
 /// Enhanced LLM API client with robust error handling
 pub struct LlmApiClient {
     client: Client,
     config: LlmApiConfig,
+    provider: LlmProvider,
     api_key: String,
+    usage: Mutex<UsageStats>,
+    cache: Mutex<AppraisalCache>,
 }
 
 impl LlmApiClient {
     /// Create a new LLM API client
     pub fn new(config: Option<LlmApiConfig>) -> Result<Self, LlmApiError> {
-        let api_key = env::var("GEMINI_API_KEY")
-            .map_err(|_| LlmApiError::ApiKeyMissing)?;
+        let config = config.unwrap_or_default();
+        let provider = config.provider.clone()
+            .or_else(LlmProvider::from_env)
+            .unwrap_or_default();
+
+        let api_key = match provider.api_key_var() {
+            Some(key_var) => env::var(key_var).map_err(|_| LlmApiError::ApiKeyMissing)?,
+            None => String::new(),
+        };
         
         let client = Client::builder()
             .timeout(Duration::from_secs(60)) // Overall client timeout
@@ -90,45 +328,112 @@ impl LlmApiClient {
         
         Ok(LlmApiClient {
             client,
-            config: config.unwrap_or_default(),
+            provider,
             api_key,
+            usage: Mutex::new(UsageStats::default()),
+            cache: Mutex::new(AppraisalCache::new(config.appraisal_cache_capacity)),
+            config,
         })
     }
 
+    /// The backend this client talks to
+    pub fn provider(&self) -> &LlmProvider {
+        &self.provider
+    }
+
+    /// Token usage accumulated over every call made by this client
+    pub fn get_usage_stats(&self) -> UsageStats {
+        *self.usage.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Add the token counts reported in a response body to the running totals,
+    /// warning once the configured token budget is exceeded
+    fn record_usage(&self, body: &Value) {
+        let (prompt_path, completion_path) = match &self.provider {
+            LlmProvider::Gemini => ("/usageMetadata/promptTokenCount", "/usageMetadata/candidatesTokenCount"),
+            LlmProvider::OpenAI => ("/usage/prompt_tokens", "/usage/completion_tokens"),
+            LlmProvider::Anthropic => ("/usage/input_tokens", "/usage/output_tokens"),
+            LlmProvider::Ollama { .. } => ("/prompt_eval_count", "/eval_count"),
+        };
+        let count = |path: &str| body.pointer(path).and_then(Value::as_u64).unwrap_or(0);
+        let (prompt, completion) = (count(prompt_path), count(completion_path));
+
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        let before = usage.total_tokens();
+        usage.prompt_tokens += prompt;
+        usage.completion_tokens += completion;
+        usage.calls += 1;
+
+        if let Some(budget) = self.config.token_budget
+            && before <= budget && usage.total_tokens() > budget {
+            println!("⚠️ Token budget exceeded: {} tokens used over {} calls (budget {})",
+                     usage.total_tokens(), usage.calls, budget);
+        }
+    }
+
+    /// Forget every cached appraisal
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    fn cached_appraisal(&self, key: u64) -> Option<Vec<AppraisedEmotion>> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner()).get(key)
+    }
+
+    fn cache_appraisal(&self, key: u64, emotions: Vec<AppraisedEmotion>) {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner()).insert(key, emotions);
+    }
+
+    /// Model used for appraisal calls
+    pub fn model(&self) -> &str {
+        self.config.model.as_deref().unwrap_or_else(|| self.provider.default_model())
+    }
+
+    /// Model used for reflection calls
+    pub fn reflection_model(&self) -> &str {
+        self.config.reflection_model.as_deref().unwrap_or_else(|| self.model())
+    }
+
     /// Call LLM for cognitive appraisal with enhanced error handling
     pub async fn call_for_appraisal(&self, user_prompt: &str, memory: &Memory) -> Result<AppraisedEmotion, LlmApiError> {
-        println!("📞 Calling LLM API for cognitive appraisal...");
-        
         let memory_context = serde_json::to_string(memory)
             .map_err(LlmApiError::SerializationError)?;
 
+        let cache_key = AppraisalCache::key("single", user_prompt, &memory_context);
+        if let Some(emotion) = self.cached_appraisal(cache_key).and_then(|hit| hit.into_iter().next()) {
+            println!("♻️ Using cached appraisal: {:?}", emotion.emotion);
+            return Ok(emotion);
+        }
+
+        println!("📞 Calling LLM API for cognitive appraisal...");
+
         let prompt_text = self.build_appraisal_prompt(&memory_context, user_prompt);
-        let request_body = self.build_request_body(&prompt_text)?;
+        let request_body = self.appraisal_request_body(&prompt_text)?;
         
         for attempt in 1..=self.config.max_retries {
-            match self.execute_request_with_timeout(&request_body).await {
+            match self.execute_request_with_timeout(&request_body, self.model()).await {
                 Ok(response) => {
                     match self.parse_appraisal_response(response).await {
                         Ok(emotion) => {
                             println!("✅ Successfully parsed emotion: {:?}", emotion.emotion);
+                            self.cache_appraisal(cache_key, vec![emotion.clone()]);
                             return Ok(emotion);
                         }
                         Err(e) if attempt < self.config.max_retries => {
                             println!("⚠️ Parsing failed on attempt {}: {:?}. Retrying...", attempt, e);
-                            self.wait_before_retry().await;
+                            self.wait_before_retry(self.config.retry_delay_ms, attempt).await;
                             continue;
                         }
                         Err(e) => return Err(e),
                     }
                 }
-                Err(LlmApiError::RateLimitExceeded) if attempt < self.config.max_retries => {
-                    println!("⏳ Rate limit hit on attempt {}. Waiting longer...", attempt);
-                    tokio::time::sleep(Duration::from_millis(self.config.rate_limit_delay_ms)).await;
+                Err(LlmApiError::RateLimitExceeded { retry_after }) if attempt < self.config.max_retries => {
+                    self.wait_for_rate_limit(retry_after, attempt).await?;
                     continue;
                 }
                 Err(e) if attempt < self.config.max_retries && self.is_retryable_error(&e) => {
                     println!("🔄 Retryable error on attempt {}: {:?}. Retrying...", attempt, e);
-                    self.wait_before_retry().await;
+                    self.wait_before_retry(self.config.retry_delay_ms, attempt).await;
                     continue;
                 }
                 Err(e) => return Err(e),
@@ -140,18 +445,115 @@ impl LlmApiClient {
         })
     }
 
+    /// Stream the multi-facet appraisal text as it arrives. Yields each text delta until the
+    /// accumulated text forms a complete JSON value; concatenate the chunks and parse them like a
+    /// `call_for_multi_appraisal` response. The rest of the body is still read so the usage
+    /// reported in the final event is recorded. No retries are attempted mid-stream.
+    pub fn call_for_appraisal_streaming<'a>(&'a self, user_prompt: &'a str, memory: &'a Memory) -> impl Stream<Item = Result<String, LlmApiError>> + 'a {
+        let response = stream::once(async move {
+            let memory_context = serde_json::to_string(memory)
+                .map_err(LlmApiError::SerializationError)?;
+
+            let prompt_text = self.build_multi_appraisal_prompt(&memory_context, user_prompt);
+            let mut request_body = self.appraisal_request_body(&prompt_text)?;
+            if self.provider != LlmProvider::Gemini {
+                request_body["stream"] = Value::Bool(true);
+            }
+            if self.provider == LlmProvider::OpenAI {
+                request_body["stream_options"] = serde_json::json!({ "include_usage": true });
+            }
+            self.send_request(&request_body, self.model(), true).await
+        });
+
+        let bytes = response.flat_map(|response| match response {
+            Ok(response) => response.bytes_stream()
+                .map(|chunk| chunk.map_err(LlmApiError::NetworkError))
+                .left_stream(),
+            Err(e) => stream::iter([Err(e)]).right_stream(),
+        });
+
+        stream::unfold((Some(Box::pin(bytes)), StreamAssembler::default()), move |(bytes, mut assembler)| async move {
+            let mut bytes = bytes?;
+            match bytes.next().await {
+                Some(Ok(chunk)) => {
+                    let deltas = assembler.push(&chunk, |event| self.extract_stream_delta(event));
+                    Some((stream::iter(deltas), (Some(bytes), assembler)))
+                }
+                Some(Err(e)) => Some((stream::iter(vec![Err(e)]), (None, assembler))),
+                None => {
+                    if let Some(event) = assembler.usage_event.take() {
+                        self.record_usage(&event);
+                    }
+                    None
+                }
+            }
+        })
+        .flatten()
+    }
+
+    /// Call LLM for a multi-facet appraisal: one emotion per distinct referent in the text,
+    /// capped at `max_emotion_facets`
+    pub async fn call_for_multi_appraisal(&self, user_prompt: &str, memory: &Memory) -> Result<Vec<AppraisedEmotion>, LlmApiError> {
+        let memory_context = serde_json::to_string(memory)
+            .map_err(LlmApiError::SerializationError)?;
+
+        let cache_key = AppraisalCache::key("multi", user_prompt, &memory_context);
+        if let Some(emotions) = self.cached_appraisal(cache_key) {
+            println!("♻️ Using {} cached emotional facet(s)", emotions.len());
+            return Ok(emotions);
+        }
+
+        println!("📞 Calling LLM API for multi-facet appraisal...");
+
+        let prompt_text = self.build_multi_appraisal_prompt(&memory_context, user_prompt);
+        let request_body = self.appraisal_request_body(&prompt_text)?;
+
+        for attempt in 1..=self.config.max_retries {
+            match self.execute_request_with_timeout(&request_body, self.model()).await {
+                Ok(response) => {
+                    match self.parse_multi_appraisal_response(response).await {
+                        Ok(emotions) => {
+                            println!("✅ Successfully parsed {} emotional facet(s)", emotions.len());
+                            self.cache_appraisal(cache_key, emotions.clone());
+                            return Ok(emotions);
+                        }
+                        Err(e) if attempt < self.config.max_retries => {
+                            println!("⚠️ Parsing failed on attempt {}: {:?}. Retrying...", attempt, e);
+                            self.wait_before_retry(self.config.retry_delay_ms, attempt).await;
+                            continue;
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+                Err(LlmApiError::RateLimitExceeded { retry_after }) if attempt < self.config.max_retries => {
+                    self.wait_for_rate_limit(retry_after, attempt).await?;
+                    continue;
+                }
+                Err(e) if attempt < self.config.max_retries && self.is_retryable_error(&e) => {
+                    println!("🔄 Retryable error on attempt {}: {:?}. Retrying...", attempt, e);
+                    self.wait_before_retry(self.config.retry_delay_ms, attempt).await;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(LlmApiError::MaxRetriesExceeded {
+            attempts: self.config.max_retries
+        })
+    }
+
     /// Call LLM for self-reflection with enhanced error handling
     pub async fn call_for_reflection(&self, memory: &Memory) -> Result<Personality, LlmApiError> {
         println!("🧘‍♀️ Calling LLM API for self-reflection...");
         
-        let memory_summary = serde_json::to_string_pretty(memory)
-            .map_err(LlmApiError::SerializationError)?;
+        let memory_summary = reflection_memory_context(memory, self.config.reflection_depth)?;
         
         let prompt_text = self.build_reflection_prompt(&memory_summary);
-        let request_body = self.build_request_body(&prompt_text)?;
+        let request_body = self.build_request_body(&prompt_text, self.config.reflection_temperature, self.reflection_model())?;
         
         for attempt in 1..=self.config.max_retries {
-            match self.execute_request_with_timeout(&request_body).await {
+            match self.execute_request_with_timeout(&request_body, self.reflection_model()).await {
                 Ok(response) => {
                     match self.parse_reflection_response(response).await {
                         Ok(personality) => {
@@ -160,15 +562,19 @@ impl LlmApiClient {
                         }
                         Err(e) if attempt < self.config.max_retries => {
                             println!("⚠️ Reflection parsing failed on attempt {}: {:?}. Retrying...", attempt, e);
-                            self.wait_before_retry().await;
+                            self.wait_before_retry(self.config.retry_delay_ms, attempt).await;
                             continue;
                         }
                         Err(e) => return Err(e),
                     }
                 }
+                Err(LlmApiError::RateLimitExceeded { retry_after }) if attempt < self.config.max_retries => {
+                    self.wait_for_rate_limit(retry_after, attempt).await?;
+                    continue;
+                }
                 Err(e) if attempt < self.config.max_retries && self.is_retryable_error(&e) => {
                     println!("🔄 Retryable error on attempt {}: {:?}. Retrying...", attempt, e);
-                    self.wait_before_retry().await;
+                    self.wait_before_retry(self.config.retry_delay_ms, attempt).await;
                     continue;
                 }
                 Err(e) => return Err(e),
@@ -181,14 +587,36 @@ impl LlmApiClient {
     }
 
     /// Execute HTTP request with timeout
-    async fn execute_request_with_timeout(&self, request_body: &Value) -> Result<reqwest::Response, LlmApiError> {
-        let api_url = format!(
-            "https://generativelanguage.googleapis.com/v1beta/models/gemini-1.5-flash:generateContent?key={}",
-            self.api_key
-        );
-
-        let request_future = self.client
-            .post(&api_url)
+    async fn execute_request_with_timeout(&self, request_body: &Value, model: &str) -> Result<reqwest::Response, LlmApiError> {
+        self.send_request(request_body, model, false).await
+    }
+
+    /// Send a request to the provider, using its streaming endpoint if `streaming` is set.
+    /// `model` only affects the URL for Gemini; other providers carry it in the body.
+    async fn send_request(&self, request_body: &Value, model: &str, streaming: bool) -> Result<reqwest::Response, LlmApiError> {
+        let request = match &self.provider {
+            LlmProvider::Gemini if streaming => self.client.post(format!(
+                "https://generativelanguage.googleapis.com/v1beta/models/{}:streamGenerateContent?alt=sse&key={}",
+                model,
+                self.api_key
+            )),
+            LlmProvider::Gemini => self.client.post(format!(
+                "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+                model,
+                self.api_key
+            )),
+            LlmProvider::OpenAI => self.client
+                .post("https://api.openai.com/v1/chat/completions")
+                .bearer_auth(&self.api_key),
+            LlmProvider::Anthropic => self.client
+                .post("https://api.anthropic.com/v1/messages")
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", "2023-06-01"),
+            LlmProvider::Ollama { base_url, .. } => self.client
+                .post(format!("{}/api/generate", base_url.trim_end_matches('/'))),
+        };
+
+        let request_future = request
             .json(request_body)
             .send();
 
@@ -205,11 +633,15 @@ impl LlmApiClient {
         // Check for HTTP errors
         if !response.status().is_success() {
             let status = response.status().as_u16();
+            let retry_after = response.headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| parse_retry_after(value, chrono::Utc::now()));
             let error_text = response.text().await
                 .unwrap_or_else(|_| "Unknown error".to_string());
             
             return if status == 429 {
-                Err(LlmApiError::RateLimitExceeded)
+                Err(LlmApiError::RateLimitExceeded { retry_after })
             } else {
                 Err(LlmApiError::HttpError {
                     status,
@@ -229,16 +661,23 @@ impl LlmApiClient {
             })?;
 
         println!("📄 Raw API Response: {}", serde_json::to_string_pretty(&body).unwrap_or_default());
+        self.record_usage(&body);
 
         let text_content = self.extract_text_content(&body)?;
-        let cleaned_text = self.clean_json_text(&text_content)?;
-        
+        self.parse_appraisal_text(&text_content)
+    }
+
+    /// Parse the appraisal model's text output, e.g. the assembled chunks of a streamed appraisal
+    pub fn parse_appraisal_text(&self, text_content: &str) -> Result<AppraisedEmotion, LlmApiError> {
+        let cleaned_text = self.clean_json_text(text_content)?;
+
         if cleaned_text.is_empty() {
             return Err(LlmApiError::EmptyResponse);
         }
 
         // Parse the cleaned JSON
         serde_json::from_str::<AppraisedEmotion>(&cleaned_text)
+            .map(clamp_appraised_vadn)
             .map_err(|e| {
                 LlmApiError::InvalidEmotionMapping {
                     details: format!("Failed to parse emotion JSON: {}. Content: '{}'", e, cleaned_text)
@@ -246,12 +685,25 @@ impl LlmApiClient {
             })
     }
 
+    /// Parse multi-facet appraisal response
+    async fn parse_multi_appraisal_response(&self, response: reqwest::Response) -> Result<Vec<AppraisedEmotion>, LlmApiError> {
+        let body: Value = response.json().await
+            .map_err(|e| LlmApiError::JsonParseError {
+                reason: format!("Failed to parse response as JSON: {}", e)
+            })?;
+        self.record_usage(&body);
+
+        let text_content = self.extract_text_content(&body)?;
+        parse_appraised_emotions(&text_content, self.config.max_emotion_facets)
+    }
+
     /// Parse self-reflection response
     async fn parse_reflection_response(&self, response: reqwest::Response) -> Result<Personality, LlmApiError> {
         let body: Value = response.json().await
             .map_err(|e| LlmApiError::JsonParseError { 
                 reason: format!("Failed to parse reflection response as JSON: {}", e)
             })?;
+        self.record_usage(&body);
 
         let text_content = self.extract_text_content(&body)?;
         let cleaned_text = self.clean_json_text(&text_content)?;
@@ -270,21 +722,55 @@ impl LlmApiClient {
 
     /// Extract text content from API response
     fn extract_text_content(&self, body: &Value) -> Result<String, LlmApiError> {
-        let text_content = body
-            .get("candidates")
-            .and_then(|c| c.get(0))
-            .and_then(|c| c.get("content"))
-            .and_then(|p| p.get("parts"))
-            .and_then(|p| p.get(0))
-            .and_then(|p| p.get("text"))
+        let text = match &self.provider {
+            LlmProvider::Gemini => body
+                .get("candidates")
+                .and_then(|c| c.get(0))
+                .and_then(|c| c.get("content"))
+                .and_then(|p| p.get("parts"))
+                .and_then(|p| p.get(0))
+                .and_then(|p| p.get("text")),
+            LlmProvider::OpenAI => body
+                .get("choices")
+                .and_then(|c| c.get(0))
+                .and_then(|c| c.get("message"))
+                .and_then(|m| m.get("content")),
+            LlmProvider::Anthropic => body
+                .get("content")
+                .and_then(|c| c.get(0))
+                .and_then(|c| c.get("text")),
+            LlmProvider::Ollama { .. } => body.get("response"),
+        };
+
+        let text_content = text
             .and_then(|t| t.as_str())
             .ok_or_else(|| LlmApiError::InvalidResponseStructure {
-                details: "Expected text content not found in response".to_string()
+                details: format!("Expected {:?} text content not found in response", self.provider)
             })?;
 
         Ok(text_content.to_string())
     }
 
+    /// Extract the text delta from one streamed event, if it carries any
+    fn extract_stream_delta(&self, event: &Value) -> Option<String> {
+        let text = match &self.provider {
+            // Each Gemini SSE event is a partial `generateContent` response
+            LlmProvider::Gemini => return self.extract_text_content(event).ok(),
+            LlmProvider::OpenAI => event
+                .get("choices")
+                .and_then(|c| c.get(0))
+                .and_then(|c| c.get("delta"))
+                .and_then(|d| d.get("content")),
+            LlmProvider::Anthropic => event
+                .get("delta")
+                .filter(|_| event.get("type").and_then(|t| t.as_str()) == Some("content_block_delta"))
+                .and_then(|d| d.get("text")),
+            LlmProvider::Ollama { .. } => event.get("response"),
+        };
+
+        text.and_then(|t| t.as_str()).map(str::to_string)
+    }
+
     /// Clean JSON text by removing markdown formatting
     fn clean_json_text(&self, text: &str) -> Result<String, LlmApiError> {
         let cleaned = text
@@ -308,15 +794,93 @@ impl LlmApiClient {
         Ok(cleaned.to_string())
     }
 
-    /// Build request body for API calls
-    fn build_request_body(&self, prompt_text: &str) -> Result<Value, LlmApiError> {
-        Ok(serde_json::json!({
-            "contents": [{
-                "parts": [{
-                    "text": prompt_text
-                }]
-            }]
-        }))
+    /// Request body for an appraisal call with the appraisal model and temperature
+    fn appraisal_request_body(&self, prompt_text: &str) -> Result<Value, LlmApiError> {
+        self.build_request_body(prompt_text, self.config.appraisal_temperature, self.model())
+    }
+
+    /// Build request body for API calls in the provider's format. An explicitly configured
+    /// temperature takes precedence over the call's own `call_temperature`.
+    fn build_request_body(&self, prompt_text: &str, call_temperature: Option<f64>, model: &str) -> Result<Value, LlmApiError> {
+        let temperature = self.config.temperature.or(call_temperature);
+
+        let body = match &self.provider {
+            LlmProvider::Gemini => {
+                let mut body = serde_json::json!({
+                    "contents": [{
+                        "parts": [{
+                            "text": prompt_text
+                        }]
+                    }]
+                });
+
+                let mut generation_config = serde_json::Map::new();
+                if let Some(temperature) = temperature {
+                    generation_config.insert("temperature".to_string(), temperature.into());
+                }
+                if let Some(top_p) = self.config.top_p {
+                    generation_config.insert("topP".to_string(), top_p.into());
+                }
+                if let Some(max_output_tokens) = self.config.max_output_tokens {
+                    generation_config.insert("maxOutputTokens".to_string(), max_output_tokens.into());
+                }
+                if !generation_config.is_empty() {
+                    body["generationConfig"] = Value::Object(generation_config);
+                }
+                body
+            }
+            LlmProvider::OpenAI => {
+                let mut body = serde_json::json!({
+                    "model": model,
+                    "messages": [{ "role": "user", "content": prompt_text }]
+                });
+                if let Some(temperature) = temperature {
+                    body["temperature"] = temperature.into();
+                }
+                if let Some(top_p) = self.config.top_p {
+                    body["top_p"] = top_p.into();
+                }
+                if let Some(max_output_tokens) = self.config.max_output_tokens {
+                    body["max_tokens"] = max_output_tokens.into();
+                }
+                body
+            }
+            LlmProvider::Anthropic => {
+                // Anthropic requires max_tokens on every request
+                let mut body = serde_json::json!({
+                    "model": model,
+                    "max_tokens": self.config.max_output_tokens.unwrap_or(1024),
+                    "messages": [{ "role": "user", "content": prompt_text }]
+                });
+                if let Some(temperature) = temperature {
+                    body["temperature"] = temperature.into();
+                }
+                if let Some(top_p) = self.config.top_p {
+                    body["top_p"] = top_p.into();
+                }
+                body
+            }
+            LlmProvider::Ollama { .. } => {
+                let mut options = serde_json::Map::new();
+                if let Some(temperature) = temperature {
+                    options.insert("temperature".to_string(), temperature.into());
+                }
+                if let Some(top_p) = self.config.top_p {
+                    options.insert("top_p".to_string(), top_p.into());
+                }
+                if let Some(max_output_tokens) = self.config.max_output_tokens {
+                    options.insert("num_predict".to_string(), max_output_tokens.into());
+                }
+                serde_json::json!({
+                    "model": model,
+                    "prompt": prompt_text,
+                    "stream": false,
+                    "options": options
+                })
+            }
+        };
+
+        Ok(body)
     }
 
     /// Build the appraisal prompt
@@ -354,6 +918,49 @@ Respond only with the JSON object."#,
         )
     }
 
+    /// Build the multi-facet appraisal prompt
+    fn build_multi_appraisal_prompt(&self, memory_context: &str, user_prompt: &str) -> String {
+        format!(
+            r#"Your task is to perform a deep cognitive appraisal of the user's text.
+The text may express several distinct emotions toward different referents (people, events, outcomes).
+1. Identify up to {} distinct, nuanced emotions. Only list more than one if the text really contains separate emotional facets.
+2. Map each emotion to a dimensional model of affect (VADN).
+3. Respond with a single, clean JSON array.
+
+**Your Memory Context:**
+{}
+
+**VADN Dimensions:**
+- `valence`: Pleasure vs. Displeasure (-1.0 to 1.0).
+- `arousal`: Energy/Activation level (0.0 to 1.0).
+- `dominance`: Sense of control/power (-1.0 to 1.0).
+- `novelty`: Surprise/Unexpectedness (-1.0 to 1.0).
+
+**JSON Schema:**
+Each element MUST be an object with three keys: "emotion" (string), "vadn" (object), and "details" (object, including the "referent" and a "weight" from 0.0 to 1.0 giving the facet's share of the overall reaction).
+
+**Example for "I got the promotion, but I'm worried about leaving my old team.":**
+[
+    {{
+        "emotion": "Pride",
+        "vadn": {{"valence": 0.7, "arousal": 0.6, "dominance": 0.5, "novelty": 0.3}},
+        "details": {{"referent": "the promotion", "reason": "The user achieved something they worked for.", "weight": 0.6}}
+    }},
+    {{
+        "emotion": "Worry",
+        "vadn": {{"valence": -0.4, "arousal": 0.5, "dominance": -0.3, "novelty": 0.2}},
+        "details": {{"referent": "the old team", "reason": "The user is concerned about the change.", "weight": 0.4}}
+    }}
+]
+
+**User Text:**
+"{}"
+
+Respond only with the JSON array."#,
+            self.config.max_emotion_facets.max(1), memory_context, user_prompt
+        )
+    }
+
     /// Build the reflection prompt
     fn build_reflection_prompt(&self, memory_summary: &str) -> String {
         format!(
@@ -403,26 +1010,382 @@ Respond only with the JSON object."#,
         }
     }
 
-    /// Wait before retrying
-    async fn wait_before_retry(&self) {
-        tokio::time::sleep(Duration::from_millis(self.config.retry_delay_ms)).await;
+    /// Wait out a 429 before retry number `attempt`: exactly as long as the provider asked, or
+    /// with backoff from `rate_limit_delay_ms` if it didn't say. A requested wait longer than
+    /// `max_retry_delay_ms` gives up instead, since retrying sooner would just be refused.
+    async fn wait_for_rate_limit(&self, retry_after: Option<Duration>, attempt: u32) -> Result<(), LlmApiError> {
+        match retry_after {
+            Some(wait) if wait > Duration::from_millis(self.config.max_retry_delay_ms) => {
+                Err(LlmApiError::RateLimitExceeded { retry_after })
+            }
+            Some(wait) => {
+                println!("⏳ Rate limit hit on attempt {}. Provider asked to wait {:?}...", attempt, wait);
+                tokio::time::sleep(wait).await;
+                Ok(())
+            }
+            None => {
+                println!("⏳ Rate limit hit on attempt {}. Waiting longer...", attempt);
+                self.wait_before_retry(self.config.rate_limit_delay_ms, attempt).await;
+                Ok(())
+            }
+        }
+    }
+
+    /// Wait before retry number `attempt`, backing off exponentially from `base_ms` with jitter
+    /// so concurrent callers don't retry in lockstep
+    async fn wait_before_retry(&self, base_ms: u64, attempt: u32) {
+        let delay = backoff_delay(base_ms, attempt, self.config.max_retry_delay_ms, &mut rand::rng());
+        tokio::time::sleep(delay).await;
     }
 }
 
-// Global API client instance (safe initialization)
-static API_CLIENT: OnceLock<LlmApiClient> = OnceLock::new();
+/// The LLM operations the mind depends on, so a live client can be swapped for a mock
+pub trait LlmBackend: Send + Sync {
+    /// Appraise every distinct emotional facet of a user's prompt
+    fn appraise<'a>(&'a self, user_prompt: &'a str, memory: &'a Memory) -> BoxFuture<'a, Result<Vec<AppraisedEmotion>, LlmApiError>>;
+
+    /// Reflect on memory and propose an updated personality
+    fn reflect<'a>(&'a self, memory: &'a Memory) -> BoxFuture<'a, Result<Personality, LlmApiError>>;
+
+    /// Appraise like `appraise`, handing the response text to `on_partial` as it arrives.
+    /// Backends that can't stream answer in one go without calling it.
+    fn appraise_streaming<'a>(
+        &'a self,
+        user_prompt: &'a str,
+        memory: &'a Memory,
+        _on_partial: &'a mut (dyn FnMut(&str) + Send),
+    ) -> BoxFuture<'a, Result<Vec<AppraisedEmotion>, LlmApiError>> {
+        self.appraise(user_prompt, memory)
+    }
+}
+
+impl LlmBackend for LlmApiClient {
+    fn appraise<'a>(&'a self, user_prompt: &'a str, memory: &'a Memory) -> BoxFuture<'a, Result<Vec<AppraisedEmotion>, LlmApiError>> {
+        self.call_for_multi_appraisal(user_prompt, memory).boxed()
+    }
+
+    fn reflect<'a>(&'a self, memory: &'a Memory) -> BoxFuture<'a, Result<Personality, LlmApiError>> {
+        self.call_for_reflection(memory).boxed()
+    }
+
+    /// Streams the multi-facet appraisal, sharing `call_for_multi_appraisal`'s cache. Since nothing
+    /// is retried mid-stream, a failed or unparsable stream falls back to the (retrying) multi-facet call.
+    fn appraise_streaming<'a>(
+        &'a self,
+        user_prompt: &'a str,
+        memory: &'a Memory,
+        on_partial: &'a mut (dyn FnMut(&str) + Send),
+    ) -> BoxFuture<'a, Result<Vec<AppraisedEmotion>, LlmApiError>> {
+        async move {
+            let memory_context = serde_json::to_string(memory)
+                .map_err(LlmApiError::SerializationError)?;
+            let cache_key = AppraisalCache::key("multi", user_prompt, &memory_context);
+            if let Some(emotions) = self.cached_appraisal(cache_key) {
+                println!("♻️ Using {} cached emotional facet(s)", emotions.len());
+                return Ok(emotions);
+            }
+
+            let mut text = String::new();
+            let mut chunks = std::pin::pin!(self.call_for_appraisal_streaming(user_prompt, memory));
+            while let Some(chunk) = chunks.next().await {
+                match chunk {
+                    Ok(delta) => {
+                        on_partial(&delta);
+                        text.push_str(&delta);
+                    }
+                    Err(e) => {
+                        println!("⚠️ Appraisal stream failed: {}. Retrying without streaming...", e);
+                        return self.call_for_multi_appraisal(user_prompt, memory).await;
+                    }
+                }
+            }
+            match parse_appraised_emotions(&text, self.config.max_emotion_facets) {
+                Ok(emotions) => {
+                    self.cache_appraisal(cache_key, emotions.clone());
+                    Ok(emotions)
+                }
+                Err(e) => {
+                    println!("⚠️ Streamed appraisal unparsable: {}. Retrying without streaming...", e);
+                    self.call_for_multi_appraisal(user_prompt, memory).await
+                }
+            }
+        }
+        .boxed()
+    }
+}
+
+/// Offline backend returning canned responses, for deterministic tests and demos
+pub struct MockLlmClient {
+    appraisals: Vec<AppraisedEmotion>,
+    personality: Personality,
+    appraisal_calls: AtomicUsize,
+    reflection_calls: AtomicUsize,
+}
+
+impl MockLlmClient {
+    /// A mock that answers every appraisal with `appraisals` and every reflection with `personality`.
+    /// An empty `appraisals` list makes appraisal fail with `EmptyResponse`.
+    pub fn new(appraisals: Vec<AppraisedEmotion>, personality: Personality) -> Self {
+        MockLlmClient {
+            appraisals,
+            personality,
+            appraisal_calls: AtomicUsize::new(0),
+            reflection_calls: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of appraisals requested so far
+    pub fn appraisal_count(&self) -> usize {
+        self.appraisal_calls.load(Ordering::SeqCst)
+    }
+
+    /// Number of reflections requested so far
+    pub fn reflection_count(&self) -> usize {
+        self.reflection_calls.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for MockLlmClient {
+    fn default() -> Self {
+        let interest = AppraisedEmotion {
+            emotion: "Interest".to_string(),
+            vadn: AffectiveStateChange { valence: 0.4, arousal: 0.5, dominance: 0.2, novelty: 0.3 },
+            details: serde_json::json!({ "source": "mock" }),
+        };
+        MockLlmClient::new(vec![interest], Personality::default())
+    }
+}
+
+impl LlmBackend for MockLlmClient {
+    fn appraise<'a>(&'a self, _user_prompt: &'a str, _memory: &'a Memory) -> BoxFuture<'a, Result<Vec<AppraisedEmotion>, LlmApiError>> {
+        self.appraisal_calls.fetch_add(1, Ordering::SeqCst);
+        let result = if self.appraisals.is_empty() {
+            Err(LlmApiError::EmptyResponse)
+        } else {
+            Ok(self.appraisals.clone())
+        };
+        future::ready(result).boxed()
+    }
+
+    fn reflect<'a>(&'a self, _memory: &'a Memory) -> BoxFuture<'a, Result<Personality, LlmApiError>> {
+        self.reflection_calls.fetch_add(1, Ordering::SeqCst);
+        future::ready(Ok(self.personality)).boxed()
+    }
+}
+
+/// Whether `COGNO_OFFLINE` asks for a run that never calls the LLM ("1", "true", "yes" or "on")
+pub fn offline_from_env() -> bool {
+    env::var("COGNO_OFFLINE")
+        .is_ok_and(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+}
+
+/// Backend that never touches the network: appraisal uses the local lexicon heuristic and
+/// reflection keeps the current personality. Used in offline mode.
+#[derive(Debug, Default, Clone)]
+pub struct OfflineLlmClient {
+    tokenizer: Tokenizer,
+}
+
+impl OfflineLlmClient {
+    /// Offline backend whose local appraisal splits prompts with `tokenizer`
+    pub fn new(tokenizer: Tokenizer) -> Self {
+        OfflineLlmClient { tokenizer }
+    }
+}
+
+impl LlmBackend for OfflineLlmClient {
+    fn appraise<'a>(&'a self, user_prompt: &'a str, _memory: &'a Memory) -> BoxFuture<'a, Result<Vec<AppraisedEmotion>, LlmApiError>> {
+        future::ready(Ok(vec![local_appraise_with(user_prompt, &self.tokenizer)])).boxed()
+    }
+
+    fn reflect<'a>(&'a self, memory: &'a Memory) -> BoxFuture<'a, Result<Personality, LlmApiError>> {
+        future::ready(Ok(memory.personality)).boxed()
+    }
+}
+
+/// Splits a streamed response body into events and accumulates their text deltas
+#[derive(Default)]
+struct StreamAssembler {
+    line_buffer: Vec<u8>,
+    accumulated: String,
+    complete: bool,
+    /// The latest event reporting token usage; providers send it with the final chunk
+    usage_event: Option<Value>,
+}
+
+impl StreamAssembler {
+    /// Consume a chunk of bytes and return the text deltas of every complete event in it.
+    /// Handles both SSE (`data: {...}`) and newline-delimited JSON bodies. Once the JSON value
+    /// is complete, later events are only scanned for usage.
+    fn push(&mut self, bytes: &[u8], extract: impl Fn(&Value) -> Option<String>) -> Vec<Result<String, LlmApiError>> {
+        let mut deltas = Vec::new();
+        self.line_buffer.extend_from_slice(bytes);
+
+        while let Some(newline) = self.line_buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.line_buffer.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&line);
+            let payload = line.trim();
+            let payload = payload.strip_prefix("data:").map(str::trim).unwrap_or(payload);
+
+            let Ok(event) = serde_json::from_str::<Value>(payload) else {
+                // Blank keep-alives, `event:` lines and `[DONE]` markers carry no text
+                continue;
+            };
+            let reports_usage = ["usage", "usageMetadata", "eval_count"].iter()
+                .any(|key| event.get(key).is_some_and(|v| !v.is_null()));
+            if reports_usage {
+                self.usage_event = Some(event.clone());
+            }
+            if self.complete {
+                continue;
+            }
+            if let Some(delta) = extract(&event).filter(|d| !d.is_empty()) {
+                self.accumulated.push_str(&delta);
+                deltas.push(Ok(delta));
+                self.complete = json_value_complete(&self.accumulated);
+            }
+        }
+
+        deltas
+    }
+}
+
+/// Whether `text` contains a JSON object or array whose brackets have all been closed
+fn json_value_complete(text: &str) -> bool {
+    let Some(start) = text.find(['{', '[']) else {
+        return false;
+    };
+
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in text[start..].chars() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' | '[' => depth += 1,
+            '}' | ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// The memory section of the reflection prompt at the given depth
+fn reflection_memory_context(memory: &Memory, depth: ReflectionDepth) -> Result<String, LlmApiError> {
+    let context = match depth {
+        ReflectionDepth::Full => serde_json::to_string_pretty(memory)?,
+        ReflectionDepth::Recent(count) => {
+            let mut recent = memory.clone();
+            let skip = recent.emotional_milestones.len().saturating_sub(count);
+            recent.emotional_milestones.drain(..skip);
+            serde_json::to_string_pretty(&recent)?
+        }
+        ReflectionDepth::SummaryOnly => serde_json::to_string_pretty(&serde_json::json!({
+            "personality": memory.personality,
+            "interaction_count": memory.interaction_count,
+            "emotional_milestone_count": memory.emotional_milestones.len(),
+            "user_name_known": memory.user_profile.name.is_some(),
+        }))?,
+    };
+    Ok(context)
+}
+
+/// Parse the LLM's multi-appraisal text into at most `max_facets` emotions.
+/// A single JSON object is accepted as a one-element list.
+fn parse_appraised_emotions(text: &str, max_facets: usize) -> Result<Vec<AppraisedEmotion>, LlmApiError> {
+    let cleaned = text
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+
+    if cleaned.is_empty() {
+        return Err(LlmApiError::EmptyResponse);
+    }
+
+    let mut emotions = if cleaned.starts_with('[') {
+        serde_json::from_str::<Vec<AppraisedEmotion>>(cleaned)
+    } else {
+        serde_json::from_str::<AppraisedEmotion>(cleaned).map(|emotion| vec![emotion])
+    }
+    .map_err(|e| LlmApiError::InvalidEmotionMapping {
+        details: format!("Failed to parse emotion list JSON: {}. Content: '{}'", e, cleaned)
+    })?;
+
+    if emotions.is_empty() {
+        return Err(LlmApiError::EmptyResponse);
+    }
+
+    emotions.truncate(max_facets.max(1));
+    Ok(emotions.into_iter().map(clamp_appraised_vadn).collect())
+}
+
+/// Jittered exponential backoff: `base_ms * 2^(attempt - 1)` scaled by a random factor in
+/// [0.5, 1.0], capped at `max_ms`. `attempt` counts from 1.
+fn backoff_delay(base_ms: u64, attempt: u32, max_ms: u64, rng: &mut impl Rng) -> Duration {
+    let exponential = base_ms as f64 * 2f64.powi(attempt.saturating_sub(1).min(32) as i32);
+    let jittered = exponential * rng.random_range(0.5..=1.0);
+    Duration::from_millis(jittered.min(max_ms as f64) as u64)
+}
+
+/// Parse a `Retry-After` header, given either as delay seconds or as an HTTP-date. Dates in
+/// the past mean no wait.
+fn parse_retry_after(value: &str, now: chrono::DateTime<chrono::Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some((date.with_timezone(&chrono::Utc) - now).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Clamp model-returned VADN values into their valid ranges, warning when the model strayed
+fn clamp_appraised_vadn(mut emotion: AppraisedEmotion) -> AppraisedEmotion {
+    let clamped = emotion.vadn.clamped();
+    if clamped.valence != emotion.vadn.valence
+        || clamped.arousal != emotion.vadn.arousal
+        || clamped.dominance != emotion.vadn.dominance
+        || clamped.novelty != emotion.vadn.novelty
+    {
+        eprintln!("⚠️ LLM returned out-of-range VADN for '{}': {:?}. Clamping.", emotion.emotion, emotion.vadn);
+        emotion.vadn = clamped;
+    }
+    emotion
+}
+
+// Global API client instance. A failed initialization is cached too, so it is
+// reported once and every later call gets the same error instead of retrying.
+static API_CLIENT: OnceLock<Result<LlmApiClient, LlmApiError>> = OnceLock::new();
 
 /// Get or initialize the global API client
-fn get_api_client() -> Result<&'static LlmApiClient, LlmApiError> {
-    API_CLIENT.get_or_init(|| {
-        // If initialization fails, panic with a clear message.
-        // This is a limitation of OnceLock on stable Rust.
-        LlmApiClient::new(None).unwrap_or_else(|e| {
-            panic!("Failed to initialize LlmApiClient: {:?}", e)
+pub fn get_api_client() -> Result<&'static LlmApiClient, LlmApiError> {
+    let client = API_CLIENT.get_or_init(|| {
+        LlmApiClient::new(None).inspect_err(|e| {
+            eprintln!("🔥 Failed to initialize LlmApiClient: {}", e);
         })
     });
-    // Safe to unwrap because we panic on error above.
-    Ok(API_CLIENT.get().unwrap())
+
+    match client {
+        Ok(client) => Ok(client),
+        Err(LlmApiError::ApiKeyMissing) => Err(LlmApiError::ApiKeyMissing),
+        Err(e) => Err(LlmApiError::ClientInitFailed { reason: e.to_string() }),
+    }
 }
 
 
@@ -440,6 +1403,19 @@ pub async fn call_llm_for_appraisal(user_prompt: &str, memory: &Memory) -> Resul
         })
 }
 
+#[allow(dead_code)]
+pub async fn call_llm_for_multi_appraisal(user_prompt: &str, memory: &Memory) -> Result<Vec<AppraisedEmotion>, Box<dyn std::error::Error>> {
+    let client = get_api_client()
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+
+    client.call_for_multi_appraisal(user_prompt, memory)
+        .await
+        .map_err(|e| {
+            eprintln!("🔥 Multi-Appraisal Error: {:?}", e);
+            Box::new(e) as Box<dyn std::error::Error>
+        })
+}
+
 #[allow(dead_code)]
 pub async fn call_llm_for_reflection(memory: &Memory) -> Result<Personality, Box<dyn std::error::Error>> {
     let client = get_api_client()
@@ -456,7 +1432,6 @@ pub async fn call_llm_for_reflection(memory: &Memory) -> Result<Personality, Box
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tokio;
 
     #[tokio::test]
     async fn test_api_client_creation() {
@@ -468,6 +1443,254 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_legacy_call_without_key_returns_error() {
+        if env::var("GEMINI_API_KEY").is_ok() {
+            return;
+        }
+
+        // Both the first call and the cached-failure path must return an error, not panic
+        let memory = Memory::new();
+        assert!(call_llm_for_appraisal("hello", &memory).await.is_err());
+        assert!(call_llm_for_reflection(&memory).await.is_err());
+        assert!(matches!(get_api_client(), Err(LlmApiError::ApiKeyMissing)));
+    }
+
+    fn client_with_config(config: LlmApiConfig) -> LlmApiClient {
+        LlmApiClient {
+            client: Client::new(),
+            provider: config.provider.clone().unwrap_or_default(),
+            api_key: "test-key".to_string(),
+            usage: Mutex::new(UsageStats::default()),
+            cache: Mutex::new(AppraisalCache::new(config.appraisal_cache_capacity)),
+            config,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_appraisal_skips_network() {
+        let client = client_with_config(LlmApiConfig::default());
+        let memory = Memory::new();
+        let memory_context = serde_json::to_string(&memory).unwrap();
+        let calm = AppraisedEmotion {
+            emotion: "Calm".to_string(),
+            vadn: AffectiveStateChange { valence: 0.2, arousal: 0.1, dominance: 0.3, novelty: 0.0 },
+            details: serde_json::json!({}),
+        };
+        client.cache_appraisal(AppraisalCache::key("multi", "hello", &memory_context), vec![calm]);
+
+        // The test key is invalid, so only a cache hit can succeed
+        let emotions = client.call_for_multi_appraisal("hello", &memory).await.unwrap();
+        assert_eq!(emotions[0].emotion, "Calm");
+
+        client.clear_cache();
+        assert!(client.cached_appraisal(AppraisalCache::key("multi", "hello", &memory_context)).is_none());
+    }
+
+    #[test]
+    fn test_appraisal_cache_evicts_least_recently_used() {
+        let mut cache = AppraisalCache::new(2);
+        cache.insert(1, Vec::new());
+        cache.insert(2, Vec::new());
+        assert!(cache.get(1).is_some());
+        cache.insert(3, Vec::new());
+
+        assert!(cache.get(2).is_none());
+        assert!(cache.get(1).is_some());
+        assert!(cache.get(3).is_some());
+    }
+
+    #[test]
+    fn test_usage_accumulates_across_calls() {
+        let client = client_with_config(LlmApiConfig {
+            token_budget: Some(100),
+            ..LlmApiConfig::default()
+        });
+        let response = serde_json::json!({
+            "candidates": [],
+            "usageMetadata": { "promptTokenCount": 60, "candidatesTokenCount": 25 }
+        });
+        client.record_usage(&response);
+        client.record_usage(&response);
+        // A response without usage data still counts as a call
+        client.record_usage(&serde_json::json!({ "candidates": [] }));
+
+        let usage = client.get_usage_stats();
+        assert_eq!(usage, UsageStats { prompt_tokens: 120, completion_tokens: 50, calls: 3 });
+        assert_eq!(usage.total_tokens(), 170);
+    }
+
+    #[test]
+    fn test_provider_request_and_response_formats() {
+        let openai = client_with_config(LlmApiConfig {
+            provider: Some(LlmProvider::OpenAI),
+            max_output_tokens: Some(200),
+            ..LlmApiConfig::default()
+        });
+        let body = openai.build_request_body("hi", Some(0.1), "test-model").unwrap();
+        assert_eq!(body["messages"][0]["content"], "hi");
+        assert_eq!(body["temperature"], 0.1);
+        assert_eq!(body["max_tokens"], 200);
+        let response = serde_json::json!({ "choices": [{ "message": { "content": "{}" } }] });
+        assert_eq!(openai.extract_text_content(&response).unwrap(), "{}");
+
+        let anthropic = client_with_config(LlmApiConfig {
+            provider: Some(LlmProvider::Anthropic),
+            ..LlmApiConfig::default()
+        });
+        let body = anthropic.build_request_body("hi", None, "test-model").unwrap();
+        assert_eq!(body["messages"][0]["role"], "user");
+        assert_eq!(body["max_tokens"], 1024);
+        let response = serde_json::json!({ "content": [{ "type": "text", "text": "{}" }] });
+        assert_eq!(anthropic.extract_text_content(&response).unwrap(), "{}");
+
+        // Gemini stays the default and rejects other providers' shapes
+        let gemini = client_with_config(LlmApiConfig::default());
+        assert_eq!(gemini.provider(), &LlmProvider::Gemini);
+        assert!(gemini.extract_text_content(&response).is_err());
+    }
+
+    #[test]
+    fn test_ollama_needs_no_api_key() {
+        let provider = LlmProvider::Ollama {
+            base_url: "http://localhost:11434".to_string(),
+            model: "llama3".to_string(),
+        };
+        assert_eq!(provider.api_key_var(), None);
+
+        let client = LlmApiClient::new(Some(LlmApiConfig {
+            provider: Some(provider),
+            max_output_tokens: Some(64),
+            ..LlmApiConfig::default()
+        }))
+        .expect("Ollama client should not require an API key");
+
+        let body = client.build_request_body("hi", Some(0.2), client.model()).unwrap();
+        assert_eq!(body["model"], "llama3");
+        assert_eq!(body["prompt"], "hi");
+        assert_eq!(body["stream"], false);
+        assert_eq!(body["options"]["num_predict"], 64);
+
+        let response = serde_json::json!({ "response": "{}", "done": true });
+        assert_eq!(client.extract_text_content(&response).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_configured_temperature_reaches_appraisal_requests() {
+        let client = client_with_config(LlmApiConfig { temperature: Some(0.9), ..LlmApiConfig::default() });
+        let prompt = client.build_appraisal_prompt("{}", "hello");
+        assert_eq!(client.appraisal_request_body(&prompt).unwrap()["generationConfig"]["temperature"], 0.9);
+
+        let unset = client_with_config(LlmApiConfig::default());
+        assert_eq!(unset.appraisal_request_body(&prompt).unwrap()["generationConfig"]["temperature"], 0.2);
+    }
+
+    #[test]
+    fn test_request_body_generation_config() {
+        let client = client_with_config(LlmApiConfig {
+            temperature: Some(0.5),
+            top_p: Some(0.9),
+            max_output_tokens: Some(256),
+            ..LlmApiConfig::default()
+        });
+
+        let body = client.build_request_body("hi", None, "test-model").unwrap();
+        assert_eq!(body["generationConfig"]["temperature"], 0.5);
+        assert_eq!(body["generationConfig"]["topP"], 0.9);
+        assert_eq!(body["generationConfig"]["maxOutputTokens"], 256);
+
+        // The configured temperature wins over the per-call default
+        let appraisal = client.build_request_body("hi", Some(0.0), "test-model").unwrap();
+        assert_eq!(appraisal["generationConfig"]["temperature"], 0.5);
+        let defaults = client_with_config(LlmApiConfig::default());
+        assert_eq!(defaults.build_request_body("hi", Some(0.0), "test-model").unwrap()["generationConfig"]["temperature"], 0.0);
+
+        let unset = client_with_config(LlmApiConfig {
+            appraisal_temperature: None,
+            reflection_temperature: None,
+            ..LlmApiConfig::default()
+        });
+        assert!(unset.build_request_body("hi", None, "test-model").unwrap().get("generationConfig").is_none());
+    }
+
+    #[test]
+    fn test_reflection_depth_limits_milestones() {
+        let mut memory = Memory::new();
+        for i in 0..20 {
+            memory.record_milestone(format!("Milestone number {} with some emotional detail", i));
+        }
+        let client = client_with_config(LlmApiConfig::default());
+
+        let recent = reflection_memory_context(&memory, ReflectionDepth::Recent(2)).unwrap();
+        let prompt = client.build_reflection_prompt(&recent);
+        assert!(prompt.contains("Milestone number 19"));
+        assert!(prompt.contains("Milestone number 18"));
+        assert!(!prompt.contains("Milestone number 17"));
+        assert!(prompt.len() < 2500, "prompt is {} bytes", prompt.len());
+
+        let full = reflection_memory_context(&memory, ReflectionDepth::Full).unwrap();
+        assert!(full.contains("Milestone number 0"));
+
+        let summary = reflection_memory_context(&memory, ReflectionDepth::SummaryOnly).unwrap();
+        assert!(summary.contains("\"emotional_milestone_count\": 20"));
+        assert!(!summary.contains("Milestone number"));
+    }
+
+    #[test]
+    fn test_stream_assembler_stops_at_complete_object() {
+        let client = client_with_config(LlmApiConfig {
+            provider: Some(LlmProvider::OpenAI),
+            ..LlmApiConfig::default()
+        });
+        let event = |text: &str| format!("data: {}\n\n", serde_json::json!({ "choices": [{ "delta": { "content": text } }] }));
+        let body = [
+            event("{\"emotion\": \"Joy\", \"vadn\": {\"valence\": 0.8, "),
+            event("\"arousal\": 0.6, \"dominance\": 0.4, \"novelty\": 0.2}, "),
+            event("\"details\": {\"note\": \"a } in a string\"}}"),
+            event("trailing chatter"),
+            "data: [DONE]\n\n".to_string(),
+        ]
+        .concat();
+
+        // Split mid-line to exercise buffering across chunks
+        let (first, second) = body.as_bytes().split_at(body.len() / 3);
+        let mut assembler = StreamAssembler::default();
+        let mut chunks = assembler.push(first, |e| client.extract_stream_delta(e));
+        assert!(!assembler.complete);
+        chunks.extend(assembler.push(second, |e| client.extract_stream_delta(e)));
+        assert!(assembler.complete);
+
+        let text: String = chunks.into_iter().map(Result::unwrap).collect();
+        assert!(!text.contains("trailing chatter"));
+        let emotion = client.parse_appraisal_text(&text).unwrap();
+        assert_eq!(emotion.emotion, "Joy");
+
+        assert!(!json_value_complete(r#"[{"emotion": "Joy"}, "#));
+        assert!(json_value_complete(r#"[{"emotion": "Joy"}, {"emotion": "Worry"}]"#));
+    }
+
+    #[test]
+    fn test_model_selection() {
+        let default = client_with_config(LlmApiConfig::default());
+        assert_eq!(default.model(), "gemini-1.5-flash");
+        assert_eq!(default.reflection_model(), "gemini-1.5-flash");
+
+        let split = client_with_config(LlmApiConfig {
+            reflection_model: Some("gemini-1.5-pro".to_string()),
+            ..LlmApiConfig::default()
+        });
+        assert_eq!(split.model(), "gemini-1.5-flash");
+        assert_eq!(split.reflection_model(), "gemini-1.5-pro");
+
+        let openai = client_with_config(LlmApiConfig {
+            provider: Some(LlmProvider::OpenAI),
+            model: Some("gpt-4o".to_string()),
+            ..LlmApiConfig::default()
+        });
+        let body = openai.build_request_body("hi", None, openai.reflection_model()).unwrap();
+        assert_eq!(body["model"], "gpt-4o");
+    }
+
     #[test]
     fn test_json_cleaning() {
         if let Ok(client) = LlmApiClient::new(None) {
@@ -476,4 +1699,232 @@ mod tests {
             assert_eq!(cleaned, r#"{"test": "value"}"#);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_mixed_sentiment_facets() {
+        let response = r#"```json
+[
+    {"emotion": "Pride", "vadn": {"valence": 0.7, "arousal": 0.6, "dominance": 0.5, "novelty": 0.3}, "details": {"referent": "promotion"}},
+    {"emotion": "Worry", "vadn": {"valence": -0.4, "arousal": 0.5, "dominance": -0.3, "novelty": 0.2}, "details": {"referent": "old team"}},
+    {"emotion": "Nostalgia", "vadn": {"valence": 0.1, "arousal": 0.2, "dominance": 0.0, "novelty": -0.2}, "details": {}}
+]
+```"#;
+
+        let emotions = parse_appraised_emotions(response, 2).unwrap();
+        assert_eq!(emotions.len(), 2);
+        assert_eq!(emotions[0].emotion, "Pride");
+        assert_eq!(emotions[1].emotion, "Worry");
+
+        // A lone object is treated as a single facet
+        let single = r#"{"emotion": "Joy", "vadn": {"valence": 0.8, "arousal": 0.6, "dominance": 0.4, "novelty": 0.1}, "details": {}}"#;
+        assert_eq!(parse_appraised_emotions(single, 3).unwrap().len(), 1);
+        assert!(parse_appraised_emotions("[]", 3).is_err());
+    }
+
+    #[test]
+    fn test_backoff_schedule_grows_with_jitter_and_cap() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let schedule: Vec<Duration> = (1..=6).map(|attempt| backoff_delay(1000, attempt, 20_000, &mut rng)).collect();
+        for (i, delay) in schedule.iter().enumerate() {
+            let full = (1000u64 << i).min(20_000);
+            let ms = delay.as_millis() as u64;
+            assert!(ms <= full, "attempt {}: {}ms > {}ms", i + 1, ms, full);
+            if 1000u64 << i <= 20_000 {
+                assert!(ms >= full / 2, "attempt {}: {}ms < {}ms", i + 1, ms, full / 2);
+            }
+        }
+        assert!(schedule[3] > schedule[0]);
+        assert!(schedule[5] <= Duration::from_millis(20_000));
+
+        // Same seed, same schedule
+        let mut again = StdRng::seed_from_u64(7);
+        let replay: Vec<Duration> = (1..=6).map(|attempt| backoff_delay(1000, attempt, 20_000, &mut again)).collect();
+        assert_eq!(schedule, replay);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = chrono::DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT").unwrap().with_timezone(&chrono::Utc);
+        assert_eq!(parse_retry_after("120", now), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 0 ", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:45 GMT", now), Some(Duration::from_secs(45)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon", now), None);
+        assert_eq!(parse_retry_after("-5", now), None);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_wait_honors_retry_after() {
+        let client = LlmApiClient::new(Some(LlmApiConfig {
+            provider: Some(LlmProvider::Ollama { base_url: "http://localhost:11434".to_string(), model: "llama3".to_string() }),
+            max_retry_delay_ms: 1000,
+            ..LlmApiConfig::default()
+        }))
+        .unwrap();
+
+        let started = std::time::Instant::now();
+        client.wait_for_rate_limit(Some(Duration::from_millis(20)), 1).await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(20));
+
+        // Asked to wait longer than we're willing to: give up instead of sleeping
+        let too_long = client.wait_for_rate_limit(Some(Duration::from_secs(60)), 1).await;
+        assert!(matches!(too_long, Err(LlmApiError::RateLimitExceeded { retry_after: Some(wait) }) if wait == Duration::from_secs(60)));
+    }
+
+    /// Serve one canned HTTP response per connection on a local port; returns its base URL
+    fn serve_responses(responses: Vec<String>) -> String {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                }
+                reader.read_exact(&mut vec![0; content_length]).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        base_url
+    }
+
+    #[tokio::test]
+    async fn test_streaming_appraisal_reports_partial_text() {
+        let deltas = [
+            r#"[{"emotion": "Joy", "vadn": "#,
+            r#"{"valence": 0.8, "arousal": 0.6, "dominance": 0.4, "novelty": 0.2}, "details": {}}, "#,
+            r#"{"emotion": "Worry", "vadn": {"valence": -0.4, "arousal": 0.5, "dominance": 0.3, "novelty": 0.1}}]"#,
+        ];
+        let mut body: String = deltas.iter()
+            .map(|delta| serde_json::json!({ "response": delta }).to_string() + "\n")
+            .collect();
+        body += &(serde_json::json!({ "response": "", "done": true, "prompt_eval_count": 40, "eval_count": 12 }).to_string() + "\n");
+        let base_url = serve_responses(vec![format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/x-ndjson\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(), body
+        )]);
+        let client = LlmApiClient::new(Some(LlmApiConfig {
+            provider: Some(LlmProvider::Ollama { base_url, model: "llama3".to_string() }),
+            ..LlmApiConfig::default()
+        }))
+        .unwrap();
+
+        let mut partials = Vec::new();
+        let mut on_partial = |text: &str| partials.push(text.to_string());
+        let facets = client.appraise_streaming("Great news!", &Memory::new(), &mut on_partial).await.unwrap();
+
+        assert_eq!(partials, deltas);
+        assert_eq!(facets.len(), 2);
+        assert_eq!(facets[0].emotion, "Joy");
+        assert_eq!(facets[0].vadn.valence, 0.8);
+        assert_eq!(facets[1].emotion, "Worry");
+
+        let usage = client.get_usage_stats();
+        assert_eq!((usage.prompt_tokens, usage.completion_tokens, usage.calls), (40, 12, 1));
+
+        // The server only answers once, so the repeat must come from the cache
+        let mut on_partial = |_: &str| {};
+        let cached = client.appraise_streaming("Great news!", &Memory::new(), &mut on_partial).await.unwrap();
+        assert_eq!(cached.len(), 2);
+        assert_eq!(client.get_usage_stats().calls, 1);
+    }
+
+    #[tokio::test]
+    async fn test_reflection_retries_after_rate_limit() {
+        let personality = Personality::default();
+        let body = serde_json::json!({ "response": serde_json::to_string(&personality).unwrap() }).to_string();
+        let base_url = serve_responses(vec![
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+            format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body),
+        ]);
+        let client = LlmApiClient::new(Some(LlmApiConfig {
+            provider: Some(LlmProvider::Ollama { base_url, model: "llama3".to_string() }),
+            max_retries: 2,
+            ..LlmApiConfig::default()
+        }))
+        .unwrap();
+
+        let reflected = client.call_for_reflection(&Memory::new()).await.unwrap();
+        assert_eq!(serde_json::to_value(reflected).unwrap(), serde_json::to_value(personality).unwrap());
+    }
+
+    #[test]
+    fn test_out_of_range_vadn_is_clamped() {
+        let client = LlmApiClient::new(Some(LlmApiConfig {
+            provider: Some(LlmProvider::Ollama { base_url: "http://localhost:11434".to_string(), model: "llama3".to_string() }),
+            ..LlmApiConfig::default()
+        }))
+        .unwrap();
+
+        let wild = r#"{"emotion": "Ecstasy", "vadn": {"valence": 5.0, "arousal": -2.0, "dominance": -1.5, "novelty": 0.4}}"#;
+        let emotion = client.parse_appraisal_text(wild).unwrap();
+        assert_eq!(emotion.vadn.valence, 1.0);
+        assert_eq!(emotion.vadn.arousal, 0.0);
+        assert_eq!(emotion.vadn.dominance, -1.0);
+        assert_eq!(emotion.vadn.novelty, 0.4);
+
+        let facets = parse_appraised_emotions(&format!("[{}]", wild), 3).unwrap();
+        assert_eq!(facets[0].vadn.valence, 1.0);
+        assert_eq!(facets[0].vadn.arousal, 0.0);
+    }
+
+    #[test]
+    fn test_malformed_vadn_is_an_invalid_mapping() {
+        let client = LlmApiClient::new(Some(LlmApiConfig {
+            provider: Some(LlmProvider::Ollama { base_url: "http://localhost:11434".to_string(), model: "llama3".to_string() }),
+            ..LlmApiConfig::default()
+        }))
+        .unwrap();
+
+        for text in [
+            r#"{"emotion": "Joy", "details": {}}"#,
+            r#"{"emotion": "Joy", "vadn": {"valence": "very", "arousal": 0.5, "dominance": 0.1, "novelty": 0.0}}"#,
+            r#"{"emotion": "Joy", "vadn": {"valence": 0.5}}"#,
+        ] {
+            assert!(matches!(client.parse_appraisal_text(text), Err(LlmApiError::InvalidEmotionMapping { .. })), "{}", text);
+            assert!(matches!(parse_appraised_emotions(text, 3), Err(LlmApiError::InvalidEmotionMapping { .. })), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_user_messages_are_actionable() {
+        let limited = LlmApiError::RateLimitExceeded { retry_after: Some(Duration::from_secs(12)) };
+        assert!(limited.user_message().contains("Try again in 12s"));
+        assert!(LlmApiError::RateLimitExceeded { retry_after: None }.user_message().contains("Try again in a moment"));
+        assert!(LlmApiError::ApiKeyMissing.user_message().contains("API key"));
+        assert!(LlmApiError::HttpError { status: 401, message: "nope".to_string() }.user_message().contains("rejected the API key"));
+        assert!(LlmApiError::HttpError { status: 503, message: String::new() }.user_message().contains("HTTP 503"));
+        assert!(LlmApiError::HttpError { status: 400, message: "bad model".to_string() }.user_message().contains("bad model"));
+        assert!(LlmApiError::Timeout { seconds: 30 }.user_message().contains("30s"));
+        assert!(LlmApiError::EmptyResponse.user_message().contains("couldn't be understood"));
+    }
+
+    #[tokio::test]
+    async fn test_offline_client_appraises_locally() {
+        let mut memory = Memory::new();
+        memory.personality.baseline_state.valence = 0.4;
+
+        let facets = OfflineLlmClient::default().appraise("I'm so happy, thank you!", &memory).await.unwrap();
+        assert_eq!(facets.len(), 1);
+        assert_eq!(facets[0].details["source"], "local_lexicon");
+        assert!(facets[0].vadn.valence > 0.2);
+
+        let personality = OfflineLlmClient::default().reflect(&memory).await.unwrap();
+        assert_eq!(personality.baseline_state.valence, 0.4);
+    }
+}
@@ -0,0 +1,85 @@
+//! lock_util.rs
+//!
+//! Bounded lock acquisition for the subsystem mutexes. A plain `try_lock` drops work whenever
+//! a background loop happens to hold the lock; blocking with `lock` risks stalling the runtime.
+
+use std::sync::{Mutex, MutexGuard, TryLockError};
+use tokio::time::{sleep, Duration, Instant};
+use tracing::{debug, warn};
+
+/// How long `lock_with_retry` keeps trying before giving up
+pub const LOCK_RETRY_BUDGET: Duration = Duration::from_millis(50);
+
+/// Try to lock `mutex`, backing off and retrying for up to `LOCK_RETRY_BUDGET`. Returns `None`
+/// if the lock stays contended or is poisoned. `what` names the lock in log messages.
+pub async fn lock_with_retry<'a, T>(mutex: &'a Mutex<T>, what: &str) -> Option<MutexGuard<'a, T>> {
+    let start = Instant::now();
+    let mut backoff = Duration::from_millis(1);
+    let mut attempts = 0u32;
+
+    loop {
+        // Resolve the attempt in its own statement so no guard is held across the sleep
+        match mutex.try_lock() {
+            Ok(guard) => {
+                if attempts > 0 {
+                    debug!("Acquired {} lock after {} retries ({:?})", what, attempts, start.elapsed());
+                }
+                return Some(guard);
+            }
+            Err(TryLockError::Poisoned(_)) => {
+                warn!("{} lock is poisoned", what);
+                return None;
+            }
+            Err(TryLockError::WouldBlock) => {}
+        };
+
+        if start.elapsed() >= LOCK_RETRY_BUDGET {
+            debug!("Gave up on {} lock after {} retries", what, attempts);
+            return None;
+        }
+        attempts += 1;
+        sleep(backoff).await;
+        backoff = (backoff * 2).min(Duration::from_millis(10));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{mpsc, Arc};
+    use std::thread;
+
+    /// Hold `mutex` on another thread for `hold`, returning once it is locked
+    fn hold_lock(mutex: &Arc<Mutex<i32>>, hold: Duration) -> thread::JoinHandle<()> {
+        let (locked_tx, locked_rx) = mpsc::channel();
+        let mutex = Arc::clone(mutex);
+        let holder = thread::spawn(move || {
+            let _guard = mutex.lock().unwrap();
+            locked_tx.send(()).unwrap();
+            thread::sleep(hold);
+        });
+        locked_rx.recv().unwrap();
+        holder
+    }
+
+    #[tokio::test]
+    async fn test_waits_for_briefly_held_lock() {
+        let mutex = Arc::new(Mutex::new(0));
+        let holder = hold_lock(&mutex, Duration::from_millis(5));
+
+        *lock_with_retry(&mutex, "counter").await.expect("lock should be released in time") += 1;
+        holder.join().unwrap();
+        assert_eq!(*mutex.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_on_held_lock() {
+        let mutex = Arc::new(Mutex::new(0));
+        let holder = hold_lock(&mutex, Duration::from_millis(200));
+        let start = Instant::now();
+
+        assert!(lock_with_retry(&mutex, "counter").await.is_none());
+        assert!(start.elapsed() >= LOCK_RETRY_BUDGET);
+        holder.join().unwrap();
+    }
+}
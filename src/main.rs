@@ -2,38 +2,81 @@
 //!
 //! Enhanced Sentient AI Simulation with comprehensive feature integration
 
-mod core;
-mod cognitive_appraisal;
-mod llm_api;
-mod memory;
-mod metacognition;
-mod goals;
-mod attention;
-mod continuous_mind;
-mod utils;
-
-use crate::core::AffectiveCore;
-use crate::cognitive_appraisal::appraise_emotion_from_prompt;
-use crate::continuous_mind::ContinuousMind;
-use crate::metacognition::CognitiveProcess;
-use crate::goals::GoalCategory;
-use crate::utils::{init_logging, check_environment, get_system_status, format_error_for_user};
-
+use cogno::{AffectiveCore, AffectiveState, AppraisedEmotion, CognitiveProcess, ContinuousMind, EmotionExpression, ExpressionStyle};
+use cogno::cognitive_appraisal::{is_offline_error, local_appraise_with};
+use cogno::config::{CognoConfig, DEFAULT_CONFIG_FILE};
+use cogno::llm_api::offline_from_env;
+use cogno::lock_util::lock_with_retry;
+use cogno::session::SessionRecorder;
+use cogno::utils::{init_logging, check_environment, get_system_status, format_error_for_user, LogFormat};
+
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::time::{sleep, Duration};
 use std::io::{self, Write};
-use tracing::{info, warn, error, debug};
+use tracing::{field, info, info_span, warn, error, debug, Instrument, Span};
 use anyhow::{Result, Context};
 
-/// Enhanced conversational turn with comprehensive system integration
+/// Enhanced conversational turn with comprehensive system integration. With `stream_appraisal`
+/// the appraisal is printed as it arrives. The prompt and its appraisals go to `recorder`, if
+/// any, as soon as they are applied.
 async fn run_conversational_turn(
     mind: Arc<ContinuousMind>,
     user_prompt: &str,
-    turn_number: u32
+    turn_number: u32,
+    stream_appraisal: bool,
+    recorder: Option<&mut SessionRecorder>,
 ) -> Result<()> {
-    info!("\n======================================================");
-    info!("Turn {}: User says: \"{}\"", turn_number, user_prompt);
+    // Every record logged during the turn carries these fields in the JSON log format
+    let span = info_span!(
+        "turn",
+        turn = turn_number,
+        valence = field::Empty,
+        arousal = field::Empty,
+        dominance = field::Empty,
+        novelty = field::Empty,
+        active_goals = field::Empty,
+    );
+
+    async {
+        record_turn_fields(&mind, &span);
+        info!("\n======================================================");
+        info!("Turn {}: User says: \"{}\"", turn_number, user_prompt);
 
+        // A background reflection in flight is interrupted rather than changing state mid-turn
+        mind.begin_user_turn();
+        let result = run_turn_steps(&mind, user_prompt, stream_appraisal, recorder).await;
+        mind.end_user_turn();
+
+        record_turn_fields(&mind, &span);
+        info!("Turn {} complete", turn_number);
+        result
+    }
+    .instrument(span.clone())
+    .await
+}
+
+/// Attach the current VADN state and active goal count to a turn's span
+fn record_turn_fields(mind: &ContinuousMind, span: &Span) {
+    if let Ok(core) = mind.get_affective_core().try_lock() {
+        let state = core.current_state();
+        span.record("valence", state.valence);
+        span.record("arousal", state.arousal);
+        span.record("dominance", state.dominance);
+        span.record("novelty", state.novelty);
+    }
+    if let Ok(goals) = mind.get_goal_system().try_lock() {
+        span.record("active_goals", goals.get_active_goals().len());
+    }
+}
+
+/// The steps of a conversational turn, run while the user turn is marked active
+async fn run_turn_steps(
+    mind: &Arc<ContinuousMind>,
+    user_prompt: &str,
+    stream_appraisal: bool,
+    recorder: Option<&mut SessionRecorder>,
+) -> Result<()> {
     let (affective_core, _goal_system, _attention_system, _metacognition) = (
         mind.get_affective_core(),
         mind.get_goal_system(),
@@ -43,29 +86,45 @@ async fn run_conversational_turn(
 
     // Update interaction count and learn from prompt
     {
-        if let Ok(mut core) = affective_core.try_lock() {
+        if let Some(mut core) = lock_with_retry(&affective_core, "affective core").await {
             core.memory.interaction_count += 1;
             core.memory.learn_from_prompt(user_prompt);
         }
     }
 
+    mind.record_interaction_turn().await;
+
     // ENHANCED: Comprehensive attention analysis
-    analyze_and_update_attention(&mind, user_prompt).await?;
+    analyze_and_update_attention(mind, user_prompt).await?;
 
     // ENHANCED: Process emotional content with detailed feedback
-    let emotion_result = process_emotions_comprehensively(&mind, user_prompt).await;
+    let emotion_result = process_emotions_comprehensively(mind, user_prompt, stream_appraisal).await;
+    if let (Some(recorder), Ok(facets)) = (recorder, &emotion_result) {
+        recorder.record(user_prompt, facets);
+    }
+    mind.record_turn_affect().await;
+    let current_state = lock_with_retry(&affective_core, "affective core").await.map(|core| core.current_state());
+    if let Some(state) = current_state {
+        let fired = match lock_with_retry(&mind.get_metacognition(), "metacognition").await {
+            Some(mut metacog) => metacog.observe_affective_state(&state),
+            None => Vec::new(),
+        };
+        mind.react_to_fired_triggers(&fired).await;
+    }
+    let arc = mind.conversation_arc().await;
+    info!("📈 Conversation arc: {:?} (peak turn: {:?})", arc.phase, arc.peak_turn);
 
     // ENHANCED: Goal management with progress tracking
-    manage_goals_comprehensively(&mind, user_prompt, emotion_result.is_ok()).await?;
+    manage_goals_comprehensively(mind, user_prompt, emotion_result.is_ok()).await?;
 
     // ENHANCED: Metacognitive analysis with pattern recognition
-    perform_metacognitive_analysis(&mind, user_prompt).await?;
+    perform_metacognitive_analysis(mind, user_prompt).await?;
 
     // Display comprehensive state with all system details
-    display_comprehensive_state(&mind).await?;
+    display_comprehensive_state(mind).await?;
 
     // ENHANCED: Generate response with full consciousness integration
-    generate_enhanced_conscious_response(&mind, user_prompt).await?;
+    generate_enhanced_conscious_response(mind, user_prompt).await?;
 
     info!("======================================================\n");
     Ok(())
@@ -73,10 +132,11 @@ async fn run_conversational_turn(
 
 /// Enhanced attention analysis using all attention system features
 async fn analyze_and_update_attention(mind: &Arc<ContinuousMind>, user_prompt: &str) -> Result<()> {
-    if let Ok(mut attention) = mind.get_attention_system().try_lock() {
-        // Analyze what should capture attention
-        let suggested_targets = attention.suggest_attention_targets(user_prompt);
-        info!("🎯 Suggested attention targets: {:?}", suggested_targets);
+    // Rank targets across goals, metacognition, drives and the prompt text
+    let suggested_targets = mind.compute_attention_priorities(user_prompt);
+    info!("🎯 Suggested attention targets: {:?}", suggested_targets);
+
+    if let Some(mut attention) = lock_with_retry(&mind.get_attention_system(), "attention").await {
 
         // Evaluate attention shifts
         attention.evaluate_attention_shift(suggested_targets);
@@ -111,34 +171,59 @@ async fn analyze_and_update_attention(mind: &Arc<ContinuousMind>, user_prompt: &
     Ok(())
 }
 
-/// Enhanced emotional processing with comprehensive error handling
+/// Enhanced emotional processing with comprehensive error handling. With `stream` the
+/// appraisal text is printed as it arrives. Returns the facets applied to the core.
 async fn process_emotions_comprehensively(
     mind: &Arc<ContinuousMind>,
-    user_prompt: &str
-) -> Result<()> {
+    user_prompt: &str,
+    stream: bool,
+) -> Result<Vec<AppraisedEmotion>> {
     let memory = {
-        match mind.get_affective_core().try_lock() {
-            Ok(core) => core.memory.clone(),
-            Err(_) => {
+        match lock_with_retry(&mind.get_affective_core(), "affective core").await {
+            Some(core) => core.memory.clone(),
+            None => {
                 warn!("Could not acquire core lock for emotion processing");
-                return Ok(());
+                return Ok(Vec::new());
             }
         }
     };
 
-    match appraise_emotion_from_prompt(user_prompt, &memory).await {
-        Ok(parsed_emotion) => {
-            info!("✅ LLM Appraised Emotion: {} (V:{:.2}, A:{:.2}, D:{:.2}, N:{:.2})",
-                  parsed_emotion.emotion,
-                  parsed_emotion.vadn.valence,
-                  parsed_emotion.vadn.arousal,
-                  parsed_emotion.vadn.dominance,
-                  parsed_emotion.vadn.novelty);
-
-            // Process emotion through affective core
-            if let Ok(mut core) = mind.get_affective_core().try_lock() {
+    let backend = mind.get_llm_backend();
+    let appraisal = if stream {
+        let mut show_partial = |text: &str| {
+            print!("{}", text);
+            let _ = io::stdout().flush();
+        };
+        print!("💭 ");
+        let streamed = backend.appraise_streaming(user_prompt, &memory, &mut show_partial).await;
+        println!();
+        streamed
+    } else {
+        backend.appraise(user_prompt, &memory).await
+    };
+    let appraisal = match appraisal {
+        Err(e) if is_offline_error(&e) => {
+            warn!("📴 LLM unavailable ({}), using local appraisal", e);
+            Ok(vec![local_appraise_with(user_prompt, mind.tokenizer())])
+        }
+        result => result,
+    };
+
+    match appraisal {
+        Ok(facets) => {
+            for facet in &facets {
+                info!("✅ LLM Appraised Emotion: {} (V:{:.2}, A:{:.2}, D:{:.2}, N:{:.2})",
+                      facet.describe(),
+                      facet.vadn.valence,
+                      facet.vadn.arousal,
+                      facet.vadn.dominance,
+                      facet.vadn.novelty);
+            }
+
+            // Process all facets through affective core
+            if let Some(mut core) = lock_with_retry(&mind.get_affective_core(), "affective core").await {
                 let old_state = core.current_state();
-                core.process_emotion(&parsed_emotion);
+                core.process_emotions(&facets);
                 let new_state = core.current_state();
 
                 info!("🔄 Emotional state change:");
@@ -149,32 +234,37 @@ async fn process_emotions_comprehensively(
             }
 
             // Record detailed emotional processing
-            if let Ok(mut metacog) = mind.get_metacognition().try_lock() {
+            if let Some(mut metacog) = lock_with_retry(&mind.get_metacognition(), "metacognition").await {
+                let impacts: Vec<String> = facets.iter()
+                    .map(|facet| format!("{} (V{:+.2}, A{:+.2}, D{:+.2}, N{:+.2})",
+                                         facet.describe(),
+                                         facet.vadn.valence,
+                                         facet.vadn.arousal,
+                                         facet.vadn.dominance,
+                                         facet.vadn.novelty))
+                    .collect();
                 metacog.record_process(CognitiveProcess::EmotionalProcessing {
                     trigger: user_prompt.to_string(),
-                    outcome: format!("Successfully processed {} with VADN impact: V{:+.2}, A{:+.2}, D{:+.2}, N{:+.2}",
-                                   parsed_emotion.emotion,
-                                   parsed_emotion.vadn.valence,
-                                   parsed_emotion.vadn.arousal,
-                                   parsed_emotion.vadn.dominance,
-                                   parsed_emotion.vadn.novelty)
+                    outcome: format!("Successfully processed {}", impacts.join(", "))
                 });
+                // This reaction is the outcome of the predictions made since the last turn
+                metacog.resolve_open_predictions(facets.iter().map(|facet| facet.vadn.valence).sum());
             }
-            Ok(())
+            Ok(facets)
         }
         Err(e) => {
             let formatted_error = format_error_for_user(&e);
             warn!("{}", formatted_error);
 
             // Record failed emotional processing
-            if let Ok(mut metacog) = mind.get_metacognition().try_lock() {
+            if let Some(mut metacog) = lock_with_retry(&mind.get_metacognition(), "metacognition").await {
                 metacog.record_process(CognitiveProcess::EmotionalProcessing {
                     trigger: user_prompt.to_string(),
                     outcome: format!("Failed to process emotion: {}", formatted_error)
                 });
             }
 
-            Err(anyhow::anyhow!("Emotional processing failed: {}", e))
+            Err(anyhow::Error::new(e).context("Emotional processing failed"))
         }
     }
 }
@@ -185,102 +275,64 @@ async fn manage_goals_comprehensively(
     user_prompt: &str,
     emotion_success: bool
 ) -> Result<()> {
-    if let Ok(mut goals) = mind.get_goal_system().try_lock() {
-        let current_state = {
-            match mind.get_affective_core().try_lock() {
-                Ok(core) => Some(core.current_state()),
-                Err(_) => None,
-            }
-        };
-
-        if let Some(state) = current_state {
-            // Analyze prompt for goal formation opportunities
-            let mut goals_formed = Vec::new();
-
-            if user_prompt.to_lowercase().contains("help") {
-                if let Some(goal_id) = goals.form_goal(
-                    format!("Help the user with: {}", user_prompt),
-                    GoalCategory::Altruistic,
-                    0.8,
-                    &state
-                ) {
-                    goals_formed.push(goal_id);
-                }
-            }
-
-            if user_prompt.to_lowercase().contains("learn") || user_prompt.to_lowercase().contains("understand") {
-                if let Some(goal_id) = goals.form_goal(
-                    "Deepen understanding of this topic".to_string(),
-                    GoalCategory::Epistemic,
-                    0.7,
-                    &state
-                ) {
-                    goals_formed.push(goal_id);
-                }
-            }
-
-            if user_prompt.to_lowercase().contains("create") || user_prompt.to_lowercase().contains("imagine") {
-                if let Some(goal_id) = goals.form_goal(
-                    "Engage in creative problem-solving".to_string(),
-                    GoalCategory::Creative,
-                    0.6,
-                    &state
-                ) {
-                    goals_formed.push(goal_id);
-                }
-            }
+    // Analyze prompt for goal formation opportunities
+    let goals_formed = mind.form_goals_from_prompt(user_prompt).await;
+    if !goals_formed.is_empty() {
+        debug!("Formed {} goal(s) from prompt triggers", goals_formed.len());
+    }
 
-            // Update progress on existing goals based on interaction success
-            let active_goal_ids: Vec<String> = {
-                let active_goals = goals.get_active_goals();
-                active_goals.iter().map(|g| g.id.clone()).collect()
-            };
+    if let Some(mut goals) = lock_with_retry(&mind.get_goal_system(), "goal system").await {
+        // Update progress on existing goals based on interaction success
+        let active_goal_ids: Vec<String> = {
+            let active_goals = goals.get_active_goals();
+            active_goals.iter().map(|g| g.id.clone()).collect()
+        };
 
-            for goal_id in active_goal_ids {
-                let progress_delta = if emotion_success { 0.1 } else { 0.05 };
-                goals.update_goal_progress(
-                    &goal_id,
-                    progress_delta,
-                    Some(format!("Interaction turn completed with user input: '{}'",
-                               user_prompt.chars().take(50).collect::<String>()))
-                );
-            }
+        for goal_id in active_goal_ids {
+            let progress_delta = if emotion_success { 0.1 } else { 0.05 };
+            goals.update_goal_progress(
+                &goal_id,
+                progress_delta,
+                Some(format!("Interaction turn completed with user input: '{}'",
+                           user_prompt.chars().take(50).collect::<String>()))
+            );
+        }
 
-            // Determine and update focus
-            if let Some(focus_id) = goals.determine_focus() {
-                if let Some(focused_goal) = goals.get_active_goals().iter().find(|g| g.id == focus_id) {
-                    info!("🎯 Current goal focus: {} (priority: {:.2}, progress: {:.1}%)",
-                          focused_goal.description,
-                          focused_goal.priority,
-                          focused_goal.progress * 100.0);
-                }
-            }
+        // Determine and update focus
+        if let Some(focus_id) = goals.determine_focus()
+            && let Some(focused_goal) = goals.get_active_goals().iter().find(|g| g.id == focus_id) {
+            info!("🎯 Current goal focus: {} (priority: {:.2}, progress: {:.1}%)",
+                  focused_goal.description,
+                  focused_goal.priority,
+                  focused_goal.progress * 100.0);
+        }
 
-            // Show comprehensive goal state
-            info!("📊 Goal System Summary: {}", goals.generate_summary());
+        // Show comprehensive goal state
+        info!("📊 Goal System Summary: {}", goals.generate_summary());
 
-            // Generate and log desired actions
-            let desired_actions = goals.generate_desired_actions();
-            if !desired_actions.is_empty() {
-                info!("🚀 Goal-driven desired actions:");
-                for action in desired_actions {
-                    info!("  - {}", action);
-                }
+        // Generate and log desired actions
+        let desired_actions = goals.generate_desired_actions();
+        if !desired_actions.is_empty() {
+            info!("🚀 Goal-driven desired actions:");
+            for action in desired_actions {
+                info!("  - {}", action);
             }
         }
     }
+    mind.apply_goal_rewards();
     Ok(())
 }
 
 /// Enhanced metacognitive analysis with comprehensive pattern recognition
 async fn perform_metacognitive_analysis(mind: &Arc<ContinuousMind>, user_prompt: &str) -> Result<()> {
-    if let Ok(mut metacog) = mind.get_metacognition().try_lock() {
+    let mut fired = Vec::new();
+    if let Some(mut metacog) = lock_with_retry(&mind.get_metacognition(), "metacognition").await {
         // Record the attention shift as a cognitive process
-        metacog.record_process(CognitiveProcess::AttentionShift {
+        fired.extend(metacog.record_process(CognitiveProcess::AttentionShift {
             from: "previous context".to_string(),
             to: format!("user input: {}", user_prompt.chars().take(30).collect::<String>()),
             reason: "new conversational turn initiated".to_string()
-        });
+        }));
 
         // Check if deep reflection is needed and get state info
         let should_reflect = metacog.should_deep_reflect();
@@ -289,10 +341,10 @@ async fn perform_metacognitive_analysis(mind: &Arc<ContinuousMind>, user_prompt:
         if should_reflect {
             info!("🤔 Metacognitive system suggests deep reflection is needed");
 
-            metacog.record_process(CognitiveProcess::SelfReflection {
+            fired.extend(metacog.record_process(CognitiveProcess::SelfReflection {
                 insight: "Recognized need for deeper self-analysis based on cognitive load and confidence levels".to_string(),
                 confidence: reasoning_confidence
-            });
+            }));
         }
 
         // Analyze and report cognitive patterns
@@ -316,6 +368,7 @@ async fn perform_metacognitive_analysis(mind: &Arc<ContinuousMind>, user_prompt:
         info!("  - Situation understanding: {:.1}%", metacog.state.situation_understanding * 100.0);
         info!("  - Attention intensity: {:.1}%", metacog.state.attention_intensity * 100.0);
     }
+    mind.react_to_fired_triggers(&fired).await;
     Ok(())
 }
 
@@ -334,6 +387,8 @@ async fn display_comprehensive_state(mind: &Arc<ContinuousMind>) -> Result<()> {
         info!("  - Arousal (energy): {:.2}", state.arousal);
         info!("  - Dominance (control): {:.2}", state.dominance);
         info!("  - Novelty (surprise): {:.2}", state.novelty);
+        let style = ExpressionStyle::for_personality(&core.memory.personality);
+        info!("  - Expression: {}", EmotionExpression::express_emotion(&state, style));
         info!("  - Memory: {} interactions, {} milestones",
               core.memory.interaction_count,
               core.memory.emotional_milestones.len());
@@ -356,7 +411,13 @@ async fn display_comprehensive_state(mind: &Arc<ContinuousMind>) -> Result<()> {
 
         if let Some(focused_goal) = goals.get_current_focus() {
             info!("  - Current focus: {}", focused_goal.description);
-            info!("    - Importance score: {:.2}", focused_goal.calculate_importance());
+            let importance = focused_goal.importance_breakdown();
+            let (dominant, value) = importance.dominant();
+            info!("    - Importance score: {:.2} (focus because {} {:.2} dominates)", importance.total, dominant, value);
+            let terms: Vec<String> = importance.terms().iter()
+                .map(|(name, value, weight, _)| format!("{} {:.2} x{:.1}", name, value, weight))
+                .collect();
+            info!("    - Importance terms: {}", terms.join(", "));
             info!("    - Strategies: {:?}", focused_goal.strategies);
         }
     }
@@ -446,7 +507,7 @@ async fn generate_enhanced_conscious_response(mind: &Arc<ContinuousMind>, user_p
     }
 
     // Generate metacognitive reflection on the response process
-    if let Ok(mut metacog) = mind.get_metacognition().try_lock() {
+    if let Some(mut metacog) = lock_with_retry(&mind.get_metacognition(), "metacognition").await {
         let confidence = metacog.state.reasoning_confidence;
         metacog.record_process(CognitiveProcess::PredictiveThinking {
             prediction: format!("Response to '{}' will integrate emotional state, attention focus, and current goals",
@@ -488,11 +549,10 @@ async fn demonstrate_spontaneous_behavior(mind: Arc<ContinuousMind>) -> Result<(
     // Demonstrate system integration by showing how different systems influence each other
     info!("\n🔗 System Integration Analysis:");
 
-    if let Ok(goals) = mind.get_goal_system().try_lock() {
-        if let Some(focus) = goals.get_current_focus() {
-            info!("  📍 Current goal focus is influencing attention and emotional priorities");
-            info!("  🎯 Goal: {} (importance: {:.2})", focus.description, focus.calculate_importance());
-        }
+    if let Ok(goals) = mind.get_goal_system().try_lock()
+        && let Some(focus) = goals.get_current_focus() {
+        info!("  📍 Current goal focus is influencing attention and emotional priorities");
+        info!("  🎯 Goal: {} (importance: {:.2})", focus.description, focus.calculate_importance());
     }
 
     if let Ok(attention) = mind.get_attention_system().try_lock() {
@@ -511,7 +571,7 @@ async fn demonstrate_spontaneous_behavior(mind: Arc<ContinuousMind>) -> Result<(
 }
 
 /// Enhanced interactive session with comprehensive feature showcase
-async fn interactive_session(mind: Arc<ContinuousMind>) -> Result<()> {
+async fn interactive_session(mind: Arc<ContinuousMind>, mut recorder: Option<&mut SessionRecorder>) -> Result<()> {
     info!("\n🗣️ === ENHANCED INTERACTIVE SESSION ===");
     info!("Available commands:");
     info!("  - Regular conversation");
@@ -520,6 +580,9 @@ async fn interactive_session(mind: Arc<ContinuousMind>) -> Result<()> {
     info!("  - 'attention' - Show attention state");
     info!("  - 'thoughts' - Show recent thoughts");
     info!("  - 'reflect' - Trigger self-reflection");
+    info!("  - 'pause' / 'resume' - Freeze or restart background processing");
+    info!("  - 'set empathy <v>' / 'set decay <v>' - Tune the affective core (0.0 to 1.0)");
+    info!("  - 'set baseline <v> <a> <d> <n>' - Change the personality baseline");
     info!("  - 'quit' - Exit");
 
     let mut turn_count: u32 = 1;
@@ -559,6 +622,14 @@ async fn interactive_session(mind: Arc<ContinuousMind>) -> Result<()> {
                     }
                     Ok(())
                 },
+                "pause" => {
+                    mind.pause();
+                    Ok(())
+                },
+                "resume" => {
+                    mind.resume();
+                    Ok(())
+                },
                 "thoughts" => {
                     let thoughts = mind.get_recent_thoughts(10).await;
                     info!("💭 Recent Thoughts:");
@@ -568,15 +639,63 @@ async fn interactive_session(mind: Arc<ContinuousMind>) -> Result<()> {
                     Ok(())
                 },
                 "reflect" => {
-                    if let Ok(mut core) = mind.get_affective_core().try_lock() {
+                    let affective_core = mind.get_affective_core();
+                    let memory = lock_with_retry(&affective_core, "affective core").await
+                        .map(|core| core.memory.clone());
+                    if let Some(memory) = memory {
                         info!("🧘‍♀️ Triggering self-reflection...");
-                        core.reflect().await;
-                        info!("Reflection completed successfully");
+                        match mind.get_llm_backend().reflect(&memory).await {
+                            Ok(new_personality) => match lock_with_retry(&affective_core, "affective core").await {
+                                Some(mut core) => {
+                                    core.adopt_personality(new_personality);
+                                    info!("Reflection completed successfully");
+                                }
+                                None => warn!("Could not access the affective core; reflection not applied"),
+                            },
+                            Err(e) => warn!("Reflection failed: {}", e),
+                        }
+                    } else {
+                        warn!("Could not access the affective core");
+                    }
+                    Ok(())
+                },
+                command if command.starts_with("set empathy ") || command.starts_with("set decay ") => {
+                    let (setting, value) = command["set ".len()..].split_once(' ').unwrap_or_default();
+                    match value.trim().parse::<f64>() {
+                        Ok(value) => {
+                            let applied = if setting == "empathy" {
+                                mind.set_empathy_factor(value).await
+                            } else {
+                                mind.set_decay_rate(value).await
+                            };
+                            match applied {
+                                Some(applied) => info!("⚙️ {} set to {:.2}", setting, applied),
+                                None => warn!("Could not access the affective core"),
+                            }
+                        }
+                        Err(_) => warn!("Usage: set {} <number between 0.0 and 1.0>", setting),
+                    }
+                    Ok(())
+                },
+                command if command.starts_with("set baseline ") => {
+                    let values: Vec<f64> = command["set baseline ".len()..]
+                        .split_whitespace()
+                        .filter_map(|value| value.parse().ok())
+                        .collect();
+                    match values[..] {
+                        [valence, arousal, dominance, novelty] => {
+                            match mind.set_baseline_state(AffectiveState { valence, arousal, dominance, novelty }).await {
+                                Some(b) => info!("⚙️ baseline set to V:{:.2}, A:{:.2}, D:{:.2}, N:{:.2}",
+                                                 b.valence, b.arousal, b.dominance, b.novelty),
+                                None => warn!("Could not access the affective core"),
+                            }
+                        }
+                        _ => warn!("Usage: set baseline <valence> <arousal> <dominance> <novelty>"),
                     }
                     Ok(())
                 },
                 _ => {
-                    let conv_result = run_conversational_turn(Arc::clone(&mind), input, turn_count).await;
+                    let conv_result = run_conversational_turn(Arc::clone(&mind), input, turn_count, true, recorder.as_deref_mut()).await;
                     match conv_result {
                         Ok(_) => {
                             turn_count += 1;
@@ -584,7 +703,7 @@ async fn interactive_session(mind: Arc<ContinuousMind>) -> Result<()> {
                         }
                         Err(e) => {
                             error!("Error during conversation turn: {:?}", e);
-                            println!("⚠️ {}", format_error_for_user(&e));
+                            println!("{}", format_error_for_user(e.as_ref()));
                             turn_count += 1; // Still increment even on error
                             Ok(())
                         }
@@ -594,7 +713,7 @@ async fn interactive_session(mind: Arc<ContinuousMind>) -> Result<()> {
 
             if let Err(e) = result {
                 error!("Error during interaction: {:?}", e);
-                println!("⚠️ {}", format_error_for_user(&e));
+                println!("{}", format_error_for_user(e.as_ref()));
             }
         }
     }
@@ -602,13 +721,85 @@ async fn interactive_session(mind: Arc<ContinuousMind>) -> Result<()> {
     Ok(())
 }
 
+/// Path given by `<flag> <path>` (or `<flag>=<path>`), if any
+fn path_arg(args: &[String], flag: &str) -> Option<PathBuf> {
+    arg_value(args, flag).map(PathBuf::from)
+}
+
+/// Value of `flag` given as `<flag> <value>` or `<flag>=<value>`
+fn arg_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter().enumerate().find_map(|(i, arg)| {
+        if arg == flag {
+            args.get(i + 1).map(String::as_str)
+        } else {
+            arg.strip_prefix(flag)?.strip_prefix('=')
+        }
+    })
+}
+
+/// Log format from `--log-format pretty|json`; pretty when absent or unrecognized
+fn log_format_arg(args: &[String]) -> LogFormat {
+    let Some(name) = arg_value(args, "--log-format") else {
+        return LogFormat::default();
+    };
+    LogFormat::from_name(name).unwrap_or_else(|| {
+        // Logging isn't up yet, so this goes straight to stderr
+        eprintln!("Unknown log format '{}' (expected pretty or json); using pretty", name);
+        LogFormat::default()
+    })
+}
+
+/// Settings from `--config <path>`, or `cogno.toml` if present; defaults otherwise
+fn load_config(config_file: Option<PathBuf>) -> CognoConfig {
+    let Some(path) = config_file.or_else(|| Some(PathBuf::from(DEFAULT_CONFIG_FILE)).filter(|path| path.exists())) else {
+        return CognoConfig::default();
+    };
+    match CognoConfig::load(&path) {
+        Ok(config) => {
+            info!("⚙️ Loaded configuration from {}", path.display());
+            config
+        }
+        Err(e) => {
+            warn!("Could not load config from {}: {}. Using defaults.", path.display(), e);
+            CognoConfig::default()
+        }
+    }
+}
+
+/// Resume the affective core from `state_file` if it exists, otherwise start fresh from the
+/// configured personality. The configured tuning applies either way.
+fn load_affective_core(state_file: Option<&Path>, config: &CognoConfig) -> AffectiveCore {
+    let fresh = || AffectiveCore::with_personality(config.personality, config.affective);
+    let Some(path) = state_file.filter(|path| path.exists()) else {
+        return fresh();
+    };
+    match AffectiveCore::load_from_path(path) {
+        Ok(mut core) => {
+            core.set_config(config.affective);
+            // Let the mind calm down for the time it was away
+            core.decay_elapsed(chrono::Utc::now());
+            info!("💾 Resumed affective state from {}", path.display());
+            core
+        }
+        Err(e) => {
+            warn!("Could not load state from {}: {}. Starting fresh.", path.display(), e);
+            fresh()
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    init_logging();
+    let args: Vec<String> = std::env::args().collect();
+    init_logging(log_format_arg(&args));
 
     info!("🚀 Starting Enhanced Sentient AI Simulation...");
 
-    match check_environment() {
+    let mut config = load_config(path_arg(&args, "--config"));
+    config.mind.offline |= offline_from_env();
+
+    match check_environment(config.mind.offline) {
+        Ok(_) if config.mind.offline => info!("🧠 Initializing consciousness systems offline..."),
         Ok(_) => info!("🧠 Initializing consciousness systems with full capabilities..."),
         Err(e) => {
             warn!("{}", e);
@@ -616,8 +807,21 @@ async fn main() -> Result<()> {
         }
     }
 
-    let affective_core = AffectiveCore::default();
-    let continuous_mind = match ContinuousMind::new(affective_core) {
+    // `--record-session <path>` saves every turn's prompt and appraisals for `session::replay`
+    let session_file = path_arg(&args, "--record-session");
+    let mut recorder = session_file.as_ref().map(|_| {
+        let seed = *config.mind.rng_seed.get_or_insert_with(rand::random);
+        SessionRecorder::new(seed)
+    });
+
+    let state_file = path_arg(&args, "--state-file");
+    let affective_core = load_affective_core(state_file.as_deref(), &config);
+    let continuous_mind = match ContinuousMind::builder()
+        .with_affective_core(affective_core)
+        .with_config(config.mind)
+        .with_llm_config(config.llm)
+        .build()
+    {
         Ok(mind) => mind,
         Err(e) => {
             error!("Failed to initialize consciousness systems: {:?}", e);
@@ -634,17 +838,14 @@ async fn main() -> Result<()> {
     info!("{}", get_system_status());
 
     // Start continuous background processing
-    let mind_for_background = Arc::clone(&mind);
-    tokio::spawn(async move {
-        ContinuousMind::start_continuous_processing(mind_for_background).await;
-    });
+    let background = tokio::spawn(ContinuousMind::start_continuous_processing(Arc::clone(&mind)));
 
     sleep(Duration::from_secs(2)).await;
 
     info!("\n🎭 === ENHANCED CONSCIOUSNESS DEVELOPMENT SIMULATION ===");
 
     // Enhanced conversation sequence that exercises all features
-    let conversations = vec![
+    let conversations = [
         "Hi there! My name is Alex. I've been thinking about consciousness and what it means to be truly aware. Do you ever reflect on your own thinking?",
         "That's fascinating! I'm working on understanding AI consciousness. It's challenging but exciting. Can you help me learn more about how awareness works?",
         "I've been having doubts about whether artificial consciousness is real or just simulation. What's your perspective on this?",
@@ -653,7 +854,7 @@ async fn main() -> Result<()> {
     ];
 
     for (i, conversation) in conversations.iter().enumerate() {
-        if let Err(e) = run_conversational_turn(Arc::clone(&mind), conversation, (i + 1) as u32).await {
+        if let Err(e) = run_conversational_turn(Arc::clone(&mind), conversation, (i + 1) as u32, false, recorder.as_mut()).await {
             warn!("Error in conversation turn {}: {:?}", i + 1, e);
         }
         sleep(Duration::from_secs(3)).await;
@@ -670,12 +871,135 @@ async fn main() -> Result<()> {
     io::stdin().read_line(&mut input).context("Failed to read user input")?;
 
     if input.trim().to_lowercase().starts_with('y') {
-        interactive_session(mind).await?;
+        interactive_session(Arc::clone(&mind), recorder.as_mut()).await?;
     }
 
-    info!("\n🌟 Enhanced Sentient AI simulation complete. All consciousness systems fully integrated.");
+    mind.shutdown();
+    if let Err(e) = background.await {
+        warn!("Background processing ended abnormally: {:?}", e);
+    }
 
-    sleep(Duration::from_secs(5)).await;
+    if let Some(path) = &state_file {
+        let affective_core = mind.get_affective_core();
+        let saved = match lock_with_retry(&affective_core, "affective core").await {
+            Some(core) => core.save_to_path(path),
+            None => Err(io::Error::other("affective core lock unavailable")),
+        };
+        match saved {
+            Ok(()) => info!("💾 Saved affective state to {}", path.display()),
+            Err(e) => warn!("Could not save state to {}: {}", path.display(), e),
+        }
+    }
+
+    if let (Some(path), Some(recorder)) = (&session_file, recorder) {
+        let saved = recorder.finish().to_json()
+            .map_err(io::Error::other)
+            .and_then(|json| std::fs::write(path, json));
+        match saved {
+            Ok(()) => info!("🎞️ Recorded session to {}", path.display()),
+            Err(e) => warn!("Could not record session to {}: {}", path.display(), e),
+        }
+    }
+
+    info!("\n🌟 Enhanced Sentient AI simulation complete. All consciousness systems fully integrated.");
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cogno::{AffectiveStateChange, AppraisedEmotion, GoalCategory, MockLlmClient, Personality};
+
+    /// Positive, energized state motivated enough to form goals of any category
+    fn eager_state() -> AffectiveState {
+        AffectiveState { valence: 0.6, arousal: 0.7, dominance: 0.3, novelty: 0.5 }
+    }
+
+    fn mind_with(mock: &Arc<MockLlmClient>) -> Arc<ContinuousMind> {
+        Arc::new(ContinuousMind::new(AffectiveCore::default(), Arc::clone(mock) as _))
+    }
+
+    #[test]
+    fn test_path_arg() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(path_arg(&args(&["cogno", "--state-file", "mind.json"]), "--state-file"), Some(PathBuf::from("mind.json")));
+        assert_eq!(path_arg(&args(&["cogno", "--state-file=mind.json"]), "--state-file"), Some(PathBuf::from("mind.json")));
+        assert_eq!(path_arg(&args(&["cogno", "--config=cogno.toml"]), "--state-file"), None);
+        assert_eq!(path_arg(&args(&["cogno", "--config", "a.toml", "--state-file", "b.json"]), "--config"), Some(PathBuf::from("a.toml")));
+        assert_eq!(path_arg(&args(&["cogno"]), "--state-file"), None);
+
+        assert_eq!(log_format_arg(&args(&["cogno", "--log-format", "json"])), LogFormat::Json);
+        assert_eq!(log_format_arg(&args(&["cogno", "--log-format=yaml"])), LogFormat::Pretty);
+        assert_eq!(log_format_arg(&args(&["cogno"])), LogFormat::Pretty);
+    }
+
+    #[tokio::test]
+    async fn test_turn_applies_mock_appraisal() {
+        let joy = AppraisedEmotion {
+            emotion: "Joy".to_string(),
+            vadn: AffectiveStateChange { valence: 0.9, arousal: 0.7, dominance: 0.5, novelty: 0.2 },
+            details: serde_json::json!({}),
+        };
+        let mock = Arc::new(MockLlmClient::new(vec![joy], Personality::default()));
+        let mind = mind_with(&mock);
+
+        run_conversational_turn(Arc::clone(&mind), "I finally finished my thesis!", 1, false, None).await.unwrap();
+
+        assert_eq!(mock.appraisal_count(), 1);
+        let core = mind.get_affective_core();
+        let core = core.try_lock().unwrap();
+        assert_eq!(core.memory.interaction_count, 1);
+        assert!(core.current_state().valence > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_next_turn_scores_the_response_prediction() {
+        let mind = mind_with(&Arc::new(MockLlmClient::default()));
+        let calibration = || mind.get_metacognition().try_lock().unwrap().get_calibration_score();
+
+        run_conversational_turn(Arc::clone(&mind), "I finally finished my thesis!", 1, false, None).await.unwrap();
+        assert_eq!(calibration(), None, "the response prediction awaits the user's reaction");
+
+        run_conversational_turn(Arc::clone(&mind), "Thanks, that helps a lot.", 2, false, None).await.unwrap();
+        assert!(calibration().is_some());
+    }
+
+    #[test]
+    fn test_completed_goal_rewards_affect() {
+        let mind = mind_with(&Arc::new(MockLlmClient::default()));
+        let eager = eager_state();
+        {
+            let goals = mind.get_goal_system();
+            let mut goals = goals.try_lock().unwrap();
+            let goal_id = goals.form_goal("Finish the thesis".to_string(), GoalCategory::Epistemic, 0.9, &eager).unwrap();
+            goals.update_goal_progress(&goal_id, 1.0, None);
+        }
+        let before = mind.get_affective_core().try_lock().unwrap().current_state();
+
+        // A contended core defers the reward rather than losing it
+        {
+            let core = mind.get_affective_core();
+            let _held = core.try_lock().unwrap();
+            assert_eq!(mind.apply_goal_rewards(), 0);
+        }
+        assert_eq!(mind.apply_goal_rewards(), 1);
+        assert_eq!(mind.apply_goal_rewards(), 0);
+        let after = mind.get_affective_core().try_lock().unwrap().current_state();
+        assert!(after.valence > before.valence);
+        assert!(after.dominance > before.dominance);
+    }
+
+    #[tokio::test]
+    async fn test_turn_survives_failed_appraisal() {
+        let mock = Arc::new(MockLlmClient::new(Vec::new(), Personality::default()));
+        let mind = mind_with(&mock);
+        let before = mind.get_affective_core().try_lock().unwrap().current_state();
+
+        run_conversational_turn(Arc::clone(&mind), "Hello", 1, false, None).await.unwrap();
+
+        assert_eq!(mock.appraisal_count(), 1);
+        let after = mind.get_affective_core().try_lock().unwrap().current_state();
+        assert_eq!(after.valence, before.valence);
+    }
+}
@@ -3,8 +3,14 @@
 //! Manages long-term memory, user profile, and the AI's own personality.
 
 use crate::core::AffectiveState; // Import AffectiveState
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::text_util::Tokenizer;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tracing::warn;
 
 /// **NEW**: Defines the core, long-term personality of the AI.
 /// This is the baseline that the AI will decay towards.
@@ -28,13 +34,134 @@ pub struct UserProfile {
     pub preferences: HashMap<String, String>,
 }
 
+/// Milestones kept at most; past this the least salient is forgotten
+const MAX_MILESTONES: usize = 20;
+/// Intensity of milestones recorded without one, and of those loaded from older saves
+const DEFAULT_MILESTONE_INTENSITY: f64 = 0.5;
+/// Retention time constant, in hours, of a zero-intensity milestone
+const BASE_MILESTONE_STABILITY_HOURS: f64 = 24.0;
+/// `consolidate` forgets milestones whose salience has decayed below this...
+const FORGET_SALIENCE: f64 = 0.1;
+/// ...unless they were at least this intense
+const PRESERVE_INTENSITY: f64 = 0.8;
+
+/// A significant emotional event, remembered along a forgetting curve
+#[derive(Debug, Clone, Serialize)]
+pub struct Milestone {
+    pub description: String,
+    /// How emotionally charged the event was (0.0 to 1.0)
+    pub intensity: f64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl Milestone {
+    pub fn new(description: String, intensity: f64) -> Self {
+        Milestone { description, intensity: intensity.clamp(0.0, 1.0), recorded_at: Utc::now() }
+    }
+
+    /// Ebbinghaus retention `e^(-t/S)` scaled by intensity, where more intense events have a
+    /// larger stability `S` and so fade more slowly
+    pub fn salience_at(&self, now: DateTime<Utc>) -> f64 {
+        let hours = (now - self.recorded_at).num_seconds().max(0) as f64 / 3600.0;
+        let stability = BASE_MILESTONE_STABILITY_HOURS * (1.0 + 4.0 * self.intensity);
+        self.intensity * (-hours / stability).exp()
+    }
+}
+
+/// Older saves stored milestones as plain strings
+impl<'de> Deserialize<'de> for Milestone {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Stored {
+            Full { description: String, intensity: f64, recorded_at: DateTime<Utc> },
+            Legacy(String),
+        }
+
+        Ok(match Stored::deserialize(deserializer)? {
+            Stored::Full { description, intensity, recorded_at } => Milestone { description, intensity, recorded_at },
+            Stored::Legacy(description) => Milestone::new(description, DEFAULT_MILESTONE_INTENSITY),
+        })
+    }
+}
+
 /// Represents the AI's memory, now including its own personality.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Memory {
     pub user_profile: UserProfile,
     pub interaction_count: u64,
-    pub emotional_milestones: Vec<String>,
+    pub emotional_milestones: Vec<Milestone>,
     pub personality: Personality, // ADD THIS
+    /// How often each topic word has come up, decayed by `decay_topics`
+    #[serde(default)]
+    pub topic_frequencies: HashMap<String, u32>,
+    /// JSONL file each milestone is appended to as it is recorded; not part of the saved memory
+    #[serde(skip)]
+    milestone_log: Option<PathBuf>,
+    /// Splits prompts into topic words; not part of the saved memory
+    #[serde(skip)]
+    tokenizer: Tokenizer,
+}
+
+/// Phrases that introduce a name, and whether the name must already be capitalized to count.
+/// "I'm"/"I am" are mostly followed by states ("I'm tired"), so they need the stricter check.
+const NAME_CUES: &[(&str, bool)] = &[
+    ("my name is", false),
+    ("call me", false),
+    ("i'm", true),
+    ("i am", true),
+];
+
+/// Topics tracked at most; the least frequent are forgotten first
+const MAX_TOPICS: usize = 50;
+
+/// Frequent conversational words that pass the stopword filter but say nothing about the topic
+const TOPIC_FILLER: &[&str] = &[
+    "think", "thinking", "know", "like", "want", "really", "been", "have", "make", "thing",
+    "things", "something", "anything", "also", "well", "yeah", "please", "thanks", "hello",
+    "there", "what's", "that's", "let's", "can't", "don't", "maybe", "actually", "today",
+];
+
+/// Words that follow a name cue but are not names
+const NOT_NAMES: &[&str] = &[
+    "a", "an", "the", "not", "so", "very", "really", "just", "also", "still", "here", "back",
+    "later", "now", "when", "if", "tomorrow", "sorry", "fine", "good", "okay", "ok", "tired",
+    "happy", "sad", "glad", "sure", "going", "trying", "working", "feeling", "curious",
+    "interested", "excited", "worried", "afraid", "new", "from", "in", "at", "on",
+];
+
+/// Finds a self-introduced name such as "my name is alex", "call me Sam" or "I'm Priya",
+/// returned with its first letter capitalized
+fn extract_name(prompt: &str) -> Option<String> {
+    // ASCII lowercasing keeps byte offsets aligned with the original prompt
+    let lower = prompt.to_ascii_lowercase();
+
+    for (cue, needs_capital) in NAME_CUES {
+        for (index, _) in lower.match_indices(cue) {
+            let at_word_start = lower[..index].chars().next_back().is_none_or(|c| !c.is_alphanumeric());
+            if !at_word_start {
+                continue;
+            }
+
+            let rest = &prompt[index + cue.len()..];
+            if !rest.starts_with(char::is_whitespace) {
+                continue;
+            }
+            let name: String = rest.trim_start()
+                .chars()
+                .take_while(|c| c.is_alphabetic() || *c == '-')
+                .collect();
+            let Some(first) = name.chars().next() else {
+                continue;
+            };
+            if (*needs_capital && !first.is_uppercase()) || NOT_NAMES.contains(&name.to_lowercase().as_str()) {
+                continue;
+            }
+
+            return Some(format!("{}{}", first.to_uppercase(), &name[first.len_utf8()..]));
+        }
+    }
+    None
 }
 
 impl Memory {
@@ -44,32 +171,118 @@ impl Memory {
             interaction_count: 0,
             emotional_milestones: Vec::new(),
             personality: Personality::default(), // AND THIS
+            topic_frequencies: HashMap::new(),
+            milestone_log: None,
+            tokenizer: Tokenizer::default(),
         }
     }
 
+    /// Writes the memory as JSON.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+    }
+
+    /// Reads a memory previously written by `save`.
+    pub fn load(path: &Path) -> io::Result<Memory> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Append every future milestone to `path`, one JSON object per line. Unlike
+    /// `emotional_milestones`, the log is never trimmed.
+    pub fn set_milestone_log(&mut self, path: impl Into<PathBuf>) {
+        self.milestone_log = Some(path.into());
+    }
+
+    /// Tokenizer used to pick topic words out of prompts
+    pub fn set_tokenizer(&mut self, tokenizer: Tokenizer) {
+        self.tokenizer = tokenizer;
+    }
+
+    fn append_to_milestone_log(path: &Path, milestone: &str) -> io::Result<()> {
+        let line = serde_json::json!({ "at": Utc::now(), "milestone": milestone });
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", line)?;
+        file.flush()
+    }
+
     /// A simple method to update the user's name if found in a prompt.
     pub fn learn_from_prompt(&mut self, prompt: &str) {
-        let lower_prompt = prompt.to_lowercase();
         if self.user_profile.name.is_none() { // Only learn if not already known
-            if let Some(index) = lower_prompt.find("my name is") {
-                let name_part = &prompt[index + "my name is".len()..];
-                if let Some(name) = name_part.trim().split([' ', ',', '.']).next() {
-                    if !name.is_empty() {
-                        let first_char = name.chars().next().unwrap().to_uppercase().to_string();
-                        self.user_profile.name = Some(format!("{}{}", first_char, &name[1..]));
-                    }
-                }
-            }
+            self.user_profile.name = extract_name(prompt);
+        }
+        self.count_topics(prompt);
+    }
+
+    /// Count each content word of the prompt once, forgetting the rarest topics over the cap
+    fn count_topics(&mut self, prompt: &str) {
+        let topics: HashSet<String> = self.tokenizer.tokenize(prompt).into_iter()
+            .filter(|word| word.chars().count() >= 4 && word.chars().all(char::is_alphabetic))
+            .filter(|word| !TOPIC_FILLER.contains(&word.as_str()))
+            .collect();
+        for topic in topics {
+            *self.topic_frequencies.entry(topic).or_insert(0) += 1;
+        }
+
+        if self.topic_frequencies.len() > MAX_TOPICS {
+            let keep: HashSet<String> = self.top_topics(MAX_TOPICS).into_iter().map(|(topic, _)| topic).collect();
+            self.topic_frequencies.retain(|topic, _| keep.contains(topic));
+        }
+    }
+
+    /// The `n` most frequent topics, most frequent first
+    pub fn top_topics(&self, n: usize) -> Vec<(String, u32)> {
+        let mut topics: Vec<_> = self.topic_frequencies.iter()
+            .map(|(topic, count)| (topic.clone(), *count))
+            .collect();
+        topics.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        topics.truncate(n);
+        topics
+    }
+
+    /// Halve every topic count so topics that stop coming up fade away
+    pub fn decay_topics(&mut self) {
+        for count in self.topic_frequencies.values_mut() {
+            *count /= 2;
         }
+        self.topic_frequencies.retain(|_, count| *count > 0);
     }
 
     /// Records a significant emotional event.
     pub fn record_milestone(&mut self, emotion_details: String) {
-        self.emotional_milestones.push(emotion_details);
-        // Keep the list from growing too large
-        if self.emotional_milestones.len() > 20 {
-            self.emotional_milestones.remove(0);
+        self.record_milestone_with_intensity(emotion_details, DEFAULT_MILESTONE_INTENSITY);
+    }
+
+    /// Records a significant emotional event of the given intensity (0.0 to 1.0).
+    pub fn record_milestone_with_intensity(&mut self, emotion_details: String, intensity: f64) {
+        if let Some(path) = &self.milestone_log
+            && let Err(e) = Self::append_to_milestone_log(path, &emotion_details) {
+            warn!("Could not append milestone to {}: {}", path.display(), e);
         }
+        self.emotional_milestones.push(Milestone::new(emotion_details, intensity));
+
+        // Keep the list from growing too large by forgetting the least salient milestone
+        if self.emotional_milestones.len() > MAX_MILESTONES {
+            let now = Utc::now();
+            let weakest = self.emotional_milestones.iter()
+                .enumerate()
+                .min_by(|a, b| a.1.salience_at(now).total_cmp(&b.1.salience_at(now)))
+                .map(|(index, _)| index);
+            if let Some(index) = weakest {
+                self.emotional_milestones.remove(index);
+            }
+        }
+    }
+
+    /// Forget milestones whose salience has decayed away, keeping intense ones regardless.
+    /// Returns how many were forgotten.
+    pub fn consolidate(&mut self, now: DateTime<Utc>) -> usize {
+        let before = self.emotional_milestones.len();
+        self.emotional_milestones.retain(|milestone| {
+            milestone.intensity >= PRESERVE_INTENSITY || milestone.salience_at(now) >= FORGET_SALIENCE
+        });
+        before - self.emotional_milestones.len()
     }
 }
 
@@ -77,4 +290,151 @@ impl Default for Memory {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_introduced_names() {
+        let cases = [
+            ("Hi there! My name is Alex. I've been thinking", "Alex"),
+            ("my name is jordan", "Jordan"),
+            ("You can call me Sam, everyone does", "Sam"),
+            ("Hey, I'm Priya and I love tide pools", "Priya"),
+            ("Hello, I am Mary-Jane.", "Mary-Jane"),
+        ];
+        for (prompt, name) in cases {
+            assert_eq!(extract_name(prompt).as_deref(), Some(name), "{}", prompt);
+        }
+    }
+
+    #[test]
+    fn test_ignores_non_names() {
+        for prompt in [
+            "I'm tired.",
+            "i'm going to the store",
+            "I am Not sure about this",
+            "Call me later when you can",
+            "I'm so happy today!",
+            "Him and I went out",
+            "What do you think?",
+        ] {
+            assert_eq!(extract_name(prompt), None, "{}", prompt);
+        }
+
+        let mut memory = Memory::new();
+        memory.learn_from_prompt("I'm Alex");
+        memory.learn_from_prompt("Actually, call me Sam");
+        assert_eq!(memory.user_profile.name.as_deref(), Some("Alex"));
+    }
+
+    #[test]
+    fn test_topic_frequencies_rank_and_decay() {
+        let mut memory = Memory::new();
+        memory.learn_from_prompt("I love tide pools and the ocean");
+        memory.learn_from_prompt("Tide pools have anemones");
+        memory.learn_from_prompt("Do you think pools at low tide are safe?");
+
+        let top = memory.top_topics(2);
+        assert_eq!(top, vec![("pools".to_string(), 3), ("tide".to_string(), 3)]);
+        assert!(!memory.topic_frequencies.contains_key("think"));
+
+        memory.decay_topics();
+        assert_eq!(memory.topic_frequencies.get("tide"), Some(&1));
+        assert!(!memory.topic_frequencies.contains_key("ocean"), "single mentions fade after one decay");
+    }
+
+    #[test]
+    fn test_topic_frequencies_are_capped() {
+        let mut memory = Memory::new();
+        memory.learn_from_prompt("astronomy astronomy");
+        memory.learn_from_prompt("astronomy");
+        for i in 0..MAX_TOPICS + 10 {
+            let word = format!("zeta{}{}", (b'a' + (i / 26) as u8) as char, (b'a' + (i % 26) as u8) as char);
+            memory.learn_from_prompt(&word);
+        }
+        assert!(memory.topic_frequencies.len() <= MAX_TOPICS);
+        assert_eq!(memory.top_topics(1)[0], ("astronomy".to_string(), 2));
+    }
+
+    #[test]
+    fn test_consolidate_forgets_faded_milestones() {
+        let now = Utc::now();
+        let milestone = |description: &str, intensity: f64, days_ago: i64| Milestone {
+            description: description.to_string(),
+            intensity,
+            recorded_at: now - chrono::Duration::days(days_ago),
+        };
+        let mut memory = Memory::new();
+        memory.emotional_milestones = vec![
+            milestone("Old mild chat", 0.3, 10),
+            milestone("Old grief", 0.95, 30),
+            milestone("Recent mild chat", 0.3, 0),
+            milestone("Fairly recent joy", 0.7, 3),
+        ];
+
+        assert_eq!(memory.consolidate(now), 1);
+        let kept: Vec<_> = memory.emotional_milestones.iter().map(|m| m.description.as_str()).collect();
+        assert_eq!(kept, vec!["Old grief", "Recent mild chat", "Fairly recent joy"]);
+        assert!(memory.emotional_milestones[2].salience_at(now) < 0.7);
+    }
+
+    #[test]
+    fn test_cap_evicts_least_salient_milestone() {
+        let mut memory = Memory::new();
+        memory.record_milestone_with_intensity("Life-changing news".to_string(), 1.0);
+        for i in 0..MAX_MILESTONES {
+            memory.record_milestone_with_intensity(format!("Small moment {}", i), 0.2);
+        }
+
+        assert_eq!(memory.emotional_milestones.len(), MAX_MILESTONES);
+        assert_eq!(memory.emotional_milestones[0].description, "Life-changing news");
+        assert_eq!(memory.emotional_milestones[1].description, "Small moment 1");
+    }
+
+    #[test]
+    fn test_loads_legacy_string_milestones() {
+        let json = r#"{"user_profile":{"name":null,"preferences":{}},"interaction_count":3,
+            "emotional_milestones":["Felt joy"],"personality":{"baseline_state":{"valence":0.0,"arousal":0.0,"dominance":0.0,"novelty":0.0}}}"#;
+        let memory: Memory = serde_json::from_str(json).unwrap();
+        assert_eq!(memory.emotional_milestones[0].description, "Felt joy");
+        assert_eq!(memory.emotional_milestones[0].intensity, DEFAULT_MILESTONE_INTENSITY);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let path = std::env::temp_dir().join(format!("cogno_memory_{}.json", std::process::id()));
+        let mut memory = Memory::new();
+        memory.learn_from_prompt("Hi, my name is alex.");
+        memory.interaction_count = 7;
+        memory.record_milestone("Felt joy".to_string());
+        memory.save(&path).unwrap();
+
+        let loaded = Memory::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.user_profile.name.as_deref(), Some("Alex"));
+        assert_eq!(loaded.interaction_count, 7);
+        assert_eq!(loaded.emotional_milestones.len(), 1);
+        assert_eq!(loaded.emotional_milestones[0].description, "Felt joy");
+    }
+
+    #[test]
+    fn test_milestones_append_to_log() {
+        let path = std::env::temp_dir().join(format!("cogno_milestones_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut memory = Memory::new();
+        memory.set_milestone_log(&path);
+        for i in 0..25 {
+            memory.record_milestone(format!("Milestone {}", i));
+        }
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<serde_json::Value> = log.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines.len(), 25, "the log keeps milestones trimmed from memory");
+        assert_eq!(lines[24]["milestone"], "Milestone 24");
+        assert_eq!(memory.emotional_milestones.len(), 20);
+    }
+}
@@ -5,6 +5,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use chrono::{DateTime, Utc};
+use crate::core::AffectiveState;
+use crate::invariants::{check_range, InvariantViolation};
 
 /// Represents different types of cognitive processes the AI can monitor
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +24,22 @@ pub enum CognitiveProcess {
 }
 
 impl CognitiveProcess {
+    /// Snake-case name of the variant, e.g. "value_conflict"; also the pattern-tracking key
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CognitiveProcess::EmotionalProcessing { .. } => "emotional_processing",
+            CognitiveProcess::SelfReflection { .. } => "self_reflection",
+            CognitiveProcess::ValueConflict { .. } => "value_conflict",
+            CognitiveProcess::ErrorRecovery { .. } => "error_recovery",
+            CognitiveProcess::CreativeThinking { .. } => "creative_thinking",
+            CognitiveProcess::AttentionShift { .. } => "attention_shift",
+            CognitiveProcess::GoalFormation { .. } => "goal_formation",
+            CognitiveProcess::MemoryRetrieval { .. } => "memory_retrieval",
+            CognitiveProcess::PredictiveThinking { .. } => "predictive_thinking",
+            CognitiveProcess::SocialInteraction { .. } => "social_interaction",
+        }
+    }
+
     /// Get the cognitive load impact of this process
     pub fn cognitive_load_impact(&self) -> f64 {
         match self {
@@ -94,10 +112,35 @@ impl Default for MetacognitiveState {
     }
 }
 
+/// The signal a reflection trigger compares against its threshold
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TriggerMetric {
+    /// Current cognitive load
+    CognitiveLoad,
+    /// One minus reasoning confidence
+    LowConfidence,
+    /// Fixed 0.6 whenever a value conflict is recorded
+    ValueConflict,
+    /// Current self-awareness level
+    SelfAwareness,
+    /// Fixed 0.6 when an error recovery is recorded while errors are frequent
+    ErrorPattern,
+    /// Originality of a recorded creative thought
+    CreativeOriginality,
+    /// Empathy level of a recorded social interaction
+    SocialEmpathy,
+    /// Negated mean valence of the last `samples` observed affective states, once that many exist
+    SustainedNegativeValence { samples: usize },
+}
+
+/// Triggers with a priority above this call for a deep reflection as soon as they fire
+pub const HIGH_PRIORITY_TRIGGER: f64 = 0.7;
+
 /// Enhanced reflection trigger system
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReflectionTrigger {
     pub name: String,
+    pub metric: TriggerMetric,
     pub threshold: f64,
     pub description: String,
     pub priority: f64,
@@ -106,9 +149,10 @@ pub struct ReflectionTrigger {
 }
 
 impl ReflectionTrigger {
-    pub fn new(name: &str, threshold: f64, description: &str, priority: f64, cooldown_minutes: u64) -> Self {
+    pub fn new(name: &str, metric: TriggerMetric, threshold: f64, description: &str, priority: f64, cooldown_minutes: u64) -> Self {
         ReflectionTrigger {
             name: name.to_string(),
+            metric,
             threshold,
             description: description.to_string(),
             priority,
@@ -117,6 +161,11 @@ impl ReflectionTrigger {
         }
     }
 
+    /// Whether firing this trigger warrants a deep reflection straight away
+    pub fn is_high_priority(&self) -> bool {
+        self.priority > HIGH_PRIORITY_TRIGGER
+    }
+
     /// Check if this trigger should fire given the current conditions
     pub fn should_trigger(&self, value: f64) -> bool {
         if value < self.threshold {
@@ -145,11 +194,7 @@ impl ReflectionTrigger {
         if let Some(last) = self.last_triggered {
             let now = Utc::now();
             let elapsed = now.signed_duration_since(last).num_minutes() as u64;
-            if elapsed < self.cooldown_minutes {
-                self.cooldown_minutes - elapsed
-            } else {
-                0
-            }
+            self.cooldown_minutes.saturating_sub(elapsed)
         } else {
             0
         }
@@ -157,7 +202,7 @@ impl ReflectionTrigger {
 }
 
 /// Enhanced pattern recognition for cognitive processes
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CognitivePattern {
     pub pattern_type: String,
     pub frequency: f64,
@@ -215,7 +260,7 @@ impl CognitivePattern {
 }
 
 /// Records and analyzes the AI's cognitive processes with enhanced reflection system
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetacognitiveMonitor {
     pub state: MetacognitiveState,
     cognitive_history: VecDeque<(DateTime<Utc>, CognitiveProcess)>,
@@ -224,8 +269,29 @@ pub struct MetacognitiveMonitor {
     cognitive_patterns: std::collections::HashMap<String, CognitivePattern>,
     reflection_queue: Vec<String>,
     metacognitive_insights: Vec<(DateTime<Utc>, String)>,
+    /// Recently observed valence, newest last, for affect-based triggers
+    #[serde(default)]
+    recent_valence: VecDeque<f64>,
+    /// Sum of squared prediction errors and number of scored predictions
+    #[serde(default)]
+    brier_sum: f64,
+    #[serde(default)]
+    predictions_scored: u32,
+    /// Confidence of `PredictiveThinking` records still awaiting an outcome, oldest first
+    #[serde(default)]
+    open_predictions: VecDeque<f64>,
 }
 
+/// How many observed valence samples are kept for affect-based triggers
+const VALENCE_HISTORY: usize = 50;
+
+/// How many unresolved predictions are kept; older ones are dropped unscored
+const MAX_OPEN_PREDICTIONS: usize = 10;
+
+/// Per-tick cognitive load decay factor with no meta-reasoning and with full meta-reasoning
+const LOAD_DECAY_WEAK: f64 = 0.95;
+const LOAD_DECAY_STRONG: f64 = 0.90;
+
 impl MetacognitiveMonitor {
     pub fn new() -> Self {
         let mut monitor = MetacognitiveMonitor {
@@ -236,6 +302,10 @@ impl MetacognitiveMonitor {
             cognitive_patterns: std::collections::HashMap::new(),
             reflection_queue: Vec::new(),
             metacognitive_insights: Vec::new(),
+            recent_valence: VecDeque::new(),
+            brier_sum: 0.0,
+            predictions_scored: 0,
+            open_predictions: VecDeque::new(),
         };
 
         // Initialize comprehensive reflection triggers
@@ -248,6 +318,7 @@ impl MetacognitiveMonitor {
         self.reflection_triggers = vec![
             ReflectionTrigger::new(
                 "high_cognitive_load",
+                TriggerMetric::CognitiveLoad,
                 0.8,
                 "Cognitive load exceeds 80% - need to assess efficiency",
                 0.9,
@@ -255,6 +326,7 @@ impl MetacognitiveMonitor {
             ),
             ReflectionTrigger::new(
                 "low_confidence",
+                TriggerMetric::LowConfidence,
                 0.3,
                 "Reasoning confidence below 30% - need to reassess understanding",
                 0.8,
@@ -262,6 +334,7 @@ impl MetacognitiveMonitor {
             ),
             ReflectionTrigger::new(
                 "value_conflict",
+                TriggerMetric::ValueConflict,
                 0.5,
                 "Value conflict detected - need ethical reflection",
                 1.0,
@@ -269,6 +342,7 @@ impl MetacognitiveMonitor {
             ),
             ReflectionTrigger::new(
                 "high_self_awareness",
+                TriggerMetric::SelfAwareness,
                 0.85,
                 "Self-awareness very high - opportunity for deep introspection",
                 0.7,
@@ -276,6 +350,7 @@ impl MetacognitiveMonitor {
             ),
             ReflectionTrigger::new(
                 "error_pattern",
+                TriggerMetric::ErrorPattern,
                 0.6,
                 "Error patterns detected - need process improvement",
                 0.85,
@@ -283,6 +358,7 @@ impl MetacognitiveMonitor {
             ),
             ReflectionTrigger::new(
                 "creative_breakthrough",
+                TriggerMetric::CreativeOriginality,
                 0.75,
                 "High creativity detected - consolidate insights",
                 0.6,
@@ -290,6 +366,7 @@ impl MetacognitiveMonitor {
             ),
             ReflectionTrigger::new(
                 "social_complexity",
+                TriggerMetric::SocialEmpathy,
                 0.7,
                 "Complex social interaction - reflect on empathy and understanding",
                 0.5,
@@ -298,13 +375,83 @@ impl MetacognitiveMonitor {
         ];
     }
 
-    /// Enhanced process recording with full pattern analysis
-    pub fn record_process(&mut self, process: CognitiveProcess) {
+    /// Score a prediction against its outcome and nudge reasoning confidence toward calibration:
+    /// confident misses lower it, unexpected successes raise it
+    pub fn record_prediction_outcome(&mut self, predicted_confidence: f64, was_correct: bool) {
+        let predicted = predicted_confidence.clamp(0.0, 1.0);
+        let outcome = if was_correct { 1.0 } else { 0.0 };
+        let error = outcome - predicted;
+
+        self.brier_sum += error * error;
+        self.predictions_scored += 1;
+        self.state.reasoning_confidence += 0.1 * error;
+        self.clamp_state_values();
+    }
+
+    /// Score every open prediction against the user's reaction, given as its net valence: a
+    /// non-negative reaction counts as the prediction holding. Returns how many were scored.
+    pub fn resolve_open_predictions(&mut self, reaction_valence: f64) -> usize {
+        let was_correct = reaction_valence >= 0.0;
+        let open: Vec<f64> = self.open_predictions.drain(..).collect();
+        for confidence in &open {
+            self.record_prediction_outcome(*confidence, was_correct);
+        }
+        open.len()
+    }
+
+    /// Mean Brier score of scored predictions (0.0 is perfectly calibrated, 1.0 always wrong),
+    /// or `None` before any outcome is recorded
+    pub fn get_calibration_score(&self) -> Option<f64> {
+        (self.predictions_scored > 0).then(|| self.brier_sum / self.predictions_scored as f64)
+    }
+
+    /// Snapshot the full monitor: state, history, patterns, triggers and insights
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Register a custom reflection trigger, replacing any existing trigger with the same name
+    pub fn add_trigger(&mut self, trigger: ReflectionTrigger) {
+        self.reflection_triggers.retain(|t| t.name != trigger.name);
+        self.reflection_triggers.push(trigger);
+    }
+
+    /// Remove the named trigger; returns whether it existed
+    pub fn remove_trigger(&mut self, name: &str) -> bool {
+        let before = self.reflection_triggers.len();
+        self.reflection_triggers.retain(|t| t.name != name);
+        self.reflection_triggers.len() != before
+    }
+
+    /// Record the current affective state and check the triggers that watch it. Returns the
+    /// triggers that fired, which callers are free to ignore.
+    pub fn observe_affective_state(&mut self, state: &AffectiveState) -> Vec<ReflectionTrigger> {
+        self.recent_valence.push_back(state.valence);
+        if self.recent_valence.len() > VALENCE_HISTORY {
+            self.recent_valence.pop_front();
+        }
+        self.fire_state_triggers(None)
+    }
+
+    /// Enhanced process recording with full pattern analysis. Returns the reflection triggers
+    /// the process fired so callers can act on them immediately; ignoring it is fine.
+    pub fn record_process(&mut self, process: CognitiveProcess) -> Vec<ReflectionTrigger> {
         let timestamp = Utc::now();
         
         // Update metacognitive state based on the process
         self.update_state_from_process(&process);
         
+        if let CognitiveProcess::PredictiveThinking { confidence, .. } = &process {
+            self.open_predictions.push_back(*confidence);
+            if self.open_predictions.len() > MAX_OPEN_PREDICTIONS {
+                self.open_predictions.pop_front();
+            }
+        }
+
         // Record in history
         self.cognitive_history.push_back((timestamp, process.clone()));
         
@@ -317,12 +464,13 @@ impl MetacognitiveMonitor {
         self.update_cognitive_patterns(&process);
         
         // Check reflection triggers
-        self.check_reflection_triggers(&process);
+        let fired = self.check_reflection_triggers(&process);
         
         // Update meta-reasoning
         self.update_meta_reasoning();
 
         self.log_metacognitive_state();
+        fired
     }
 
     /// Update state based on cognitive process
@@ -368,18 +516,7 @@ impl MetacognitiveMonitor {
 
     /// Update cognitive pattern tracking
     fn update_cognitive_patterns(&mut self, process: &CognitiveProcess) {
-        let pattern_key = match process {
-            CognitiveProcess::EmotionalProcessing { .. } => "emotional_processing",
-            CognitiveProcess::SelfReflection { .. } => "self_reflection",
-            CognitiveProcess::ValueConflict { .. } => "value_conflict",
-            CognitiveProcess::ErrorRecovery { .. } => "error_recovery",
-            CognitiveProcess::CreativeThinking { .. } => "creative_thinking",
-            CognitiveProcess::AttentionShift { .. } => "attention_shift",
-            CognitiveProcess::GoalFormation { .. } => "goal_formation",
-            CognitiveProcess::MemoryRetrieval { .. } => "memory_retrieval",
-            CognitiveProcess::PredictiveThinking { .. } => "predictive_thinking",
-            CognitiveProcess::SocialInteraction { .. } => "social_interaction",
-        };
+        let pattern_key = process.kind();
 
         let insight = match process {
             CognitiveProcess::SelfReflection { insight, .. } => Some(insight.clone()),
@@ -395,59 +532,53 @@ impl MetacognitiveMonitor {
     }
 
     /// Enhanced reflection trigger checking system
-    fn check_reflection_triggers(&mut self, process: &CognitiveProcess) {
+    fn check_reflection_triggers(&mut self, process: &CognitiveProcess) -> Vec<ReflectionTrigger> {
         // Check process-specific triggers
         if process.triggers_reflection() {
             self.queue_reflection(format!("Process-triggered reflection: {:?}", process));
         }
 
-        // Get current state values to avoid borrow conflicts
-        let cognitive_load = self.state.cognitive_load;
-        let reasoning_confidence = self.state.reasoning_confidence;
-        let self_awareness_level = self.state.self_awareness_level;
-        let error_frequency = self.get_error_frequency();
-
-        // Check state-based triggers
-        let mut triggers_to_fire = Vec::new();
-        
-        for (i, trigger) in self.reflection_triggers.iter().enumerate() {
-            let should_trigger = match trigger.name.as_str() {
-                "high_cognitive_load" => trigger.should_trigger(cognitive_load),
-                "low_confidence" => trigger.should_trigger(1.0 - reasoning_confidence),
-                "value_conflict" => {
-                    matches!(process, CognitiveProcess::ValueConflict { .. }) && trigger.should_trigger(0.6)
-                },
-                "high_self_awareness" => trigger.should_trigger(self_awareness_level),
-                "error_pattern" => {
-                    matches!(process, CognitiveProcess::ErrorRecovery { .. }) && 
-                    error_frequency > 0.6 && trigger.should_trigger(0.6)
-                },
-                "creative_breakthrough" => {
-                    if let CognitiveProcess::CreativeThinking { originality, .. } = process {
-                        trigger.should_trigger(*originality)
-                    } else {
-                        false
-                    }
-                },
-                "social_complexity" => {
-                    if let CognitiveProcess::SocialInteraction { empathy_level, .. } = process {
-                        trigger.should_trigger(*empathy_level)
-                    } else {
-                        false
-                    }
-                },
-                _ => false,
-            };
+        self.fire_state_triggers(Some(process))
+    }
 
-            if should_trigger {
-                triggers_to_fire.push((i, trigger.clone()));
+    /// Current value of a trigger metric, or `None` if it does not apply right now
+    /// (e.g. a process-specific metric when no matching process was recorded)
+    fn metric_value(&self, metric: TriggerMetric, process: Option<&CognitiveProcess>) -> Option<f64> {
+        match (metric, process) {
+            (TriggerMetric::CognitiveLoad, _) => Some(self.state.cognitive_load),
+            (TriggerMetric::LowConfidence, _) => Some(1.0 - self.state.reasoning_confidence),
+            (TriggerMetric::SelfAwareness, _) => Some(self.state.self_awareness_level),
+            (TriggerMetric::ValueConflict, Some(CognitiveProcess::ValueConflict { .. })) => Some(0.6),
+            (TriggerMetric::ErrorPattern, Some(CognitiveProcess::ErrorRecovery { .. }))
+                if self.get_error_frequency() > 0.6 => Some(0.6),
+            (TriggerMetric::CreativeOriginality, Some(CognitiveProcess::CreativeThinking { originality, .. })) => Some(*originality),
+            (TriggerMetric::SocialEmpathy, Some(CognitiveProcess::SocialInteraction { empathy_level, .. })) => Some(*empathy_level),
+            (TriggerMetric::SustainedNegativeValence { samples }, _)
+                if samples > 0 && self.recent_valence.len() >= samples => {
+                let mean = self.recent_valence.iter().rev().take(samples).sum::<f64>() / samples as f64;
+                Some(-mean)
             }
+            _ => None,
         }
+    }
+
+    /// Fire every trigger whose metric currently meets its threshold, returning those that fired
+    fn fire_state_triggers(&mut self, process: Option<&CognitiveProcess>) -> Vec<ReflectionTrigger> {
+        let triggers_to_fire: Vec<(usize, ReflectionTrigger)> = self.reflection_triggers.iter()
+            .enumerate()
+            .filter(|(_, trigger)| {
+                self.metric_value(trigger.metric, process)
+                    .is_some_and(|value| trigger.should_trigger(value))
+            })
+            .map(|(i, trigger)| (i, trigger.clone()))
+            .collect();
 
         // Now fire the triggers without borrowing conflicts
+        let mut fired = Vec::with_capacity(triggers_to_fire.len());
         for (i, mut trigger) in triggers_to_fire {
             trigger.trigger();
             self.reflection_triggers[i] = trigger.clone();
+            tracing::info!("🔔 Reflection trigger '{}' fired (priority {:.2})", trigger.name, trigger.priority);
             
             self.queue_reflection(format!("Trigger '{}': {}", trigger.name, trigger.description));
             
@@ -456,7 +587,9 @@ impl MetacognitiveMonitor {
                 Utc::now(),
                 format!("Reflection triggered by {}: {}", trigger.name, trigger.description)
             ));
+            fired.push(trigger);
         }
+        fired
     }
 
     /// Queue a reflection for later processing
@@ -588,7 +721,7 @@ impl MetacognitiveMonitor {
     pub fn should_deep_reflect(&self) -> bool {
         // Check if any reflection triggers are ready and waiting
         for trigger in &self.reflection_triggers {
-            if trigger.cooldown_remaining_minutes() == 0 && trigger.priority > 0.7 {
+            if trigger.cooldown_remaining_minutes() == 0 && trigger.is_high_priority() {
                 return true;
             }
         }
@@ -634,6 +767,11 @@ impl MetacognitiveMonitor {
         queue
     }
 
+    /// Occurrences of a kind of process (as named by `CognitiveProcess::kind`) in the last 24 hours
+    pub fn pattern_occurrences(&self, kind: &str) -> usize {
+        self.cognitive_patterns.get(kind).map_or(0, |pattern| pattern.recent_occurrences.len())
+    }
+
     /// Get comprehensive cognitive pattern summary
     pub fn get_pattern_summary(&self) -> Vec<String> {
         self.cognitive_patterns.values()
@@ -642,9 +780,10 @@ impl MetacognitiveMonitor {
             .collect()
     }
 
-    /// Simulate cognitive decay over time
+    /// Simulate cognitive decay over time. Stronger meta-reasoning sheds cognitive load faster.
     pub fn decay_over_time(&mut self) {
-        self.state.cognitive_load *= 0.95;
+        let strength = self.state.meta_reasoning_strength.clamp(0.0, 1.0);
+        self.state.cognitive_load *= LOAD_DECAY_WEAK + (LOAD_DECAY_STRONG - LOAD_DECAY_WEAK) * strength;
         self.state.attention_intensity *= 0.98;
         // Self-awareness and confidence decay more slowly
         self.state.self_awareness_level *= 0.99;
@@ -706,6 +845,55 @@ impl MetacognitiveMonitor {
                 awareness_desc, confidence_desc, load_desc, introspection_desc, meta_reasoning_desc)
     }
 
+    /// The `count` most recently recorded processes, newest first
+    pub fn recent_processes(&self, count: usize) -> Vec<&(DateTime<Utc>, CognitiveProcess)> {
+        self.cognitive_history.iter().rev().take(count).collect()
+    }
+
+    /// Every recorded process of the given kind (see `CognitiveProcess::kind`), oldest first
+    pub fn processes_of_type(&self, kind: &str) -> Vec<&(DateTime<Utc>, CognitiveProcess)> {
+        self.cognitive_history.iter()
+            .filter(|(_, process)| process.kind() == kind)
+            .collect()
+    }
+
+    /// Reflections waiting to be processed, oldest first
+    pub fn reflection_queue(&self) -> &[String] {
+        &self.reflection_queue
+    }
+
+    /// Insights recorded from triggers and processed reflections, oldest first
+    pub fn recent_insights(&self) -> &[(DateTime<Utc>, String)] {
+        &self.metacognitive_insights
+    }
+
+    /// Check the metacognitive state and reflection triggers for out-of-range values
+    pub fn check_invariants(&self) -> Vec<InvariantViolation> {
+        let mut violations = Vec::new();
+        let state = &self.state;
+
+        for (field, value) in [
+            ("self_awareness_level", state.self_awareness_level),
+            ("reasoning_confidence", state.reasoning_confidence),
+            ("cognitive_load", state.cognitive_load),
+            ("situation_understanding", state.situation_understanding),
+            ("attention_intensity", state.attention_intensity),
+            ("introspection_depth", state.introspection_depth),
+            ("meta_reasoning_strength", state.meta_reasoning_strength),
+        ] {
+            check_range(&mut violations, "metacognition", field, value, 0.0, 1.0);
+        }
+
+        for trigger in &self.reflection_triggers {
+            check_range(&mut violations, "metacognition", &format!("trigger '{}'.priority", trigger.name),
+                        trigger.priority, 0.0, 1.0);
+            check_range(&mut violations, "metacognition", &format!("trigger '{}'.threshold", trigger.name),
+                        trigger.threshold, 0.0, 1.0);
+        }
+
+        violations
+    }
+
     /// Get trigger status for all reflection triggers
     pub fn get_trigger_status(&self) -> Vec<String> {
         self.reflection_triggers.iter()
@@ -721,4 +909,130 @@ impl Default for MetacognitiveMonitor {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prediction_outcomes_calibrate_confidence() {
+        let mut monitor = MetacognitiveMonitor::new();
+        assert_eq!(monitor.get_calibration_score(), None);
+        let start = monitor.state.reasoning_confidence;
+
+        monitor.record_prediction_outcome(0.9, false);
+        assert!(monitor.state.reasoning_confidence < start);
+        let after_miss = monitor.state.reasoning_confidence;
+
+        monitor.record_prediction_outcome(0.2, true);
+        assert!(monitor.state.reasoning_confidence > after_miss);
+
+        // (0.9 - 0)^2 and (0.2 - 1)^2 averaged
+        let score = monitor.get_calibration_score().unwrap();
+        assert!((score - (0.81 + 0.64) / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_predictions_resolve_against_the_reaction() {
+        let mut monitor = MetacognitiveMonitor::new();
+        assert_eq!(monitor.resolve_open_predictions(0.5), 0);
+
+        monitor.record_process(CognitiveProcess::PredictiveThinking {
+            prediction: "The reply will land well".to_string(),
+            confidence: 0.9,
+        });
+        let start = monitor.state.reasoning_confidence;
+        assert_eq!(monitor.resolve_open_predictions(-0.4), 1);
+        assert!(monitor.state.reasoning_confidence < start);
+        assert!((monitor.get_calibration_score().unwrap() - 0.81).abs() < 1e-9);
+
+        // Each prediction is scored once
+        assert_eq!(monitor.resolve_open_predictions(0.5), 0);
+    }
+
+    #[test]
+    fn test_meta_reasoning_speeds_load_decay() {
+        let load_after_decay = |meta_reasoning: f64| {
+            let mut monitor = MetacognitiveMonitor::new();
+            monitor.state.cognitive_load = 0.8;
+            monitor.state.meta_reasoning_strength = meta_reasoning;
+            monitor.decay_over_time();
+            monitor.state.cognitive_load
+        };
+
+        assert!((load_after_decay(0.0) - 0.8 * 0.95).abs() < 1e-9);
+        assert!((load_after_decay(1.0) - 0.8 * 0.90).abs() < 1e-9);
+        assert!(load_after_decay(0.9) < load_after_decay(0.2));
+    }
+
+    #[test]
+    fn test_history_queries() {
+        let mut monitor = MetacognitiveMonitor::new();
+        for i in 0..3 {
+            monitor.record_process(CognitiveProcess::MemoryRetrieval { query: format!("q{}", i), success: true });
+        }
+        monitor.record_process(CognitiveProcess::ValueConflict {
+            conflict: "honesty vs kindness".to_string(),
+            resolution: "gentle honesty".to_string(),
+        });
+
+        let recent = monitor.recent_processes(2);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].1.kind(), "value_conflict");
+        assert!(matches!(&recent[1].1, CognitiveProcess::MemoryRetrieval { query, .. } if query == "q2"));
+
+        assert_eq!(monitor.processes_of_type("memory_retrieval").len(), 3);
+        assert_eq!(monitor.processes_of_type("value_conflict").len(), 1);
+        // Value conflicts always queue a reflection
+        assert!(monitor.reflection_queue().iter().any(|r| r.contains("ValueConflict")));
+    }
+
+    #[test]
+    fn test_monitor_json_round_trip() {
+        let mut monitor = MetacognitiveMonitor::new();
+        monitor.record_process(CognitiveProcess::CreativeThinking { concept: "tide pools".to_string(), originality: 0.9 });
+        monitor.record_process(CognitiveProcess::ValueConflict {
+            conflict: "honesty vs kindness".to_string(),
+            resolution: "gentle honesty".to_string(),
+        });
+
+        let restored = MetacognitiveMonitor::from_json(&monitor.to_json().unwrap()).unwrap();
+
+        assert_eq!(restored.cognitive_history.len(), 2);
+        assert_eq!(restored.get_pattern_summary().len(), monitor.get_pattern_summary().len());
+        assert_eq!(restored.get_trigger_status(), monitor.get_trigger_status());
+        assert_eq!(restored.recent_insights().len(), monitor.recent_insights().len());
+    }
+
+    #[test]
+    fn test_custom_trigger_fires_on_sustained_low_valence() {
+        let mut monitor = MetacognitiveMonitor::new();
+        monitor.add_trigger(ReflectionTrigger::new(
+            "prolonged_low_valence",
+            TriggerMetric::SustainedNegativeValence { samples: 3 },
+            0.4,
+            "Mood has stayed low - reflect on what is weighing on me",
+            0.8,
+            30,
+        ));
+        let low = AffectiveState { valence: -0.6, arousal: 0.3, dominance: -0.2, novelty: 0.0 };
+
+        let fired_custom = |fired: Vec<ReflectionTrigger>| fired.into_iter().find(|t| t.name == "prolonged_low_valence");
+
+        assert!(fired_custom(monitor.observe_affective_state(&low)).is_none());
+        assert!(fired_custom(monitor.observe_affective_state(&low)).is_none());
+        assert!(monitor.process_reflection_queue().iter().all(|r| !r.contains("prolonged_low_valence")));
+
+        let fired = fired_custom(monitor.observe_affective_state(&low)).expect("third low sample fires the trigger");
+        assert!(fired.is_high_priority());
+        let queued = monitor.process_reflection_queue();
+        assert!(queued.iter().any(|r| r.contains("prolonged_low_valence")));
+
+        // Cooldown stops it from firing again immediately; removal stops it for good
+        assert!(fired_custom(monitor.observe_affective_state(&low)).is_none());
+        assert!(monitor.process_reflection_queue().is_empty());
+        assert!(monitor.remove_trigger("prolonged_low_valence"));
+        assert!(!monitor.remove_trigger("prolonged_low_valence"));
+    }
+}
@@ -0,0 +1,156 @@
+//! session.rs
+//!
+//! Records conversational sessions (user prompts plus the appraisals the LLM returned) so they
+//! can be serialized and replayed deterministically for regression tests.
+
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use crate::cognitive_appraisal::AppraisedEmotion;
+use crate::continuous_mind::ContinuousMind;
+use crate::core::AffectiveCore;
+use crate::llm_api::MockLlmClient;
+use crate::lock_util::lock_with_retry;
+use crate::metacognition::CognitiveProcess;
+
+/// One user turn and the appraisal the LLM produced for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedTurn {
+    pub prompt: String,
+    pub appraisals: Vec<AppraisedEmotion>,
+}
+
+/// A complete recorded session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    /// RNG seed the session ran with
+    pub seed: u64,
+    pub turns: Vec<RecordedTurn>,
+}
+
+impl Session {
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Collects turns as they happen
+#[derive(Debug, Clone)]
+pub struct SessionRecorder {
+    session: Session,
+}
+
+impl SessionRecorder {
+    pub fn new(seed: u64) -> Self {
+        SessionRecorder {
+            session: Session { seed, turns: Vec::new() },
+        }
+    }
+
+    /// Record a prompt together with the appraisals returned for it
+    pub fn record(&mut self, prompt: &str, appraisals: &[AppraisedEmotion]) {
+        self.session.turns.push(RecordedTurn {
+            prompt: prompt.to_string(),
+            appraisals: appraisals.to_vec(),
+        });
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    pub fn finish(self) -> Session {
+        self.session
+    }
+}
+
+/// Apply one turn to the mind: count the interaction, learn from the prompt, absorb the
+/// appraisals and record the processing, as a live turn does after its appraisal returns
+pub async fn apply_turn(mind: &ContinuousMind, prompt: &str, appraisals: &[AppraisedEmotion]) {
+    if let Some(mut core) = lock_with_retry(&mind.get_affective_core(), "affective core").await {
+        core.memory.interaction_count += 1;
+        core.memory.learn_from_prompt(prompt);
+        core.process_emotions(appraisals);
+    }
+    mind.record_interaction_turn().await;
+
+    if let Some(mut metacog) = lock_with_retry(&mind.get_metacognition(), "metacognition").await {
+        let impacts: Vec<String> = appraisals.iter().map(AppraisedEmotion::describe).collect();
+        metacog.record_process(CognitiveProcess::EmotionalProcessing {
+            trigger: prompt.to_string(),
+            outcome: format!("Successfully processed {}", impacts.join(", ")),
+        });
+        metacog.resolve_open_predictions(appraisals.iter().map(|appraisal| appraisal.vadn.valence).sum());
+    }
+    mind.record_turn_affect().await;
+}
+
+/// Rebuild a mind from `affective_core` with the session's seed and drive it through every
+/// recorded turn. The recorded appraisals stand in for the LLM, so the mock is never asked.
+pub async fn replay(session: &Session, affective_core: AffectiveCore) -> ContinuousMind {
+    let mind = ContinuousMind::new_seeded(affective_core, Arc::new(MockLlmClient::default()), session.seed);
+    for turn in &session.turns {
+        apply_turn(&mind, &turn.prompt, &turn.appraisals).await;
+    }
+    mind
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+    use crate::cognitive_appraisal::AffectiveStateChange;
+
+    fn appraisal(emotion: &str, valence: f64, arousal: f64) -> AppraisedEmotion {
+        AppraisedEmotion {
+            emotion: emotion.to_string(),
+            vadn: AffectiveStateChange { valence, arousal, dominance: 0.1, novelty: 0.2 },
+            details: serde_json::json!({ "referent": emotion }),
+        }
+    }
+
+    /// Snapshot as JSON with every timestamp blanked, since those follow the wall clock
+    async fn snapshot_without_timestamps(mind: &ContinuousMind) -> serde_json::Value {
+        fn blank_timestamps(value: &mut serde_json::Value) {
+            match value {
+                serde_json::Value::String(text) if text.parse::<DateTime<Utc>>().is_ok() => *value = serde_json::Value::Null,
+                serde_json::Value::Array(items) => items.iter_mut().for_each(blank_timestamps),
+                serde_json::Value::Object(fields) => fields.values_mut().for_each(blank_timestamps),
+                _ => {}
+            }
+        }
+        let mut snapshot = serde_json::to_value(mind.snapshot().await.unwrap()).unwrap();
+        blank_timestamps(&mut snapshot);
+        snapshot
+    }
+
+    #[tokio::test]
+    async fn test_replay_reaches_identical_state() {
+        let turns = [
+            ("Hi, my name is Sam. I just got great news!", vec![appraisal("Joy", 0.7, 0.6)]),
+            ("But I'm nervous about the move.", vec![appraisal("Worry", -0.4, 0.5), appraisal("Hope", 0.3, 0.2)]),
+        ];
+
+        let mut recorder = SessionRecorder::new(42);
+        let live = ContinuousMind::new_seeded(AffectiveCore::new(), Arc::new(MockLlmClient::default()), 42);
+        for (prompt, appraisals) in &turns {
+            apply_turn(&live, prompt, appraisals).await;
+            recorder.record(prompt, appraisals);
+        }
+
+        let json = recorder.finish().to_json().unwrap();
+        let session = Session::from_json(&json).unwrap();
+        assert_eq!(session.seed, 42);
+
+        let replayed = replay(&session, AffectiveCore::new()).await;
+
+        assert_eq!(snapshot_without_timestamps(&live).await, snapshot_without_timestamps(&replayed).await);
+        let core = replayed.get_affective_core();
+        let core = core.try_lock().unwrap();
+        assert_eq!(core.memory.interaction_count, 2);
+        assert_eq!(core.memory.user_profile.name.as_deref(), Some("Sam"));
+    }
+}
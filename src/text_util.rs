@@ -0,0 +1,106 @@
+//! text_util.rs
+//!
+//! Shared tokenization and stopword filtering for the text analysis paths
+//! (goal triggers, attention suggestions, topic tracking).
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// Default English stopwords
+pub const DEFAULT_STOPWORDS: &[&str] = &[
+    "a", "about", "after", "again", "all", "am", "an", "and", "any", "are", "as", "at",
+    "be", "because", "been", "before", "being", "but", "by", "can", "could", "did", "do",
+    "does", "doing", "for", "from", "had", "has", "have", "having", "he", "her", "here",
+    "hers", "him", "his", "how", "i", "i'm", "i've", "if", "in", "into", "is", "it", "it's",
+    "its", "just", "me", "more", "my", "no", "not", "now", "of", "on", "or", "our", "out",
+    "over", "she", "so", "some", "than", "that", "the", "their", "them", "then", "there",
+    "these", "they", "this", "those", "to", "too", "up", "very", "was", "we", "were", "what",
+    "when", "where", "which", "who", "why", "will", "with", "would", "you", "you're", "your",
+];
+
+/// Splits text into lowercase word tokens and drops stopwords
+#[derive(Debug, Clone)]
+pub struct Tokenizer {
+    stopwords: HashSet<String>,
+}
+
+impl Tokenizer {
+    /// Tokenizer using the default English stopwords
+    pub fn new() -> Self {
+        Self::with_stopwords(DEFAULT_STOPWORDS.iter().copied())
+    }
+
+    /// Tokenizer with a custom stopword list, e.g. for another language
+    pub fn with_stopwords<I, S>(stopwords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Tokenizer {
+            stopwords: stopwords.into_iter().map(|w| w.as_ref().to_lowercase()).collect(),
+        }
+    }
+
+    pub fn is_stopword(&self, word: &str) -> bool {
+        self.stopwords.contains(&word.to_lowercase())
+    }
+
+    /// Lowercase word tokens with punctuation and stopwords removed. Apostrophes inside a
+    /// word are kept ("don't"), those around it are not.
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        text.split(|c: char| !(c.is_alphanumeric() || c == '\''))
+            .map(|word| word.trim_matches('\'').to_lowercase())
+            .filter(|word| !word.is_empty() && !self.stopwords.contains(word))
+            .collect()
+    }
+}
+
+impl Default for Tokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tokenize with the default English tokenizer
+pub fn tokenize(text: &str) -> Vec<String> {
+    static DEFAULT: OnceLock<Tokenizer> = OnceLock::new();
+    DEFAULT.get_or_init(Tokenizer::new).tokenize(text)
+}
+
+/// Whether any token starts with the single-word `keyword`, so "learn" matches "learning"
+pub fn contains_keyword(tokens: &[String], keyword: &str) -> bool {
+    let keyword = keyword.to_lowercase();
+    tokens.iter().any(|token| token.starts_with(&keyword))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_punctuation_and_case() {
+        assert_eq!(
+            tokenize("Hello, WORLD! Don't-stop: 'quoted' words..."),
+            vec!["hello", "world", "don't", "stop", "quoted", "words"]
+        );
+        assert!(tokenize("  ...!!  ").is_empty());
+    }
+
+    #[test]
+    fn test_stopword_removal() {
+        assert_eq!(tokenize("I want to learn about the stars"), vec!["want", "learn", "stars"]);
+
+        let spanish = Tokenizer::with_stopwords(["el", "la", "de", "quiero"]);
+        assert_eq!(spanish.tokenize("Quiero aprender de la luna"), vec!["aprender", "luna"]);
+        // Custom lists replace the English defaults
+        assert_eq!(spanish.tokenize("the moon"), vec!["the", "moon"]);
+    }
+
+    #[test]
+    fn test_contains_keyword_prefix() {
+        let tokens = tokenize("I'm learning to create things");
+        assert!(contains_keyword(&tokens, "learn"));
+        assert!(contains_keyword(&tokens, "Create"));
+        assert!(!contains_keyword(&tokens, "imagine"));
+    }
+}
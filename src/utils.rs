@@ -2,44 +2,83 @@
 //!
 //! Utility functions for logging, error handling, and system setup.
 
+use crate::llm_api::{LlmApiError, LlmProvider};
 use tracing::{info, Level};
 use tracing_subscriber::{EnvFilter, FmtSubscriber};
 use std::env;
 
+/// How log records are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Pretty,
+    /// One JSON object per event, carrying the fields of the span it happened in
+    Json,
+}
+
+impl LogFormat {
+    /// Format for a case-insensitive name ("pretty" or "json")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "pretty" | "text" => Some(LogFormat::Pretty),
+            "json" => Some(LogFormat::Json),
+            _ => None,
+        }
+    }
+}
+
 /// Initialize logging system with appropriate levels
-pub fn init_logging() {
+pub fn init_logging(format: LogFormat) {
     let filter = EnvFilter::from_default_env()
         .add_directive("cogno=info".parse().unwrap())
         .add_directive("reqwest=warn".parse().unwrap())
         .add_directive("hyper=warn".parse().unwrap());
 
-    let subscriber = FmtSubscriber::builder()
+    let builder = FmtSubscriber::builder()
         .with_max_level(Level::INFO)
         .with_env_filter(filter)
         .with_target(false)
         .with_thread_ids(true)
         .with_file(false)
-        .with_line_number(false)
-        .finish();
+        .with_line_number(false);
 
-    tracing::subscriber::set_global_default(subscriber)
-        .expect("Failed to set tracing subscriber");
+    let installed = match format {
+        LogFormat::Pretty => tracing::subscriber::set_global_default(builder.finish()),
+        LogFormat::Json => tracing::subscriber::set_global_default(
+            builder.json().with_current_span(true).with_span_list(false).finish()
+        ),
+    };
+    installed.expect("Failed to set tracing subscriber");
 
     info!("🔧 Logging system initialized");
 }
 
-/// Check if required environment variables are set
-pub fn check_environment() -> Result<(), String> {
-    if env::var("GEMINI_API_KEY").is_err() {
-        return Err("GEMINI_API_KEY environment variable not set. Please set it to use LLM features.".to_string());
+/// Check if required environment variables are set. Offline mode needs none.
+pub fn check_environment(offline: bool) -> Result<(), String> {
+    if offline {
+        info!("📴 Offline mode: LLM calls disabled, using local appraisal");
+        return Ok(());
+    }
+
+    if let Some(key_var) = LlmProvider::from_env().unwrap_or_default().api_key_var()
+        && env::var(key_var).is_err() {
+        return Err(format!("{} environment variable not set. Please set it to use LLM features.", key_var));
     }
     
     info!("✅ Environment variables validated");
     Ok(())
 }
 
-/// Format error messages for user display
-pub fn format_error_for_user<E: std::fmt::Display>(error: &E) -> String {
+/// Format error messages for user display, using `LlmApiError::user_message` when an LLM
+/// error is anywhere in the source chain
+pub fn format_error_for_user(error: &(dyn std::error::Error + 'static)) -> String {
+    let llm_error = std::iter::successors(Some(error), |e| e.source())
+        .find_map(|e| e.downcast_ref::<LlmApiError>());
+    if let Some(llm_error) = llm_error {
+        return llm_error.user_message();
+    }
+
     let error_str = error.to_string();
     
     if error_str.contains("API key") {
@@ -55,7 +94,8 @@ pub fn format_error_for_user<E: std::fmt::Display>(error: &E) -> String {
 
 /// Get a user-friendly system status
 pub fn get_system_status() -> String {
-    let api_status = if env::var("GEMINI_API_KEY").is_ok() {
+    let key_var = LlmProvider::from_env().unwrap_or_default().api_key_var();
+    let api_status = if key_var.is_none_or(|key_var| env::var(key_var).is_ok()) {
         "🟢 API Ready"
     } else {
         "🟡 API Not Configured (local mode)"
@@ -64,6 +104,31 @@ pub fn get_system_status() -> String {
     format!("System Status: {} | Memory: 🟢 Active | Processing: 🟢 Running", api_status)
 }
 
+/// Pearson correlation of two equally long series. Returns 0.0 when there are fewer than
+/// two pairs or either series is constant.
+pub(crate) fn pearson_correlation(xs: &[f64], ys: &[f64]) -> f64 {
+    let n = xs.len().min(ys.len());
+    if n < 2 {
+        return 0.0;
+    }
+
+    let mean_x = xs[..n].iter().sum::<f64>() / n as f64;
+    let mean_y = ys[..n].iter().sum::<f64>() / n as f64;
+
+    let (mut covariance, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in xs[..n].iter().zip(&ys[..n]) {
+        let (dx, dy) = (x - mean_x, y - mean_y);
+        covariance += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+
+    if var_x == 0.0 || var_y == 0.0 {
+        return 0.0;
+    }
+    covariance / (var_x.sqrt() * var_y.sqrt())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,6 +140,35 @@ mod tests {
         assert!(formatted.contains("Network connectivity"));
     }
 
+    #[test]
+    fn test_llm_errors_use_their_user_message() {
+        let error = LlmApiError::RateLimitExceeded { retry_after: None };
+        assert_eq!(format_error_for_user(&error), error.user_message());
+
+        let wrapped = anyhow::Error::new(LlmApiError::EmptyResponse).context("Emotional processing failed");
+        assert_eq!(format_error_for_user(wrapped.as_ref()), LlmApiError::EmptyResponse.user_message());
+    }
+
+    #[test]
+    fn test_pearson_correlation() {
+        assert!((pearson_correlation(&[1.0, 2.0, 3.0], &[2.0, 4.0, 6.0]) - 1.0).abs() < 1e-9);
+        assert!((pearson_correlation(&[1.0, 2.0, 3.0], &[3.0, 2.0, 1.0]) + 1.0).abs() < 1e-9);
+        assert_eq!(pearson_correlation(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0]), 0.0);
+        assert_eq!(pearson_correlation(&[1.0], &[1.0]), 0.0);
+    }
+
+    #[test]
+    fn test_log_format_names() {
+        assert_eq!(LogFormat::from_name("JSON"), Some(LogFormat::Json));
+        assert_eq!(LogFormat::from_name("pretty"), Some(LogFormat::Pretty));
+        assert_eq!(LogFormat::from_name("xml"), None);
+    }
+
+    #[test]
+    fn test_offline_mode_needs_no_api_key() {
+        assert!(check_environment(true).is_ok());
+    }
+
     #[test]
     fn test_system_status() {
         let status = get_system_status();